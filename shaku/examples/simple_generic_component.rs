@@ -32,6 +32,7 @@ struct AnimalServiceImpl<A>
 where
     A: Animal + Default + Interface,
 {
+    #[shaku(default)]
     animal: A,
 }
 
@@ -4,7 +4,7 @@
 
 use shaku::{
     Component, HasComponent, HasProvider, Interface, Module, ModuleBuildContext, ModuleBuilder,
-    Provider, ProviderFn,
+    ModuleInstance, Provider, ProviderFn,
 };
 use std::error::Error;
 use std::fmt::Debug;
@@ -70,6 +70,7 @@ impl Module for SimpleModule {
         }
     }
 }
+impl ModuleInstance for SimpleModule {}
 impl HasComponent<dyn SimpleDependency> for SimpleModule {
     fn build_component(context: &mut ModuleBuildContext<Self>) -> Arc<dyn SimpleDependency> {
         context.build_component::<SimpleDependencyImpl>()
@@ -0,0 +1,104 @@
+//! This module contains trait definitions for asynchronously built components
+
+use crate::parameters;
+use crate::{BuildError, Dependency, Interface, Module, ModuleBuildContext, ParameterViolation};
+use std::error::Error;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// An owned, type-erased future. Traits cannot contain `async fn`s, so the
+/// async traits return this type instead.
+#[cfg(not(feature = "thread_safe"))]
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+/// An owned, type-erased future. Traits cannot contain `async fn`s, so the
+/// async traits return this type instead.
+#[cfg(feature = "thread_safe")]
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Like [`Component`], but the component is built asynchronously. This is
+/// useful for components which need to `.await` during setup, such as
+/// database pools or caches loaded from disk.
+///
+/// Async components are marked with `#[async]` in the [`module`] macro, and the
+/// module must be built with [`ModuleBuilder::build_async`]. Once built, async
+/// components are resolved like any other component.
+///
+/// This trait is normally derived, but if the `derive` feature is turned off
+/// then it will need to be implemented manually.
+///
+/// [`Component`]: trait.Component.html
+/// [`module`]: macro.module.html
+/// [`ModuleBuilder::build_async`]: struct.ModuleBuilder.html#method.build_async
+pub trait AsyncComponent<M: Module>: Interface {
+    /// The trait/interface which this component implements
+    type Interface: Interface + ?Sized;
+
+    /// The parameters this component requires. If none are required, use `()`.
+    #[cfg(feature = "thread_safe")]
+    type Parameters: Default + Send;
+
+    /// The parameters this component requires. If none are required, use `()`.
+    #[cfg(not(feature = "thread_safe"))]
+    type Parameters: Default;
+
+    /// The names of the parameters which do not have a default value. See
+    /// [`Component::REQUIRED_PARAMETERS`].
+    ///
+    /// [`Component::REQUIRED_PARAMETERS`]: trait.Component.html#associatedconstant.REQUIRED_PARAMETERS
+    const REQUIRED_PARAMETERS: &'static [&'static str] = &[];
+
+    /// Create the parameters which are used when they were not set during
+    /// module build. See [`Component::default_parameters`].
+    ///
+    /// [`Component::default_parameters`]: trait.Component.html#method.default_parameters
+    fn default_parameters() -> Result<Self::Parameters, BuildError> {
+        parameters::default_parameters::<Self, _>(Self::REQUIRED_PARAMETERS)
+    }
+
+    /// Check the parameters before the component is built. See
+    /// [`Component::validate_parameters`].
+    ///
+    /// [`Component::validate_parameters`]: trait.Component.html#method.validate_parameters
+    fn validate_parameters(_params: &Self::Parameters) -> Vec<ParameterViolation> {
+        Vec::new()
+    }
+
+    /// Use the build context and parameters to create the component. Other
+    /// components can be resolved by adding a [`HasComponent`] bound to the
+    /// `M` generic, then awaiting [`M::build_component_async`].
+    ///
+    /// [`HasComponent`]: trait.HasComponent.html
+    /// [`M::build_component_async`]: trait.HasComponent.html#method.build_component_async
+    #[allow(clippy::type_complexity)]
+    fn build_async(
+        context: &mut ModuleBuildContext<M>,
+        params: Self::Parameters,
+    ) -> BoxFuture<'_, Result<Box<Self::Interface>, Box<dyn Error + Send + Sync>>>;
+
+    /// Like [`build_async`], but creates the shared instance of the component
+    /// which is stored in the module. See [`Component::try_build_shared`].
+    ///
+    /// By default, this calls [`build_async`].
+    ///
+    /// [`build_async`]: #tymethod.build_async
+    /// [`Component::try_build_shared`]: trait.Component.html#method.try_build_shared
+    #[allow(clippy::type_complexity)]
+    fn build_shared_async(
+        context: &mut ModuleBuildContext<M>,
+        params: Self::Parameters,
+    ) -> BoxFuture<'_, Result<Arc<Self::Interface>, Box<dyn Error + Send + Sync>>> {
+        Box::pin(async move { Self::build_async(context, params).await.map(Arc::from) })
+    }
+
+    /// The dependencies of this component, which are included in
+    /// [`Module::dependency_graph`]. The derive macro lists every injected
+    /// and provided property.
+    ///
+    /// By default, this returns no dependencies.
+    ///
+    /// [`Module::dependency_graph`]: trait.Module.html#method.dependency_graph
+    fn dependencies() -> Vec<Dependency> {
+        Vec::new()
+    }
+}
@@ -0,0 +1,78 @@
+//! This module contains trait definitions for asynchronously provided services
+
+use crate::module::ModuleInstance;
+use crate::{BoxFuture, Dependency, Module};
+use std::error::Error;
+
+/// Like [`Provider`], but the service is created asynchronously. This is
+/// useful for services which need to `.await` when they are created, such as a
+/// database transaction which requires a pool checkout.
+///
+/// Async providers are marked with `#[async]` in the [`module`] macro, and are
+/// used via [`HasAsyncProvider::provide_async`]. Async providers can depend on
+/// components and other async providers.
+///
+/// This trait is normally derived, but if the `derive` feature is turned off
+/// then it will need to be implemented manually.
+///
+/// [`Provider`]: trait.Provider.html
+/// [`module`]: macro.module.html
+/// [`HasAsyncProvider::provide_async`]: trait.HasAsyncProvider.html#tymethod.provide_async
+pub trait AsyncProvider<M: Module>: 'static {
+    /// The trait/interface which this provider implements
+    type Interface: ?Sized;
+
+    /// Provides the service, possibly resolving other components/providers
+    /// to do so.
+    #[allow(clippy::type_complexity)]
+    fn provide_async(
+        module: &M,
+    ) -> BoxFuture<'_, Result<Box<Self::Interface>, Box<dyn Error + Send + Sync>>>;
+
+    /// The dependencies of this provider, which are included in
+    /// [`Module::dependency_graph`]. The derive macro lists every injected
+    /// and provided property.
+    ///
+    /// By default, this returns no dependencies.
+    ///
+    /// [`Module::dependency_graph`]: trait.Module.html#method.dependency_graph
+    fn dependencies() -> Vec<Dependency> {
+        Vec::new()
+    }
+}
+
+/// The type signature of [`AsyncProvider::provide_async`]. This is used when
+/// overriding an async provider via
+/// [`ModuleBuilder::with_async_provider_override`]
+///
+/// [`AsyncProvider::provide_async`]: trait.AsyncProvider.html#tymethod.provide_async
+/// [`ModuleBuilder::with_async_provider_override`]: struct.ModuleBuilder.html#method.with_async_provider_override
+#[cfg(not(feature = "thread_safe"))]
+pub type AsyncProviderFn<M, I> =
+    Box<dyn (for<'a> Fn(&'a M) -> BoxFuture<'a, Result<Box<I>, Box<dyn Error + Send + Sync>>>)>;
+/// The type signature of [`AsyncProvider::provide_async`]. This is used when
+/// overriding an async provider via
+/// [`ModuleBuilder::with_async_provider_override`]
+///
+/// [`AsyncProvider::provide_async`]: trait.AsyncProvider.html#tymethod.provide_async
+/// [`ModuleBuilder::with_async_provider_override`]: struct.ModuleBuilder.html#method.with_async_provider_override
+#[cfg(feature = "thread_safe")]
+pub type AsyncProviderFn<M, I> = Box<
+    dyn (for<'a> Fn(&'a M) -> BoxFuture<'a, Result<Box<I>, Box<dyn Error + Send + Sync>>>)
+        + Send
+        + Sync,
+>;
+
+/// Indicates that a module contains an async provider which implements the
+/// interface.
+pub trait HasAsyncProvider<I: ?Sized>: ModuleInstance {
+    /// Create a service using the async provider registered with the
+    /// interface `I`. Each call will create a new instance of the service.
+    ///
+    /// # Examples
+    /// ```ignore
+    /// let foo: Box<dyn Foo> = module.provide_async().await.unwrap();
+    /// ```
+    #[allow(clippy::type_complexity)]
+    fn provide_async(&self) -> BoxFuture<'_, Result<Box<I>, Box<dyn Error + Send + Sync>>>;
+}
@@ -1,10 +1,14 @@
 //! This module contains trait definitions for components and interfaces
 
-use crate::module::ModuleInterface;
+use crate::module::ModuleInstance;
+use crate::parameters;
 use crate::Module;
-use crate::ModuleBuildContext;
+use crate::{BuildError, Dependency, Lifecycle, ModuleBuildContext, ParameterViolation};
 use std::any::Any;
+use std::error::Error;
 use std::sync::Arc;
+#[cfg(feature = "async")]
+use {crate::BoxFuture, std::future};
 
 /// Components provide a service by implementing an interface. They may use
 /// other components as dependencies.
@@ -23,6 +27,41 @@ pub trait Component<M: Module>: Interface {
     #[cfg(not(feature = "thread_safe"))]
     type Parameters: Default;
 
+    /// The names of the parameters which do not have a default value. If the
+    /// parameters are not set during module build and this list is not empty,
+    /// the build will fail with [`BuildError::MissingParameter`].
+    ///
+    /// [`BuildError::MissingParameter`]: enum.BuildError.html#variant.MissingParameter
+    const REQUIRED_PARAMETERS: &'static [&'static str] = &[];
+
+    /// Create the parameters which are used when they were not set during
+    /// module build. The derive macro reads `#[shaku(env = "...")]`
+    /// parameters from their environment variables here.
+    ///
+    /// By default, this returns the default parameters, or fails with
+    /// [`BuildError::MissingParameter`] if [`REQUIRED_PARAMETERS`] is not
+    /// empty.
+    ///
+    /// [`BuildError::MissingParameter`]: enum.BuildError.html#variant.MissingParameter
+    /// [`REQUIRED_PARAMETERS`]: #associatedconstant.REQUIRED_PARAMETERS
+    fn default_parameters() -> Result<Self::Parameters, BuildError> {
+        parameters::default_parameters::<Self, _>(Self::REQUIRED_PARAMETERS)
+    }
+
+    /// Check the parameters before the component is built. The derive macro
+    /// calls the `#[shaku(validate = ...)]` functions here.
+    ///
+    /// The [`module`] macro validates every component before building any of
+    /// them, so a component with invalid parameters is never built. The
+    /// violations of every component are reported together by
+    /// [`BuildError::InvalidParameters`]. By default, nothing is checked.
+    ///
+    /// [`module`]: macro.module.html
+    /// [`BuildError::InvalidParameters`]: enum.BuildError.html#variant.InvalidParameters
+    fn validate_parameters(_params: &Self::Parameters) -> Vec<ParameterViolation> {
+        Vec::new()
+    }
+
     /// Use the build context and parameters to create the component. Other
     /// components can be resolved by adding a [`HasComponent`] bound to the
     /// `M` generic, then calling [`M::build_component`].
@@ -31,6 +70,64 @@ pub trait Component<M: Module>: Interface {
     /// [`M::build_component`]: trait.HasComponent.html#tymethod.build_component
     fn build(context: &mut ModuleBuildContext<M>, params: Self::Parameters)
         -> Box<Self::Interface>;
+
+    /// Fallible version of [`build`]. Dependencies should be resolved via
+    /// [`M::try_build_component`] so their errors are propagated. Errors
+    /// returned here are reported by [`ModuleBuilder::try_build`].
+    ///
+    /// By default, this calls [`build`].
+    ///
+    /// [`build`]: #tymethod.build
+    /// [`M::try_build_component`]: trait.HasComponent.html#method.try_build_component
+    /// [`ModuleBuilder::try_build`]: struct.ModuleBuilder.html#method.try_build
+    fn try_build(
+        context: &mut ModuleBuildContext<M>,
+        params: Self::Parameters,
+    ) -> Result<Box<Self::Interface>, Box<dyn Error + Send + Sync>> {
+        Ok(Self::build(context, params))
+    }
+
+    /// Like [`try_build`], but creates the shared instance of the component
+    /// which is stored in the module. Components with lifecycle hooks
+    /// override this to run their start hook and register their stop hook via
+    /// [`ModuleBuildContext::lifecycle`].
+    ///
+    /// By default, this calls [`try_build`].
+    ///
+    /// [`try_build`]: #method.try_build
+    /// [`ModuleBuildContext::lifecycle`]: struct.ModuleBuildContext.html#method.lifecycle
+    #[allow(clippy::type_complexity)]
+    fn try_build_shared(
+        context: &mut ModuleBuildContext<M>,
+        params: Self::Parameters,
+    ) -> Result<Arc<Self::Interface>, Box<dyn Error + Send + Sync>> {
+        Self::try_build(context, params).map(Arc::from)
+    }
+
+    /// Run the start hook of an instance of the component, and register its
+    /// stop hook with the module's [`Lifecycle`]. This is called for
+    /// instances which the module did not build, such as the replacement of
+    /// a `#[reloadable]` component.
+    ///
+    /// By default, this does nothing.
+    ///
+    /// [`Lifecycle`]: struct.Lifecycle.html
+    fn start(_component: &Arc<Self>, _lifecycle: &Lifecycle)
+    where
+        Self: Sized,
+    {
+    }
+
+    /// The dependencies of this component, which are included in
+    /// [`Module::dependency_graph`]. The derive macro lists every injected
+    /// and provided property.
+    ///
+    /// By default, this returns no dependencies.
+    ///
+    /// [`Module::dependency_graph`]: trait.Module.html#method.dependency_graph
+    fn dependencies() -> Vec<Dependency> {
+        Vec::new()
+    }
 }
 
 #[cfg(not(feature = "thread_safe"))]
@@ -67,8 +164,24 @@ pub type ComponentFn<M, I> = Box<dyn FnOnce(&mut ModuleBuildContext<M>) -> Box<I
 #[cfg(feature = "thread_safe")]
 pub type ComponentFn<M, I> = Box<dyn (FnOnce(&mut ModuleBuildContext<M>) -> Box<I>) + Send + Sync>;
 
+/// The type signature of a component decorator. The decorator is given the
+/// built component, and returns the component which wraps it. This is used
+/// when decorating a component via [`ModuleBuilder::with_component_decorator`]
+///
+/// [`ModuleBuilder::with_component_decorator`]: struct.ModuleBuilder.html#method.with_component_decorator
+#[cfg(not(feature = "thread_safe"))]
+pub type ComponentDecoratorFn<M, I> = Box<dyn Fn(Arc<I>, &mut ModuleBuildContext<M>) -> Box<I>>;
+/// The type signature of a component decorator. The decorator is given the
+/// built component, and returns the component which wraps it. This is used
+/// when decorating a component via [`ModuleBuilder::with_component_decorator`]
+///
+/// [`ModuleBuilder::with_component_decorator`]: struct.ModuleBuilder.html#method.with_component_decorator
+#[cfg(feature = "thread_safe")]
+pub type ComponentDecoratorFn<M, I> =
+    Box<dyn (Fn(Arc<I>, &mut ModuleBuildContext<M>) -> Box<I>) + Send + Sync>;
+
 /// Indicates that a module contains a component which implements the interface.
-pub trait HasComponent<I: Interface + ?Sized>: ModuleInterface {
+pub trait HasComponent<I: Interface + ?Sized>: ModuleInstance {
     /// Build the component during module build. Usually this involves calling
     /// [`ModuleBuildContext::build_component`] with the implementation.
     ///
@@ -77,6 +190,39 @@ pub trait HasComponent<I: Interface + ?Sized>: ModuleInterface {
     where
         Self: Module + Sized;
 
+    /// Fallible version of [`build_component`]. Usually this involves calling
+    /// [`ModuleBuildContext::try_build_component`] with the implementation.
+    ///
+    /// By default, this calls [`build_component`].
+    ///
+    /// [`build_component`]: #tymethod.build_component
+    /// [`ModuleBuildContext::try_build_component`]: struct.ModuleBuildContext.html#method.try_build_component
+    fn try_build_component(context: &mut ModuleBuildContext<Self>) -> Result<Arc<I>, BuildError>
+    where
+        Self: Module + Sized,
+    {
+        Ok(Self::build_component(context))
+    }
+
+    /// Async version of [`try_build_component`]. Usually this involves
+    /// calling [`ModuleBuildContext::build_async_component`] if the component
+    /// is an [`AsyncComponent`].
+    ///
+    /// By default, this calls [`try_build_component`].
+    ///
+    /// [`try_build_component`]: #method.try_build_component
+    /// [`ModuleBuildContext::build_async_component`]: struct.ModuleBuildContext.html#method.build_async_component
+    /// [`AsyncComponent`]: trait.AsyncComponent.html
+    #[cfg(feature = "async")]
+    fn build_component_async(
+        context: &mut ModuleBuildContext<Self>,
+    ) -> BoxFuture<'_, Result<Arc<I>, BuildError>>
+    where
+        Self: Module + Sized,
+    {
+        Box::pin(future::ready(Self::try_build_component(context)))
+    }
+
     /// Get a reference to the component. The ownership of the component is
     /// shared via `Arc`.
     ///
@@ -135,4 +281,30 @@ pub trait HasComponent<I: Interface + ?Sized>: ModuleInterface {
     /// # }
     /// ```
     fn resolve_ref(&self) -> &I;
+
+    /// Fallible version of [`resolve`]. Modules use this to get the
+    /// components they import from a submodule while they are being built,
+    /// so a submodule which can be missing components at runtime, such as a
+    /// [`DynamicModule`], fails the build instead of panicking.
+    ///
+    /// By default, this calls [`resolve`].
+    ///
+    /// [`resolve`]: #tymethod.resolve
+    /// [`DynamicModule`]: struct.DynamicModule.html
+    fn try_resolve(&self) -> Result<Arc<I>, BuildError> {
+        Ok(self.resolve())
+    }
+
+    /// Get a reference to the component, building it first if it is a lazy
+    /// [`AsyncComponent`]. Lazy async components can only be resolved
+    /// synchronously after they have been resolved once via this method.
+    ///
+    /// By default, this calls [`resolve`].
+    ///
+    /// [`AsyncComponent`]: trait.AsyncComponent.html
+    /// [`resolve`]: #tymethod.resolve
+    #[cfg(feature = "async")]
+    fn resolve_async(&self) -> BoxFuture<'_, Arc<I>> {
+        Box::pin(future::ready(self.resolve()))
+    }
 }
@@ -0,0 +1,278 @@
+//! Renderers which turn a module's [`DependencyGraph`] into diagrams, so
+//! architecture docs can be generated from the [`module`] declarations
+//! instead of being drawn by hand.
+//!
+//! Services are grouped by the module they are registered in. Components are
+//! drawn as boxes, lazy components as rounded boxes, and providers as
+//! hexagons. Services which were overridden via the [`ModuleBuilder`] are
+//! highlighted. Injected dependencies are solid arrows, and provided
+//! dependencies are dashed arrows. Dependencies which are not registered in
+//! the graph (for example, if they are imported from a manually implemented
+//! submodule) point to the interface instead.
+//!
+//! # Example
+//! ```
+//! use shaku::{module, Component, Interface, Module};
+//! use std::sync::Arc;
+//!
+//! trait Logger: Interface {}
+//! trait DateLogger: Interface {}
+//!
+//! #[derive(Component)]
+//! #[shaku(interface = Logger)]
+//! struct LoggerImpl;
+//! impl Logger for LoggerImpl {}
+//!
+//! #[derive(Component)]
+//! #[shaku(interface = DateLogger)]
+//! struct DateLoggerImpl {
+//!     #[shaku(inject)]
+//!     logger: Arc<dyn Logger>,
+//! }
+//! impl DateLogger for DateLoggerImpl {}
+//!
+//! module! {
+//!     MyModule {
+//!         components = [LoggerImpl, DateLoggerImpl],
+//!         providers = []
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let module = MyModule::builder().build();
+//! let dot = shaku::graph::to_dot(&module.dependency_graph());
+//! assert!(dot.starts_with("digraph {"));
+//! # }
+//! ```
+//!
+//! [`DependencyGraph`]: ../struct.DependencyGraph.html
+//! [`module`]: ../macro.module.html
+//! [`ModuleBuilder`]: ../struct.ModuleBuilder.html
+
+use crate::{DependencyGraph, DependencyKind, GraphNode, ServiceKind};
+
+/// Render the graph in the [Graphviz] DOT language. Each module is drawn as a
+/// cluster.
+///
+/// [Graphviz]: https://graphviz.org/
+pub fn to_dot(graph: &DependencyGraph) -> String {
+    let layout = Layout::new(graph);
+    let mut output = String::from("digraph {\n");
+
+    for (module_index, module) in layout.modules.iter().enumerate() {
+        output.push_str(&format!("    subgraph cluster_{} {{\n", module_index));
+        output.push_str(&format!("        label=\"{}\";\n", escape_dot(module)));
+
+        for (index, node) in layout.module_nodes(module) {
+            let (shape, mut styles) = match (node.kind, node.lazy) {
+                (ServiceKind::Component, false) => ("box", vec![]),
+                (ServiceKind::Component, true) => ("box", vec!["rounded", "dashed"]),
+                (ServiceKind::Provider, _) => ("hexagon", vec![]),
+            };
+
+            let mut attributes = format!(
+                "label=\"{}\\n{}\", shape={}",
+                escape_dot(node.service),
+                escape_dot(&interface_label(node)),
+                shape
+            );
+            if node.overridden {
+                styles.push("filled");
+                attributes.push_str(", fillcolor=orange");
+            }
+            if !styles.is_empty() {
+                attributes.push_str(&format!(", style=\"{}\"", styles.join(",")));
+            }
+
+            output.push_str(&format!("        n{} [{}];\n", index, attributes));
+        }
+
+        output.push_str("    }\n");
+    }
+
+    for (index, interface) in layout.unresolved.iter().enumerate() {
+        output.push_str(&format!(
+            "    i{} [label=\"{}\", shape=plaintext];\n",
+            index,
+            escape_dot(interface)
+        ));
+    }
+
+    for edge in &layout.edges {
+        let style = match edge.kind {
+            DependencyKind::Inject => "",
+            DependencyKind::Provide => " [style=dashed]",
+        };
+        output.push_str(&format!("    {} -> {}{};\n", edge.from, edge.to, style));
+    }
+
+    output.push_str("}\n");
+    output
+}
+
+/// Render the graph as a [Mermaid] flowchart. Each module is drawn as a
+/// subgraph.
+///
+/// [Mermaid]: https://mermaid.js.org/
+pub fn to_mermaid(graph: &DependencyGraph) -> String {
+    let layout = Layout::new(graph);
+    let mut output = String::from("flowchart LR\n");
+    let mut overridden = Vec::new();
+
+    for (module_index, module) in layout.modules.iter().enumerate() {
+        output.push_str(&format!(
+            "    subgraph m{} [\"{}\"]\n",
+            module_index,
+            escape_mermaid(module)
+        ));
+
+        for (index, node) in layout.module_nodes(module) {
+            let label = format!(
+                "{}<br/>{}",
+                escape_mermaid(node.service),
+                escape_mermaid(&interface_label(node))
+            );
+            let shape = match (node.kind, node.lazy) {
+                (ServiceKind::Component, false) => format!("[\"{}\"]", label),
+                (ServiceKind::Component, true) => format!("([\"{}\"])", label),
+                (ServiceKind::Provider, _) => format!("{{{{\"{}\"}}}}", label),
+            };
+
+            output.push_str(&format!("        n{}{}\n", index, shape));
+            if node.overridden {
+                overridden.push(format!("n{}", index));
+            }
+        }
+
+        output.push_str("    end\n");
+    }
+
+    for (index, interface) in layout.unresolved.iter().enumerate() {
+        output.push_str(&format!(
+            "    i{}>\"{}\"]\n",
+            index,
+            escape_mermaid(interface)
+        ));
+    }
+
+    for edge in &layout.edges {
+        let arrow = match edge.kind {
+            DependencyKind::Inject => "-->",
+            DependencyKind::Provide => "-.->",
+        };
+        output.push_str(&format!("    {} {} {}\n", edge.from, arrow, edge.to));
+    }
+
+    if !overridden.is_empty() {
+        output.push_str("    classDef overridden fill:orange\n");
+        output.push_str(&format!("    class {} overridden\n", overridden.join(",")));
+    }
+
+    output
+}
+
+/// The modules, interfaces, and edges of a graph, with the identifiers which
+/// the renderers use for them. Nodes are identified by `n{index}`, and
+/// interfaces which are not implemented in the graph by `i{index}`.
+struct Layout<'a> {
+    graph: &'a DependencyGraph,
+    modules: Vec<&'static str>,
+    unresolved: Vec<&'static str>,
+    edges: Vec<LayoutEdge>,
+}
+
+struct LayoutEdge {
+    from: String,
+    to: String,
+    kind: DependencyKind,
+}
+
+impl<'a> Layout<'a> {
+    fn new(graph: &'a DependencyGraph) -> Self {
+        let mut modules = Vec::new();
+        for node in graph.nodes() {
+            if !modules.contains(&node.module) {
+                modules.push(node.module);
+            }
+        }
+
+        let mut unresolved = Vec::new();
+        let mut edges = Vec::new();
+        for edge in graph.edges() {
+            let from = match graph
+                .nodes()
+                .iter()
+                .position(|node| node.service == edge.from && node.module == edge.module)
+            {
+                Some(index) => format!("n{}", index),
+                None => continue,
+            };
+
+            let targets = graph.resolve_edge(edge);
+            if targets.is_empty() {
+                let index = match unresolved.iter().position(|i| *i == edge.to) {
+                    Some(index) => index,
+                    None => {
+                        unresolved.push(edge.to);
+                        unresolved.len() - 1
+                    }
+                };
+
+                edges.push(LayoutEdge {
+                    from,
+                    to: format!("i{}", index),
+                    kind: edge.kind,
+                });
+                continue;
+            }
+
+            for target in targets {
+                let index = graph
+                    .nodes()
+                    .iter()
+                    .position(|node| node == target)
+                    .expect("Resolved nodes are in the graph");
+
+                edges.push(LayoutEdge {
+                    from: from.clone(),
+                    to: format!("n{}", index),
+                    kind: edge.kind,
+                });
+            }
+        }
+
+        Layout {
+            graph,
+            modules,
+            unresolved,
+            edges,
+        }
+    }
+
+    /// The nodes of a module, with their indices
+    fn module_nodes(&self, module: &'a str) -> impl Iterator<Item = (usize, &'a GraphNode)> {
+        self.graph
+            .nodes()
+            .iter()
+            .enumerate()
+            .filter(move |(_, node)| node.module == module)
+    }
+}
+
+/// The interface of a node, with its tag if it is a named component
+fn interface_label(node: &GraphNode) -> String {
+    match node.named {
+        Some(tag) => format!("{} ({})", node.interface, tag),
+        None => node.interface.to_string(),
+    }
+}
+
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_mermaid(text: &str) -> String {
+    text.replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+}
@@ -233,9 +233,11 @@
 //! [`with_component_parameters`] to pass in the parameters.
 //!
 //! Note that if you don't pass in parameters, the parameters' default values will be used. You can
-//! override the default value by annotating the property with `#[shaku(default = ...)]`. If the
-//! parameter should not have a default value, annotate it with `#[shaku(no_default)]`. This will
-//! cause module creation to panic if no value is provided for the parameter.
+//! use the type's default value by annotating the property with `#[shaku(default)]`, or provide
+//! one with `#[shaku(default = ...)]`. Parameters without a default are required: the module
+//! builder tracks which components still need their parameters, and `build` will not compile until
+//! they are passed in (or the components are overridden). Call [`unchecked`] on the builder, or use `try_build`, to check for missing
+//! parameters when the module is built instead.
 //!
 //! ```
 //! # use shaku::{module, Component, Interface};
@@ -284,6 +286,372 @@
 //! # }
 //! ```
 //!
+//! Parameters can also be read from environment variables by annotating them with
+//! `#[shaku(env = "VARIABLE")]`. If the parameters are not passed in, the variable is parsed with
+//! `FromStr`, and the parameter's default value is used if the variable is not set (for example
+//! `#[shaku(env = "LOG_LEVEL", default = "info".to_string())]`). Module creation fails with
+//! [`BuildError::MissingEnvVar`] or [`BuildError::InvalidEnvVar`] if a variable without a default
+//! is not set, or can't be parsed.
+//!
+//! Parameters can be validated before the component is built with
+//! `#[shaku(validate = path::to::fn)]`. On a parameter, the function is given a reference to the
+//! parameter. On the component struct, it is given a reference to the whole `*Parameters` struct.
+//! The function returns a `Result<(), E>` where `E: Display`. Every component is validated before
+//! any of them is built, so a component with invalid parameters is never built. Module creation
+//! fails with one [`BuildError::InvalidParameters`] listing all of the violations (lazy components
+//! are validated when they are built):
+//!
+//! ```
+//! # use shaku::{module, Component, Interface};
+//! #
+//! # trait Server: Interface {}
+//! #
+//! fn non_zero(port: &u16) -> Result<(), &'static str> {
+//!     if *port == 0 { Err("must not be zero") } else { Ok(()) }
+//! }
+//!
+//! #[derive(Component)]
+//! #[shaku(interface = Server)]
+//! struct ServerImpl {
+//!     #[shaku(default = 8080, validate = non_zero)]
+//!     port: u16,
+//! }
+//! # impl Server for ServerImpl {}
+//! #
+//! # module! {
+//! #     MyModule {
+//! #         components = [ServerImpl],
+//! #         providers = []
+//! #     }
+//! # }
+//! #
+//! # fn main() {
+//! let result = MyModule::builder()
+//!     .with_component_parameters::<ServerImpl>(ServerImplParameters { port: 0 })
+//!     .try_build();
+//! assert!(result.is_err());
+//! # }
+//! ```
+//!
+//! ### Loading parameters from configuration
+//! With the `serde` feature enabled, the `*Parameters` structs implement `Deserialize`, and
+//! [`with_parameters_from`] sets the parameters of any of the module's components from a serde
+//! deserializer, such as a parsed TOML, JSON, or YAML file. The configuration is keyed by component
+//! name, and parameters which are left out use their default value:
+//!
+//! ```ignore
+//! // config.toml:
+//! // [DateLoggerImpl]
+//! // today = "Jan 26"
+//! let config: toml::Value = toml::from_str(&std::fs::read_to_string("config.toml")?)?;
+//! let module = MyModule::builder().with_parameters_from(config)?.build();
+//! ```
+//!
+//! ### Handling build errors
+//! Components which can fail to build, such as ones which open files or validate their parameters,
+//! can point to a function with `#[shaku(try_build = ...)]`. The function is given the component
+//! after its dependencies and parameters are set, and returns a `Result` with the finished
+//! component. Use [`ModuleBuilder::try_build`] to get the error instead of a panic. Build errors
+//! are reported as a [`BuildError`].
+//!
+//! ```
+//! use shaku::{module, BuildError, Component, Interface};
+//!
+//! trait Server: Interface {}
+//!
+//! #[derive(Component)]
+//! #[shaku(interface = Server, try_build = ServerImpl::validate)]
+//! struct ServerImpl {
+//!     port: u16,
+//! }
+//! impl Server for ServerImpl {}
+//!
+//! impl ServerImpl {
+//!     fn validate(self) -> Result<Self, String> {
+//!         if self.port == 0 {
+//!             return Err("The port must not be zero".to_string());
+//!         }
+//!
+//!         Ok(self)
+//!     }
+//! }
+//!
+//! module! {
+//!     MyModule {
+//!         components = [ServerImpl],
+//!         providers = []
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let result = MyModule::builder()
+//!     .with_component_parameters::<ServerImpl>(ServerImplParameters { port: 0 })
+//!     .try_build();
+//!
+//! match result {
+//!     Err(BuildError::ComponentBuild { source, .. }) => {
+//!         assert_eq!(source.to_string(), "The port must not be zero");
+//!     }
+//!     _ => panic!("The build should have failed"),
+//! }
+//! # }
+//! ```
+//!
+//! ### Observing the build
+//! To find out which components slow down the module build, register a [`BuildObserver`] via
+//! [`ModuleBuilder::with_observer`]. The built-in [`BuildReport`] records each component in the
+//! order it was built, whether it came from an override, and how long it took to build (both with
+//! and without its dependencies). Lazy components are reported when they are first resolved.
+//!
+//! ```ignore
+//! let report = BuildReport::new();
+//! let module = MyModule::builder().with_observer(report.clone()).build();
+//!
+//! for component in report.slowest() {
+//!     println!("{}: {:?}", component.info.component, component.timing.own);
+//! }
+//! ```
+//!
+//! ### Lifecycle hooks
+//! Components can run code after they are built, and when the module is shut down, via
+//! `#[shaku(on_start = ...)]` and `#[shaku(on_stop = ...)]`. Both point to a function which takes
+//! `&self`. Start hooks run as soon as the component is built, so a component's dependencies are
+//! started before it. [`Module::shutdown`] runs the stop hooks in the reverse order, and then shuts
+//! down the submodules. Each stop hook only runs once, even if the module is shut down multiple
+//! times or a submodule is shared by multiple modules.
+//!
+//! ```
+//! use shaku::{module, Component, Interface, Module};
+//!
+//! trait Server: Interface {}
+//!
+//! #[derive(Component)]
+//! #[shaku(interface = Server, on_start = ServerImpl::listen, on_stop = ServerImpl::close)]
+//! struct ServerImpl;
+//! impl Server for ServerImpl {}
+//!
+//! impl ServerImpl {
+//!     fn listen(&self) {
+//!         println!("Listening");
+//!     }
+//!
+//!     fn close(&self) {
+//!         println!("Closing");
+//!     }
+//! }
+//!
+//! module! {
+//!     MyModule {
+//!         components = [ServerImpl],
+//!         providers = []
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let module = MyModule::builder().build(); // Prints "Listening"
+//! module.shutdown(); // Prints "Closing"
+//! # }
+//! ```
+//!
+//! ### Named components
+//! A module can only contain one component of each interface, unless the components are named.
+//! Mark a component with `#[named(Tag)]` in the module, where `Tag` is any type (usually an empty
+//! struct). Named components are injected with `#[shaku(inject, named = Tag)]`, and resolved via
+//! [`HasNamedComponent`]. Named components can be configured with
+//! [`with_named_component_parameters`], and overridden with [`with_named_component_override`].
+//!
+//! ```
+//! use shaku::{module, Component, HasNamedComponent, Interface};
+//! use std::sync::Arc;
+//!
+//! trait Database: Interface {}
+//! trait Repository: Interface {}
+//!
+//! struct Replica;
+//!
+//! #[derive(Component)]
+//! #[shaku(interface = Database)]
+//! struct DatabaseImpl {
+//!     url: String,
+//! }
+//! impl Database for DatabaseImpl {}
+//!
+//! #[derive(Component)]
+//! #[shaku(interface = Repository)]
+//! struct RepositoryImpl {
+//!     #[shaku(inject)]
+//!     primary: Arc<dyn Database>,
+//!     #[shaku(inject, named = Replica)]
+//!     replica: Arc<dyn Database>,
+//! }
+//! impl Repository for RepositoryImpl {}
+//!
+//! module! {
+//!     MyModule {
+//!         components = [RepositoryImpl, DatabaseImpl, #[named(Replica)] DatabaseImpl],
+//!         providers = []
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let module = MyModule::builder()
+//!     .with_component_parameters::<DatabaseImpl>(DatabaseImplParameters {
+//!         url: "postgres://primary".to_string(),
+//!     })
+//!     .with_named_component_parameters::<DatabaseImpl, Replica>(DatabaseImplParameters {
+//!         url: "postgres://replica".to_string(),
+//!     })
+//!     .build();
+//!
+//! let replica: &dyn Database = HasNamedComponent::<dyn Database, Replica>::resolve_named_ref(&module);
+//! # }
+//! ```
+//!
+//! ### Multi-bindings
+//! Plugin-style systems (middlewares, validators, event listeners) often have any number of
+//! components which implement the same interface. Add them to a set with
+//! `#[multi(dyn Interface)]` in the module, and inject the whole set as a
+//! `Vec<Arc<dyn Interface>>`. The set can be resolved via [`HasComponents`], and also contains the
+//! components imported from submodules with `#[multi] dyn Interface`.
+//!
+//! ```
+//! use shaku::{module, Component, HasComponent, Interface};
+//! use std::sync::Arc;
+//!
+//! trait Listener: Interface {}
+//! trait EventBus: Interface {}
+//!
+//! #[derive(Component)]
+//! #[shaku(interface = Listener)]
+//! struct AuditListener;
+//! impl Listener for AuditListener {}
+//!
+//! #[derive(Component)]
+//! #[shaku(interface = Listener)]
+//! struct MetricsListener;
+//! impl Listener for MetricsListener {}
+//!
+//! #[derive(Component)]
+//! #[shaku(interface = EventBus)]
+//! struct EventBusImpl {
+//!     #[shaku(inject)]
+//!     listeners: Vec<Arc<dyn Listener>>,
+//! }
+//! impl EventBus for EventBusImpl {}
+//!
+//! module! {
+//!     MyModule {
+//!         components = [
+//!             EventBusImpl,
+//!             #[multi(dyn Listener)] AuditListener,
+//!             #[multi(dyn Listener)] MetricsListener
+//!         ],
+//!         providers = []
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let module = MyModule::builder().build();
+//! let event_bus: &dyn EventBus = module.resolve_ref();
+//! # }
+//! ```
+//!
+//! ### Map bindings
+//! When the implementation is chosen at runtime (CLI commands, export formats, payment
+//! providers), give each multi-bound component a key with `#[key("name")]`. These components are
+//! added to a map instead of a set, which is injected as a `HashMap<&'static str, Arc<dyn Interface>>`
+//! and can be resolved via [`HasComponentMap`]. Submodule maps are imported with
+//! `#[key] dyn Interface`. Duplicate keys are rejected: within a module at compile time, and
+//! across submodules when the module is built.
+//!
+//! ```
+//! use shaku::{module, Component, HasComponentMap, Interface};
+//! use std::collections::HashMap;
+//! use std::sync::Arc;
+//!
+//! trait Command: Interface {}
+//! trait Cli: Interface {}
+//!
+//! #[derive(Component)]
+//! #[shaku(interface = Command)]
+//! struct ExportCommand;
+//! impl Command for ExportCommand {}
+//!
+//! #[derive(Component)]
+//! #[shaku(interface = Command)]
+//! struct ImportCommand;
+//! impl Command for ImportCommand {}
+//!
+//! #[derive(Component)]
+//! #[shaku(interface = Cli)]
+//! struct CliImpl {
+//!     #[shaku(inject)]
+//!     commands: HashMap<&'static str, Arc<dyn Command>>,
+//! }
+//! impl Cli for CliImpl {}
+//!
+//! module! {
+//!     MyModule {
+//!         components = [
+//!             CliImpl,
+//!             #[multi(dyn Command)] #[key("export")] ExportCommand,
+//!             #[multi(dyn Command)] #[key("import")] ImportCommand
+//!         ],
+//!         providers = []
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let module = MyModule::builder().build();
+//! let export: Option<Arc<dyn Command>> = module.resolve_keyed("export");
+//! # assert!(export.is_some());
+//! # }
+//! ```
+//!
+//! ### Async components
+//! With the `async` feature enabled, components which need to `.await` while building (database
+//! pools, HTTP clients, etc) can derive [`AsyncComponent`] instead of `Component`. The
+//! `try_build` function of an async component is an `async fn`. Mark async components with
+//! `#[async]` in the module, and build the module with [`ModuleBuilder::build_async`]. Async
+//! components are built in dependency order, and normal components can depend on them.
+//!
+//! Async components can also be `#[lazy]`. They are built the first time they are resolved via
+//! [`HasComponent::resolve_async`].
+//!
+//! ```ignore
+//! use shaku::{module, AsyncComponent, Interface};
+//!
+//! trait Database: Interface {}
+//!
+//! #[derive(AsyncComponent)]
+//! #[shaku(interface = Database, try_build = DatabaseImpl::connect)]
+//! struct DatabaseImpl {
+//!     url: String,
+//! }
+//! impl Database for DatabaseImpl {}
+//!
+//! impl DatabaseImpl {
+//!     async fn connect(self) -> Result<Self, std::io::Error> {
+//!         // Connect to the database...
+//!         Ok(self)
+//!     }
+//! }
+//!
+//! module! {
+//!     MyModule {
+//!         components = [#[async] DatabaseImpl],
+//!         providers = []
+//!     }
+//! }
+//!
+//! let module = MyModule::builder()
+//!     .with_async_component_parameters::<DatabaseImpl>(DatabaseImplParameters {
+//!         url: "postgres://localhost".to_string(),
+//!     })
+//!     .build_async()
+//!     .await;
+//! ```
+//!
 //! ## Resolve components
 //! Once you created the module, you can resolve the components using the module's [`HasComponent`]
 //! methods.
@@ -340,6 +708,10 @@
 //! # }
 //! ```
 //!
+//! Generic code which can't name the interfaces statically, such as an admin console, can use
+//! [`ResolveAny`] instead. It lists the interfaces of the module's components (including the ones
+//! imported from submodules) and resolves them by their `TypeId`.
+//!
 //! ## Overriding components
 //! Although shaku is a compile time DI library, you can override the implementation of a service
 //! during the module build. This can be useful during testing, for example using an in-memory
@@ -407,6 +779,101 @@
 //! # }
 //! ```
 //!
+//! Overrides and parameters which are never used, such as the parameters of a component which was
+//! overridden, are ignored by default. Call [`ModuleBuilder::strict`] before building to fail the
+//! build with [`BuildError::UnusedEntries`] instead, so a misconfigured test doesn't pass
+//! vacuously.
+//!
+//! ### Replacing components at runtime
+//! Overrides are fixed once the module is built. Components which need to change afterwards, such
+//! as credentials which are rotated, can be marked with `#[reloadable]` in the module declaration
+//! and replaced via [`Module::replace`]:
+//!
+//! ```ignore
+//! module! {
+//!     MyModule {
+//!         components = [#[reloadable] LoggerImpl, DateLoggerImpl],
+//!         providers = []
+//!     }
+//! }
+//!
+//! let old_logger = module.replace::<dyn Logger>(Box::new(LoggerImpl::new("new.log")));
+//! ```
+//!
+//! The replacement is an instance of the declared component. Its lifecycle hooks run and the
+//! module's decorators are applied to it, like the instance built by the module. `resolve` returns
+//! the new instance, as do providers and lazy components which are created afterwards. Components
+//! which were injected with the old instance (`DateLoggerImpl` above) keep using it. Only the
+//! latest instance is kept, so `resolve_ref` can't be used on a reloadable component. See
+//! [`HasReloadableComponent`] for the details.
+//!
+//! ### Decorating components
+//! Cross-cutting behavior, such as caching, metrics, or retries, can be added by wrapping a
+//! component in another implementation of its interface. Pass a decorator to
+//! [`with_component_decorator`]. It is given the built (or overridden) component and the build
+//! context, and returns the wrapper. Decorators are applied in registration order, so the last one
+//! registered is the outermost. [`with_provider_decorator`] does the same for each service a
+//! provider provides.
+//!
+//! `with_component_decorator` only decorates the unnamed component of an interface. Named,
+//! multi-bound and keyed components each have their own decorators, registered via
+//! [`with_named_component_decorator`], [`with_multi_component_decorator`] and
+//! [`with_keyed_component_decorator`].
+//!
+//! ```ignore
+//! struct TimedLogger {
+//!     inner: Arc<dyn Logger>,
+//! }
+//!
+//! impl Logger for TimedLogger {
+//!     fn log(&self, content: &str) {
+//!         let start = Instant::now();
+//!         self.inner.log(content);
+//!         println!("Logged in {:?}", start.elapsed());
+//!     }
+//! }
+//!
+//! let module = MyModule::builder()
+//!     .with_component_decorator::<dyn Logger>(Box::new(|inner, _| Box::new(TimedLogger { inner })))
+//!     .build();
+//! ```
+//!
+//! Wrappers which only add logging or timing around each method don't need to be written by hand.
+//! Annotate the interface trait with [`#[shaku::interface]`][interface attribute] to generate a
+//! `LoggerProxy` which delegates every method to the inner component through an [`Interceptor`]:
+//!
+//! ```ignore
+//! #[shaku::interface]
+//! trait Logger: Interface {
+//!     fn log(&self, content: &str);
+//! }
+//!
+//! let module = MyModule::builder()
+//!     .with_component_decorator::<dyn Logger>(Box::new(|inner, _| {
+//!         Box::new(LoggerProxy::new(inner, TimingInterceptor))
+//!     }))
+//!     .build();
+//! ```
+//!
+//! ## Inspecting the dependency graph
+//! A built module can describe its wiring via [`Module::dependency_graph`]. The returned
+//! [`DependencyGraph`] contains a node for each component and provider (including those of the
+//! submodules services are imported from) and an edge for each injected or provided dependency.
+//! This is useful for auditing the wiring of a module in tests:
+//!
+//! ```ignore
+//! let graph = module.dependency_graph();
+//! let logger_users: Vec<&str> = graph
+//!     .dependents_of(std::any::type_name::<dyn Logger>())
+//!     .map(|edge| edge.from)
+//!     .collect();
+//! assert_eq!(logger_users, vec![std::any::type_name::<DateLoggerImpl>()]);
+//! ```
+//!
+//! The graph can also be rendered as a diagram via [`graph::to_dot`] or [`graph::to_mermaid`],
+//! which keeps architecture docs in sync with the module declarations. Overridden services are
+//! highlighted.
+//!
 //! ## The full example
 //! ```
 //! use shaku::{module, Component, Interface, HasComponent};
@@ -478,8 +945,43 @@
 //! [module macro]: ../macro.module.html
 //! [`ModuleBuilder::with_submodules`]: ../struct.ModuleBuilder.html#method.with_submodules
 //! [`ModuleBuilder::build`]: ../struct.ModuleBuilder.html#method.build
+//! [`ModuleBuilder::try_build`]: ../struct.ModuleBuilder.html#method.try_build
+//! [`BuildError`]: ../enum.BuildError.html
+//! [`BuildObserver`]: ../trait.BuildObserver.html
+//! [`BuildReport`]: ../struct.BuildReport.html
+//! [`ModuleBuilder::with_observer`]: ../struct.ModuleBuilder.html#method.with_observer
+//! [`Module::shutdown`]: ../trait.Module.html#method.shutdown
+//! [`Module::replace`]: ../trait.Module.html#method.replace
+//! [`HasReloadableComponent`]: ../trait.HasReloadableComponent.html
+//! [`Module::dependency_graph`]: ../trait.Module.html#method.dependency_graph
+//! [`DependencyGraph`]: ../struct.DependencyGraph.html
+//! [`graph::to_dot`]: ../graph/fn.to_dot.html
+//! [`graph::to_mermaid`]: ../graph/fn.to_mermaid.html
+//! [`HasNamedComponent`]: ../trait.HasNamedComponent.html
+//! [`HasComponents`]: ../trait.HasComponents.html
+//! [`HasComponentMap`]: ../trait.HasComponentMap.html
+//! [`with_named_component_parameters`]: ../struct.ModuleBuilder.html#method.with_named_component_parameters
+//! [`with_named_component_override`]: ../struct.ModuleBuilder.html#method.with_named_component_override
+//! [`AsyncComponent`]: ../trait.AsyncComponent.html
+//! [`ModuleBuilder::build_async`]: ../struct.ModuleBuilder.html#method.build_async
+//! [`HasComponent::resolve_async`]: ../trait.HasComponent.html#method.resolve_async
 //! [`with_component_parameters`]: ../struct.ModuleBuilder.html#method.with_component_parameters
+//! [`unchecked`]: ../struct.ModuleBuilder.html#method.unchecked
+//! [`with_parameters_from`]: ../struct.ModuleBuilder.html#method.with_parameters_from
+//! [`BuildError::MissingEnvVar`]: ../enum.BuildError.html#variant.MissingEnvVar
+//! [`BuildError::InvalidEnvVar`]: ../enum.BuildError.html#variant.InvalidEnvVar
+//! [`BuildError::InvalidParameters`]: ../enum.BuildError.html#variant.InvalidParameters
 //! [`with_component_override`]: ../struct.ModuleBuilder.html#method.with_component_override
+//! [`ModuleBuilder::strict`]: ../struct.ModuleBuilder.html#method.strict
+//! [`BuildError::UnusedEntries`]: ../enum.BuildError.html#variant.UnusedEntries
+//! [`with_component_decorator`]: ../struct.ModuleBuilder.html#method.with_component_decorator
+//! [`with_provider_decorator`]: ../struct.ModuleBuilder.html#method.with_provider_decorator
+//! [`with_named_component_decorator`]: ../struct.ModuleBuilder.html#method.with_named_component_decorator
+//! [`with_multi_component_decorator`]: ../struct.ModuleBuilder.html#method.with_multi_component_decorator
+//! [`with_keyed_component_decorator`]: ../struct.ModuleBuilder.html#method.with_keyed_component_decorator
+//! [interface attribute]: ../attr.interface.html
+//! [`Interceptor`]: ../trait.Interceptor.html
+//! [`ResolveAny`]: ../trait.ResolveAny.html
 
 pub mod provider;
 pub mod submodules;
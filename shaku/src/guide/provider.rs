@@ -348,6 +348,90 @@
 //! # }
 //! ```
 //!
+//! ## Scoped providers
+//! Some provided services should be shared for a unit of work, such as a database transaction
+//! which every repository in one HTTP request uses. Mark their providers with `#[scoped]` in the
+//! module, and inject them as an `Arc` instead of a `Box`. Create a [`Scope`] per unit of work
+//! via [`Module::create_scope`]:
+//!
+//! - [`Scope::provide`] creates a scoped service the first time it is requested, and returns the
+//!   same instance afterwards.
+//! - [`HasProvider::provide_scoped`] creates an unscoped service whose scoped dependencies come
+//!   from the scope.
+//! - Components are still resolved from the module, via [`Scope::module`].
+//!
+//! The scoped services are dropped with the scope. Outside of a scope, each call to
+//! [`HasProvider::provide`] uses a new scope.
+//!
+//! ```
+//! use shaku::{module, HasProvider, Interface, Module, Provider};
+//! use std::sync::Arc;
+//!
+//! trait Transaction: Interface {}
+//! trait UserRepository {}
+//!
+//! #[derive(Provider)]
+//! #[shaku(interface = Transaction)]
+//! struct TransactionImpl;
+//! impl Transaction for TransactionImpl {}
+//!
+//! #[derive(Provider)]
+//! #[shaku(interface = UserRepository)]
+//! struct UserRepositoryImpl {
+//!     #[shaku(provide)]
+//!     transaction: Arc<dyn Transaction>,
+//! }
+//! impl UserRepository for UserRepositoryImpl {}
+//!
+//! module! {
+//!     ExampleModule {
+//!         components = [],
+//!         providers = [#[scoped] TransactionImpl, UserRepositoryImpl]
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let module = ExampleModule::builder().build();
+//!
+//! // For example, at the start of an HTTP request
+//! let scope = module.create_scope();
+//! let transaction: Arc<dyn Transaction> = scope.provide().unwrap();
+//! let repository: Box<dyn UserRepository> =
+//!     HasProvider::<dyn UserRepository>::provide_scoped(&scope).unwrap();
+//! # }
+//! ```
+//!
+//! ## Async providers
+//! With the `async` feature enabled, providers which need to `.await` (for example, to check out a
+//! connection from an async pool) can derive [`AsyncProvider`] instead of `Provider`. Mark them
+//! with `#[async]` in the module, and create services with [`HasAsyncProvider::provide_async`].
+//! The `#[shaku(provide)]` properties of an async provider are resolved from other async
+//! providers. Async providers are overridden via [`with_async_provider_override`].
+//!
+//! ```ignore
+//! use shaku::{module, AsyncProvider, HasAsyncProvider};
+//!
+//! #[derive(AsyncProvider)]
+//! #[shaku(interface = Repository)]
+//! struct RepositoryImpl {
+//!     #[shaku(provide)]
+//!     transaction: Box<dyn Transaction>,
+//! }
+//!
+//! module! {
+//!     ExampleModule {
+//!         components = [DatabasePool],
+//!         providers = [#[async] TransactionImpl, #[async] RepositoryImpl]
+//!     }
+//! }
+//!
+//! let module = ExampleModule::builder().build();
+//! let repository: Box<dyn Repository> = module.provide_async().await?;
+//! ```
+//!
+//! When the `thread_safe` feature is enabled, the futures must be `Send`. Services which are held
+//! across an `.await` in an async provider must be `Send`.
+//!
 //! ## The full example
 //! ```
 //! use shaku::{module, Component, HasComponent, HasProvider, Interface, Module, Provider};
@@ -446,3 +530,11 @@
 //! [`Provider::provide`]: ../../trait.Provider.html#tymethod.provide
 //! [`HasProvider::provide`]: ../../trait.HasProvider.html#tymethod.provide
 //! [`with_provider_override`]: ../../struct.ModuleBuilder.html#method.with_provider_override
+//! [`AsyncProvider`]: ../../trait.AsyncProvider.html
+//! [`HasAsyncProvider::provide_async`]: ../../trait.HasAsyncProvider.html#tymethod.provide_async
+//! [`with_async_provider_override`]: ../../struct.ModuleBuilder.html#method.with_async_provider_override
+//! [`Scope`]: ../../struct.Scope.html
+//! [`Scope::provide`]: ../../struct.Scope.html#method.provide
+//! [`Scope::module`]: ../../struct.Scope.html#method.module
+//! [`Module::create_scope`]: ../../trait.Module.html#method.create_scope
+//! [`HasProvider::provide_scoped`]: ../../trait.HasProvider.html#method.provide_scoped
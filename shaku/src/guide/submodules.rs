@@ -104,7 +104,58 @@
 //! implement `AuthModule`. It does this by adding `: AuthModule` after the name of the module.
 //! This is shorthand for the statement `impl AuthModule for AuthModuleImpl {}`.
 //!
+//! ## Registering services at runtime
+//! When the implementations are only known at runtime, such as plugins, use a [`DynamicModule`]
+//! as the submodule. Its components and providers are registered on its builder. The interfaces
+//! which the root module imports must be declared via [`dynamic_interfaces`], and are then
+//! imported like those of any other submodule:
+//!
+//! ```
+//! # use shaku::{dynamic_interfaces, module, Component, DynamicModule, HasComponent, Interface};
+//! # use std::sync::Arc;
+//! #
+//! # trait AuthManager: Interface {}
+//! #
+//! # #[derive(Component)]
+//! # #[shaku(interface = AuthManager)]
+//! # struct AuthManagerImpl;
+//! # impl AuthManager for AuthManagerImpl {}
+//! #
+//! dynamic_interfaces! {
+//!     components = [dyn AuthManager],
+//!     providers = []
+//! }
+//!
+//! module! {
+//!     RootModule {
+//!         components = [],
+//!         providers = [],
+//!
+//!         use DynamicModule {
+//!             components = [dyn AuthManager],
+//!             providers = []
+//!         }
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let plugins = DynamicModule::builder()
+//!     .with_component::<AuthManagerImpl>()
+//!     .build();
+//! let root_module = RootModule::builder(Arc::new(plugins)).build();
+//!
+//! let auth_manager: &dyn AuthManager = root_module.resolve_ref();
+//! # }
+//! ```
+//!
+//! If an imported interface was not registered, building a component of the root module which
+//! depends on it fails with [`BuildError::UnregisteredComponent`], and resolving it from the root
+//! module panics.
+//!
 //! [getting started guide]: ../index.html
 //! [`Module`]: ../../trait.Module.html
 //! [module macro]: ../../macro.module.html
 //! [`ModuleBuilder`]: ../../struct.ModuleBuilder.html
+//! [`DynamicModule`]: ../../struct.DynamicModule.html
+//! [`dynamic_interfaces`]: ../../macro.dynamic_interfaces.html
+//! [`BuildError::UnregisteredComponent`]: ../../enum.BuildError.html#variant.UnregisteredComponent
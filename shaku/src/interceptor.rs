@@ -0,0 +1,54 @@
+//! This module contains the callbacks used by the proxies generated by the
+//! `#[shaku::interface]` attribute
+
+use crate::Interface;
+use std::fmt::Debug;
+use std::time::Duration;
+
+/// Identifies a method call which is intercepted by a generated proxy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MethodCall {
+    /// The name of the interface trait
+    pub interface: &'static str,
+    /// The name of the called method
+    pub method: &'static str,
+}
+
+/// Callbacks which are run around every method call of a proxy generated by
+/// the [`interface`] attribute. All of the callbacks do nothing by default.
+///
+/// # Example
+/// ```
+/// use shaku::{Interceptor, MethodCall};
+/// use std::fmt::Debug;
+/// use std::time::Duration;
+///
+/// struct Timing;
+///
+/// impl Interceptor for Timing {
+///     fn after(&self, call: &MethodCall, elapsed: Duration) {
+///         println!("{}::{} took {:?}", call.interface, call.method, elapsed);
+///     }
+///
+///     fn error(&self, call: &MethodCall, error: &dyn Debug, _elapsed: Duration) {
+///         println!("{}::{} failed: {:?}", call.interface, call.method, error);
+///     }
+/// }
+/// ```
+///
+/// [`interface`]: attr.interface.html
+pub trait Interceptor: Interface {
+    /// Called before the method is delegated to the inner service, with the
+    /// method's arguments (not including `self`).
+    fn before(&self, _call: &MethodCall, _arguments: &[&dyn Debug]) {}
+
+    /// Called after the method returns. Methods which return a `Result` only
+    /// call this on success.
+    fn after(&self, _call: &MethodCall, _elapsed: Duration) {}
+
+    /// Called instead of [`after`] when a method which returns a `Result`
+    /// returns an error.
+    ///
+    /// [`after`]: #method.after
+    fn error(&self, _call: &MethodCall, _error: &dyn Debug, _elapsed: Duration) {}
+}
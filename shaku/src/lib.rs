@@ -11,11 +11,29 @@
 //!
 //! - `thread_safe`: Requires components to be `Send + Sync`
 //! - `derive`: Uses the `shaku_derive` crate to provide proc-macro derives of `Component` and
-//!   `Provider`, and the `module` macro.
+//!   `Provider`, the `module` macro, and the `interface` attribute.
+//!
+//! The following features are disabled by default:
+//!
+//! - `async`: Enables components which are built asynchronously and providers which provide
+//!   services asynchronously. See [`AsyncComponent`] and [`AsyncProvider`].
+//! - `tracing`: Emits [`tracing`] spans when the module is built (`shaku::build_module`), when each
+//!   component is built (`shaku::build_component`), when a lazy component is initialized
+//!   (`shaku::lazy_component`), and when a provider is called (`shaku::provide`). Build and
+//!   provider errors are emitted as error events within these spans.
+//! - `serde`: Derives `Deserialize` for the generated `*Parameters` structs, so component parameters
+//!   can be loaded from configuration files via [`ModuleBuilder::with_parameters_from`].
+//! - `mock`: Enables `#[shaku::interface(mock)]`, which generates a mock implementation of an
+//!   interface trait for use in tests. See the [`interface`] attribute.
 //!
 //! [Rocket]: https://rocket.rs
 //! [`shaku_rocket`]: https://crates.io/crates/shaku_rocket
 //! [getting started guide]: guide/index.html
+//! [`AsyncComponent`]: trait.AsyncComponent.html
+//! [`AsyncProvider`]: trait.AsyncProvider.html
+//! [`tracing`]: https://crates.io/crates/tracing
+//! [`ModuleBuilder::with_parameters_from`]: struct.ModuleBuilder.html#method.with_parameters_from
+//! [`interface`]: attr.interface.html
 
 // This lint is ignored because proc-macros aren't allowed in statement position
 // (at least until 1.45). Removing the main function makes rustdoc think the
@@ -26,16 +44,35 @@
 // Modules
 #[macro_use]
 mod trait_alias;
+#[cfg(feature = "async")]
+mod async_component;
+#[cfg(feature = "async")]
+mod async_provider;
 mod component;
+mod interceptor;
+mod map_component;
+#[cfg(feature = "mock")]
+mod mock;
 mod module;
+mod multi_component;
+mod named_component;
 mod parameters;
 mod provider;
+mod reloadable;
+mod resolve_any;
+mod scope;
+mod trace;
 
+pub mod graph;
 pub mod guide;
 
 // Reexport proc macros
 #[cfg(feature = "derive")]
-pub use {shaku_derive::module, shaku_derive::Component, shaku_derive::Provider};
+pub use {
+    shaku_derive::interface, shaku_derive::module, shaku_derive::Component, shaku_derive::Provider,
+};
+#[cfg(all(feature = "derive", feature = "async"))]
+pub use {shaku_derive::AsyncComponent, shaku_derive::AsyncProvider};
 
 // Reexport OnceCell to support lazy components
 #[doc(hidden)]
@@ -45,5 +82,45 @@ pub use once_cell::sync::OnceCell;
 #[cfg(not(feature = "thread_safe"))]
 pub use once_cell::unsync::OnceCell;
 
+// Reexport async-lock to support lazy async components
+#[doc(hidden)]
+#[cfg(feature = "async")]
+pub use async_lock::{Mutex as AsyncMutex, OnceCell as AsyncOnceCell};
+
+// Reexport serde to support deserializing parameters
+#[doc(hidden)]
+#[cfg(feature = "serde")]
+pub use serde_crate as serde;
+
+// Reexport parameter helpers to support environment variable parameters
+#[doc(hidden)]
+pub use crate::parameters::env_parameter;
+
+// Reexport tracing helpers to support lazy components
+#[doc(hidden)]
+pub use crate::trace::trace_lazy_component;
+#[doc(hidden)]
+#[cfg(feature = "async")]
+pub use crate::trace::trace_lazy_component_async;
+
+// Reexport the compile-time tracking of the module builder's parameters and
+// overrides, which is only used by the output of the derive macros
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "async")]
+    pub use crate::module::parameter_state::AsyncComponentInterface;
+    pub use crate::module::parameter_state::{
+        ApplyOverride, ComponentInterface, ComponentOverridden, OverrideComponent, ParametersReady,
+        ParametersRequired, ParametersSet, ParametersUnset, ParametersUntracked,
+        RequiredParameters, RequiredParametersSet, SetParameters,
+    };
+}
+
 // Expose a flat module structure
-pub use crate::{component::*, module::*, provider::*};
+#[cfg(feature = "mock")]
+pub use crate::mock::*;
+#[cfg(feature = "async")]
+pub use crate::{async_component::*, async_provider::*};
+pub use crate::{component::*, interceptor::*, map_component::*, module::*};
+pub use crate::{multi_component::*, named_component::*, provider::*};
+pub use crate::{reloadable::*, resolve_any::*, scope::*};
@@ -0,0 +1,120 @@
+//! This module contains the trait definition for keyed (map) multi-bound
+//! components
+
+use crate::module::ModuleInstance;
+use crate::{BuildError, Interface, Module, ModuleBuildContext};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// Indicates that a module contains a map of components which implement the
+/// interface, keyed by name. This is useful for command registries or
+/// strategy lookups, where the implementation is chosen at runtime.
+///
+/// Components are added to the map with `#[multi(dyn Interface)]` and
+/// `#[key("name")]` in the [module macro], and the whole map is injected with
+/// `#[shaku(inject)] commands: HashMap<&'static str, Arc<dyn Command>>`. The
+/// map also contains the components imported from submodules with
+/// `#[key] dyn Interface`. Keys must be unique: a duplicate key within a
+/// module is a compile error, and a duplicate key across submodules fails the
+/// module build with [`BuildError::DuplicateKey`].
+///
+/// [module macro]: macro.module.html
+/// [`BuildError::DuplicateKey`]: enum.BuildError.html#variant.DuplicateKey
+pub trait HasComponentMap<I: Interface + ?Sized>: ModuleInstance {
+    /// Build the components during module build. Usually this involves
+    /// calling [`ModuleBuildContext::build_keyed_component`] with each
+    /// implementation.
+    ///
+    /// [`ModuleBuildContext::build_keyed_component`]: struct.ModuleBuildContext.html#method.build_keyed_component
+    fn build_component_map(context: &mut ModuleBuildContext<Self>) -> HashMap<&'static str, Arc<I>>
+    where
+        Self: Module + Sized;
+
+    /// Fallible version of [`build_component_map`]. Usually this involves
+    /// calling [`ModuleBuildContext::try_build_keyed_component`] with each
+    /// implementation.
+    ///
+    /// By default, this calls [`build_component_map`].
+    ///
+    /// [`build_component_map`]: #tymethod.build_component_map
+    /// [`ModuleBuildContext::try_build_keyed_component`]: struct.ModuleBuildContext.html#method.try_build_keyed_component
+    fn try_build_component_map(
+        context: &mut ModuleBuildContext<Self>,
+    ) -> Result<HashMap<&'static str, Arc<I>>, BuildError>
+    where
+        Self: Module + Sized,
+    {
+        Ok(Self::build_component_map(context))
+    }
+
+    /// Get all of the components, including the submodules', by key.
+    ///
+    /// # Example
+    /// ```
+    /// # use shaku::{module, Component, Interface, HasComponentMap};
+    /// # use std::collections::HashMap;
+    /// # use std::sync::Arc;
+    /// #
+    /// # trait Command: Interface {}
+    /// #
+    /// # #[derive(Component)]
+    /// # #[shaku(interface = Command)]
+    /// # struct ExportCommand;
+    /// # impl Command for ExportCommand {}
+    /// #
+    /// # module! {
+    /// #     TestModule {
+    /// #         components = [#[multi(dyn Command)] #[key("export")] ExportCommand],
+    /// #         providers = []
+    /// #     }
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # let module = TestModule::builder().build();
+    /// #
+    /// let commands: HashMap<&'static str, Arc<dyn Command>> = module.resolve_map();
+    /// # assert!(commands.contains_key("export"));
+    /// # }
+    /// ```
+    fn resolve_map(&self) -> HashMap<&'static str, Arc<I>>;
+
+    /// Get the component with the given key, or `None` if there is no such
+    /// component in the map.
+    ///
+    /// # Example
+    /// ```
+    /// # use shaku::{module, Component, Interface, HasComponentMap};
+    /// # use std::sync::Arc;
+    /// #
+    /// # trait Command: Interface {}
+    /// #
+    /// # #[derive(Component)]
+    /// # #[shaku(interface = Command)]
+    /// # struct ExportCommand;
+    /// # impl Command for ExportCommand {}
+    /// #
+    /// # module! {
+    /// #     TestModule {
+    /// #         components = [#[multi(dyn Command)] #[key("export")] ExportCommand],
+    /// #         providers = []
+    /// #     }
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # let module = TestModule::builder().build();
+    /// #
+    /// let command: Option<Arc<dyn Command>> = module.resolve_keyed("export");
+    /// # assert!(command.is_some());
+    /// # assert!(HasComponentMap::<dyn Command>::resolve_keyed(&module, "import").is_none());
+    /// # }
+    /// ```
+    fn resolve_keyed(&self, key: &str) -> Option<Arc<I>> {
+        self.resolve_map().remove(key)
+    }
+}
+
+/// The tag of a keyed component. Keyed components are stored separately from
+/// the components in a set, even if the same implementation is used.
+#[doc(hidden)]
+pub struct Keyed<C: ?Sized>(PhantomData<C>);
@@ -0,0 +1,100 @@
+//! This module contains the storage used by the mocks generated by
+//! `#[shaku::interface(mock)]`
+
+use std::fmt::{self, Debug};
+use std::sync::{Arc, Mutex};
+
+/// A method call recorded by a generated mock.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MockCall {
+    /// The name of the called method
+    pub method: &'static str,
+    /// The `Debug` representation of each argument (not including `self`)
+    pub arguments: Vec<String>,
+}
+
+/// The calls recorded by a generated mock, in the order they were made.
+/// Clones share the same recording.
+#[derive(Clone, Default)]
+pub struct MockRecorder {
+    calls: Arc<Mutex<Vec<MockCall>>>,
+}
+
+impl MockRecorder {
+    /// Record a method call
+    pub fn record(&self, method: &'static str, arguments: &[&dyn Debug]) {
+        let arguments = arguments
+            .iter()
+            .map(|argument| format!("{:?}", argument))
+            .collect();
+
+        self.calls
+            .lock()
+            .unwrap()
+            .push(MockCall { method, arguments });
+    }
+
+    /// Every recorded call
+    pub fn calls(&self) -> Vec<MockCall> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    /// The recorded calls of one method
+    pub fn calls_to(&self, method: &str) -> Vec<MockCall> {
+        self.calls
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|call| call.method == method)
+            .cloned()
+            .collect()
+    }
+}
+
+impl Debug for MockRecorder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.calls().iter()).finish()
+    }
+}
+
+/// The expected behavior of one method of a generated mock. `F` is the
+/// function type of the method, such as `dyn Fn(&str) -> u32 + Send + Sync`.
+/// Clones share the same expectation.
+pub struct MockExpectation<F: ?Sized> {
+    function: Arc<Mutex<Option<Arc<F>>>>,
+}
+
+impl<F: ?Sized> MockExpectation<F> {
+    /// Set the function which is called when the method is called, replacing
+    /// the previous expectation
+    pub fn set(&self, function: Arc<F>) {
+        *self.function.lock().unwrap() = Some(function);
+    }
+
+    /// Get the function to call.
+    ///
+    /// # Panics
+    /// Panics if no expectation was set for the method.
+    pub fn get(&self, method: &str) -> Arc<F> {
+        // The function is cloned out of the lock so it can call the mock again
+        let function = self.function.lock().unwrap().clone();
+
+        function.unwrap_or_else(|| panic!("No expectation was set for `{}`", method))
+    }
+}
+
+impl<F: ?Sized> Clone for MockExpectation<F> {
+    fn clone(&self) -> Self {
+        MockExpectation {
+            function: Arc::clone(&self.function),
+        }
+    }
+}
+
+impl<F: ?Sized> Default for MockExpectation<F> {
+    fn default() -> Self {
+        MockExpectation {
+            function: Arc::new(Mutex::new(None)),
+        }
+    }
+}
@@ -0,0 +1,339 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+
+/// An error which occurred while building a module. Returned by
+/// [`ModuleBuilder::try_build`].
+///
+/// [`ModuleBuilder::try_build`]: struct.ModuleBuilder.html#method.try_build
+#[derive(Debug)]
+pub enum BuildError {
+    /// A component (indirectly) depends on itself.
+    CircularDependency(CircularDependencyError),
+    /// A component has a parameter without a default value, and its
+    /// parameters were not set via [`ModuleBuilder::with_component_parameters`].
+    ///
+    /// [`ModuleBuilder::with_component_parameters`]: struct.ModuleBuilder.html#method.with_component_parameters
+    MissingParameter {
+        /// The type name of the component
+        component: &'static str,
+        /// The name of the parameter without a default value
+        parameter: &'static str,
+    },
+    /// A component has a `#[shaku(env = "...")]` parameter without a
+    /// default value, its parameters were not set via
+    /// [`ModuleBuilder::with_component_parameters`], and the environment
+    /// variable is not set.
+    ///
+    /// [`ModuleBuilder::with_component_parameters`]: struct.ModuleBuilder.html#method.with_component_parameters
+    MissingEnvVar {
+        /// The type name of the component
+        component: &'static str,
+        /// The name of the parameter
+        parameter: &'static str,
+        /// The name of the environment variable
+        variable: &'static str,
+    },
+    /// The environment variable of a `#[shaku(env = "...")]` parameter could
+    /// not be parsed, or is not valid unicode.
+    InvalidEnvVar {
+        /// The type name of the component
+        component: &'static str,
+        /// The name of the parameter
+        parameter: &'static str,
+        /// The name of the environment variable
+        variable: &'static str,
+        /// The error returned when parsing the variable
+        source: Box<dyn Error + Send + Sync>,
+    },
+    /// Some component parameters failed their validation. The parameters are
+    /// validated before any component is built, and all of the violations
+    /// are reported together. See [`Component::validate_parameters`].
+    ///
+    /// [`Component::validate_parameters`]: trait.Component.html#method.validate_parameters
+    InvalidParameters {
+        /// The violations, in the order the components are listed in the
+        /// module
+        violations: Vec<ParameterViolation>,
+    },
+    /// A component returned an error from [`Component::try_build`] or
+    /// [`AsyncComponent::build_async`].
+    ///
+    /// [`Component::try_build`]: trait.Component.html#method.try_build
+    /// [`AsyncComponent::build_async`]: trait.AsyncComponent.html#tymethod.build_async
+    ComponentBuild {
+        /// The type name of the component
+        component: &'static str,
+        /// The error returned by the component
+        source: Box<dyn Error + Send + Sync>,
+    },
+    /// An async component was required while building the module
+    /// synchronously. Modules with async components must be built via
+    /// [`ModuleBuilder::build_async`].
+    ///
+    /// [`ModuleBuilder::build_async`]: struct.ModuleBuilder.html#method.build_async
+    AsyncComponent {
+        /// The type name of the component
+        component: &'static str,
+    },
+    /// Two components in a [`HasComponentMap`] have the same key. This can
+    /// happen when the map is combined with the maps of submodules.
+    ///
+    /// [`HasComponentMap`]: trait.HasComponentMap.html
+    DuplicateKey {
+        /// The type name of the interface of the map
+        interface: &'static str,
+        /// The duplicated key
+        key: &'static str,
+    },
+    /// A [`strict`] module builder has entries which were not used while
+    /// building the module, such as parameters set for a component which was
+    /// overridden, or an override of a lazy component which was not resolved.
+    ///
+    /// [`strict`]: struct.ModuleBuilder.html#method.strict
+    UnusedEntries {
+        /// The unused entries, in the order they were set
+        entries: Vec<UnusedEntry>,
+    },
+    /// A component of a [`DynamicModule`] depends on an interface which is
+    /// not registered on the module's builder.
+    ///
+    /// [`DynamicModule`]: struct.DynamicModule.html
+    UnregisteredComponent {
+        /// The type name of the interface
+        interface: &'static str,
+    },
+}
+
+impl Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::CircularDependency(error) => Display::fmt(error, f),
+            BuildError::MissingParameter {
+                component,
+                parameter,
+            } => write!(
+                f,
+                "There is no default value for `{}::{}`",
+                component, parameter
+            ),
+            BuildError::MissingEnvVar {
+                component,
+                parameter,
+                variable,
+            } => write!(
+                f,
+                "The environment variable {} for `{}::{}` is not set",
+                variable, component, parameter
+            ),
+            BuildError::InvalidEnvVar {
+                component,
+                parameter,
+                variable,
+                source,
+            } => write!(
+                f,
+                "Invalid environment variable {} for `{}::{}`: {}",
+                variable, component, parameter, source
+            ),
+            BuildError::InvalidParameters { violations } => {
+                write!(f, "Invalid component parameters:")?;
+
+                for violation in violations {
+                    write!(f, "\n- {}", violation)?;
+                }
+
+                Ok(())
+            }
+            BuildError::ComponentBuild { component, source } => {
+                write!(f, "Failed to build {}: {}", component, source)
+            }
+            BuildError::AsyncComponent { component } => write!(
+                f,
+                "{} is an async component. Use ModuleBuilder::build_async to build the module.",
+                component
+            ),
+            BuildError::DuplicateKey { interface, key } => write!(
+                f,
+                "Duplicate key \"{}\" in the component map of {}",
+                key, interface
+            ),
+            BuildError::UnusedEntries { entries } => {
+                write!(f, "Unused module builder entries:")?;
+
+                for entry in entries {
+                    write!(f, "\n- {}", entry)?;
+                }
+
+                Ok(())
+            }
+            BuildError::UnregisteredComponent { interface } => {
+                write!(f, "No component is registered for {}", interface)
+            }
+        }
+    }
+}
+
+impl Error for BuildError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        // CircularDependency displays the inner error, so it isn't a source
+        match self {
+            BuildError::ComponentBuild { source, .. } => Some(source.as_ref()),
+            BuildError::InvalidEnvVar { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<CircularDependencyError> for BuildError {
+    fn from(error: CircularDependencyError) -> Self {
+        BuildError::CircularDependency(error)
+    }
+}
+
+/// A circular dependency which was detected while building a module. This
+/// can only happen when a module is implemented manually, or an override fn
+/// resolves a component which depends on the overridden one. The [`module`]
+/// macro detects other cycles at compile time.
+///
+/// The error is displayed as the chain of components, for example
+/// `Circular dependency detected: A -> B -> C -> A`.
+///
+/// Manually implemented modules only return this error from
+/// [`ModuleBuilder::try_build`] if they implement [`Module::try_build`] and
+/// [`HasComponent::try_build_component`] via
+/// [`ModuleBuildContext::try_build_component`]. The default implementations
+/// call [`Module::build`] and [`HasComponent::build_component`], which panic
+/// with this error instead.
+///
+/// [`module`]: macro.module.html
+/// [`ModuleBuilder::try_build`]: struct.ModuleBuilder.html#method.try_build
+/// [`Module::try_build`]: trait.Module.html#method.try_build
+/// [`HasComponent::try_build_component`]: trait.HasComponent.html#method.try_build_component
+/// [`ModuleBuildContext::try_build_component`]: struct.ModuleBuildContext.html#method.try_build_component
+/// [`Module::build`]: trait.Module.html#tymethod.build
+/// [`HasComponent::build_component`]: trait.HasComponent.html#tymethod.build_component
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CircularDependencyError {
+    chain: Vec<DependencyStep>,
+}
+
+/// A component which was being resolved when a circular dependency was
+/// detected. See [`CircularDependencyError::chain`].
+///
+/// [`CircularDependencyError::chain`]: struct.CircularDependencyError.html#method.chain
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DependencyStep {
+    /// The type name of the component
+    pub component: &'static str,
+    /// The type name of the interface which the component was resolved as
+    pub interface: &'static str,
+}
+
+impl CircularDependencyError {
+    /// Create the error from the resolution chain. The last step is the one
+    /// which is already in the chain.
+    pub(crate) fn new(chain: Vec<DependencyStep>) -> Self {
+        CircularDependencyError { chain }
+    }
+
+    /// The components which were being resolved, in resolution order. The
+    /// last step is the component which (indirectly) depends on itself, so it
+    /// also appears earlier in the chain.
+    pub fn chain(&self) -> &[DependencyStep] {
+        &self.chain
+    }
+
+    /// The type name of the interface which was being resolved when the cycle
+    /// was detected.
+    pub fn interface(&self) -> &'static str {
+        // The chain always contains at least the repeated step
+        self.chain.last().map_or("", |step| step.interface)
+    }
+}
+
+impl Display for CircularDependencyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let components: Vec<&str> = self.chain.iter().map(|step| step.component).collect();
+        write!(
+            f,
+            "Circular dependency detected: {}",
+            components.join(" -> ")
+        )
+    }
+}
+
+impl Error for CircularDependencyError {}
+
+/// A component parameter which failed its validation. See
+/// [`BuildError::InvalidParameters`].
+///
+/// The violation is displayed as the parameter and the message, for example
+/// `` `app::ServerImpl::port`: must not be zero ``.
+///
+/// [`BuildError::InvalidParameters`]: enum.BuildError.html#variant.InvalidParameters
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParameterViolation {
+    /// The type name of the component
+    pub component: &'static str,
+    /// The name of the invalid parameter, or `None` if the parameters were
+    /// validated as a whole
+    pub parameter: Option<&'static str>,
+    /// Describes why the parameter is invalid
+    pub message: String,
+}
+
+impl Display for ParameterViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.parameter {
+            Some(parameter) => write!(f, "`{}::{}`: {}", self.component, parameter, self.message),
+            None => write!(f, "`{}`: {}", self.component, self.message),
+        }
+    }
+}
+
+/// An entry of a [`ModuleBuilder`] which was not used while building the
+/// module. See [`BuildError::UnusedEntries`].
+///
+/// [`ModuleBuilder`]: struct.ModuleBuilder.html
+/// [`BuildError::UnusedEntries`]: enum.BuildError.html#variant.UnusedEntries
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UnusedEntry {
+    /// The parameters of a component which was not built
+    Parameters {
+        /// The type name of the component
+        component: &'static str,
+        /// The type name of the tag, if the component is named
+        named: Option<&'static str>,
+    },
+    /// An override of a component which was not resolved
+    ComponentOverride {
+        /// The type name of the overridden interface
+        interface: &'static str,
+        /// The type name of the tag, if a named component was overridden
+        named: Option<&'static str>,
+    },
+    /// An override of a provider which was not used by the module
+    ProviderOverride {
+        /// The type name of the overridden interface
+        interface: &'static str,
+    },
+}
+
+impl Display for UnusedEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (description, name, named) = match self {
+            UnusedEntry::Parameters { component, named } => ("parameters of", component, named),
+            UnusedEntry::ComponentOverride { interface, named } => {
+                ("component override of", interface, named)
+            }
+            UnusedEntry::ProviderOverride { interface } => {
+                ("provider override of", interface, &None)
+            }
+        };
+
+        match named {
+            Some(tag) => write!(f, "{} `{}` named `{}`", description, name, tag),
+            None => write!(f, "{} `{}`", description, name),
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use crate::Interface;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Receives callbacks while a module is built. Observers are registered via
+/// [`ModuleBuilder::with_observer`], and are also notified when lazy
+/// components are built after the module.
+///
+/// All methods do nothing by default. See [`BuildReport`] for an observer
+/// which collects the build order and timings.
+///
+/// [`ModuleBuilder::with_observer`]: struct.ModuleBuilder.html#method.with_observer
+/// [`BuildReport`]: struct.BuildReport.html
+pub trait BuildObserver: Interface {
+    /// Called before a component is built (or resolved from an override)
+    fn component_started(&self, _info: &ComponentBuildInfo) {}
+
+    /// Called after a component is built (or resolved from an override). This
+    /// is not called if the component fails to build.
+    fn component_finished(&self, _info: &ComponentBuildInfo, _timing: ComponentTiming) {}
+
+    /// Called after the module is built. This is not called if the module
+    /// fails to build.
+    fn module_built(&self, _module: &'static str, _duration: Duration) {}
+}
+
+/// Describes a component which is being built. See [`BuildObserver`].
+///
+/// [`BuildObserver`]: trait.BuildObserver.html
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ComponentBuildInfo {
+    /// The type name of the component. For overrides, this is the component
+    /// which was overridden.
+    pub component: &'static str,
+    /// The type name of the component's interface
+    pub interface: &'static str,
+    /// How the component was created
+    pub source: ComponentSource,
+    /// Whether the component was built after the module was built, which
+    /// happens when a lazy component is resolved for the first time
+    pub lazy: bool,
+}
+
+/// How a component was created. See [`ComponentBuildInfo`].
+///
+/// [`ComponentBuildInfo`]: struct.ComponentBuildInfo.html
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ComponentSource {
+    /// The component was built from its `Component` implementation
+    Built,
+    /// The component instance was set via
+    /// [`ModuleBuilder::with_component_override`]
+    ///
+    /// [`ModuleBuilder::with_component_override`]: struct.ModuleBuilder.html#method.with_component_override
+    InstanceOverride,
+    /// The component was created by the function set via
+    /// [`ModuleBuilder::with_component_override_fn`]
+    ///
+    /// [`ModuleBuilder::with_component_override_fn`]: struct.ModuleBuilder.html#method.with_component_override_fn
+    FnOverride,
+}
+
+/// How long a component took to build. See [`BuildObserver`].
+///
+/// [`BuildObserver`]: trait.BuildObserver.html
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ComponentTiming {
+    /// The time spent building the component, including the time spent
+    /// building its dependencies
+    pub total: Duration,
+    /// The time spent building the component itself, excluding the time
+    /// spent building its dependencies
+    pub own: Duration,
+}
+
+/// A component which was built, as recorded by a [`BuildReport`]
+///
+/// [`BuildReport`]: struct.BuildReport.html
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ComponentReport {
+    /// The component which was built
+    pub info: ComponentBuildInfo,
+    /// How long the component took to build
+    pub timing: ComponentTiming,
+}
+
+/// A [`BuildObserver`] which records the components in the order they were
+/// built (dependencies first), with their timings. The report is shared
+/// between its clones, so keep a clone to read the report after the build.
+///
+/// # Example
+/// ```
+/// use shaku::{module, BuildReport, Component, Interface};
+///
+/// trait Database: Interface {}
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Database)]
+/// struct DatabaseImpl;
+/// impl Database for DatabaseImpl {}
+///
+/// module! {
+///     MyModule {
+///         components = [DatabaseImpl],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// let report = BuildReport::new();
+/// let module = MyModule::builder().with_observer(report.clone()).build();
+///
+/// assert_eq!(report.build_order(), vec![std::any::type_name::<DatabaseImpl>()]);
+/// assert!(report.module_duration().is_some());
+/// # }
+/// ```
+///
+/// [`BuildObserver`]: trait.BuildObserver.html
+#[derive(Clone, Debug, Default)]
+pub struct BuildReport {
+    data: Arc<Mutex<BuildReportData>>,
+}
+
+#[derive(Debug, Default)]
+struct BuildReportData {
+    components: Vec<ComponentReport>,
+    module_duration: Option<Duration>,
+}
+
+impl BuildReport {
+    /// Create an empty report
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The components which were built, in the order they finished building
+    pub fn components(&self) -> Vec<ComponentReport> {
+        self.data.lock().unwrap().components.clone()
+    }
+
+    /// The type names of the components which were built, in the order they
+    /// finished building
+    pub fn build_order(&self) -> Vec<&'static str> {
+        self.data
+            .lock()
+            .unwrap()
+            .components
+            .iter()
+            .map(|report| report.info.component)
+            .collect()
+    }
+
+    /// The components which were built, from the slowest to the fastest, by
+    /// the time spent building the component itself
+    pub fn slowest(&self) -> Vec<ComponentReport> {
+        let mut components = self.components();
+        components.sort_by(|a, b| b.timing.own.cmp(&a.timing.own));
+        components
+    }
+
+    /// How long the module took to build, or `None` if it has not been built
+    pub fn module_duration(&self) -> Option<Duration> {
+        self.data.lock().unwrap().module_duration
+    }
+}
+
+impl BuildObserver for BuildReport {
+    fn component_finished(&self, info: &ComponentBuildInfo, timing: ComponentTiming) {
+        self.data.lock().unwrap().components.push(ComponentReport {
+            info: *info,
+            timing,
+        });
+    }
+
+    fn module_built(&self, _module: &'static str, duration: Duration) {
+        self.data.lock().unwrap().module_duration = Some(duration);
+    }
+}
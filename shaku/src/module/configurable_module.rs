@@ -0,0 +1,54 @@
+use crate::{Module, ModuleBuilder};
+use serde_crate::de::{MapAccess, Visitor};
+use std::fmt;
+
+/// Indicates that the parameters of a module's components can be
+/// deserialized from configuration, keyed by component name. See
+/// [`ModuleBuilder::with_parameters_from`].
+///
+/// This trait is implemented by the [`module`] macro when the `serde` feature
+/// is enabled, as long as the parameters of every component in the module
+/// implement `Deserialize`.
+///
+/// [`ModuleBuilder::with_parameters_from`]: struct.ModuleBuilder.html#method.with_parameters_from
+/// [`module`]: macro.module.html
+pub trait ConfigurableModule<'de>: Module + Sized {
+    /// Deserialize the parameters of the component named `component` from the
+    /// next value of `map`, and set them on the builder. Unknown component
+    /// names are an error.
+    fn with_component_parameters_from<A: MapAccess<'de>>(
+        builder: ModuleBuilder<Self>,
+        component: &str,
+        map: &mut A,
+    ) -> Result<ModuleBuilder<Self>, A::Error>;
+}
+
+/// Sets the parameters of each component in a map of component names to
+/// parameters
+pub(crate) struct ParametersVisitor<M: Module> {
+    builder: ModuleBuilder<M>,
+}
+
+impl<M: Module> ParametersVisitor<M> {
+    pub(crate) fn new(builder: ModuleBuilder<M>) -> Self {
+        ParametersVisitor { builder }
+    }
+}
+
+impl<'de, M: ConfigurableModule<'de>> Visitor<'de> for ParametersVisitor<M> {
+    type Value = ModuleBuilder<M>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map of component names to component parameters")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut builder = self.builder;
+
+        while let Some(component) = map.next_key::<String>()? {
+            builder = M::with_component_parameters_from(builder, &component, &mut map)?;
+        }
+
+        Ok(builder)
+    }
+}
@@ -0,0 +1,332 @@
+use std::any::type_name;
+
+/// A dependency of a service, which is declared by the service's `Component`
+/// or `Provider` implementation (see [`Component::dependencies`]). The derive
+/// macros declare a dependency for every injected or provided property.
+///
+/// [`Component::dependencies`]: trait.Component.html#method.dependencies
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Dependency {
+    /// The type name of the interface which the service depends on. For
+    /// `Vec` and `HashMap` injections, this is the element's interface.
+    pub interface: &'static str,
+    /// The type name of the tag, if a named component is injected
+    pub named: Option<&'static str>,
+    /// How the dependency is resolved
+    pub kind: DependencyKind,
+}
+
+impl Dependency {
+    /// A component of the interface `I` is injected
+    pub fn inject<I: ?Sized>() -> Self {
+        Dependency {
+            interface: type_name::<I>(),
+            named: None,
+            kind: DependencyKind::Inject,
+        }
+    }
+
+    /// The component of the interface `I` named `Tag` is injected
+    pub fn inject_named<I: ?Sized, Tag>() -> Self {
+        Dependency {
+            interface: type_name::<I>(),
+            named: Some(type_name::<Tag>()),
+            kind: DependencyKind::Inject,
+        }
+    }
+
+    /// A service of the interface `I` is provided
+    pub fn provide<I: ?Sized>() -> Self {
+        Dependency {
+            interface: type_name::<I>(),
+            named: None,
+            kind: DependencyKind::Provide,
+        }
+    }
+}
+
+/// How a [`Dependency`] is resolved
+///
+/// [`Dependency`]: struct.Dependency.html
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DependencyKind {
+    /// The dependency is a component, which is injected via `#[shaku(inject)]`
+    Inject,
+    /// The dependency is provided via `#[shaku(provide)]`
+    Provide,
+}
+
+/// Whether a [`GraphNode`] is a component or a provider
+///
+/// [`GraphNode`]: struct.GraphNode.html
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ServiceKind {
+    /// A component, which is a singleton owned by the module
+    Component,
+    /// A provider, which creates a new service each time
+    Provider,
+}
+
+/// A component or provider which is registered in a module
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct GraphNode {
+    /// The type name of the component or provider
+    pub service: &'static str,
+    /// The type name of the interface which the service implements
+    pub interface: &'static str,
+    /// The type name of the tag, if this is a named component
+    pub named: Option<&'static str>,
+    /// Whether the service is a component or a provider
+    pub kind: ServiceKind,
+    /// Whether the component is `#[lazy]`. Always false for providers.
+    pub lazy: bool,
+    /// Whether the service was overridden when the module was built, for
+    /// example via [`ModuleBuilder::with_component_override`]
+    ///
+    /// [`ModuleBuilder::with_component_override`]: struct.ModuleBuilder.html#method.with_component_override
+    pub overridden: bool,
+    /// The type name of the module which the service is registered in
+    pub module: &'static str,
+}
+
+impl GraphNode {
+    /// Check if this service has the interface (and tag) of the dependency,
+    /// and is the kind of service which the dependency resolves
+    fn satisfies(&self, edge: &GraphEdge) -> bool {
+        self.interface == edge.to
+            && self.named == edge.named
+            && match edge.kind {
+                DependencyKind::Inject => self.kind == ServiceKind::Component,
+                DependencyKind::Provide => self.kind == ServiceKind::Provider,
+            }
+    }
+}
+
+/// A service which was overridden when its module was built. See
+/// [`ModuleBuildContext::overrides`].
+///
+/// [`ModuleBuildContext::overrides`]: struct.ModuleBuildContext.html#method.overrides
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ServiceOverride {
+    /// The type name of the overridden interface
+    pub interface: &'static str,
+    /// The type name of the tag, if a named component was overridden
+    pub named: Option<&'static str>,
+    /// Whether a component or a provider was overridden
+    pub kind: ServiceKind,
+}
+
+impl ServiceOverride {
+    /// The component of the interface `I` was overridden
+    pub fn component<I: ?Sized>() -> Self {
+        ServiceOverride {
+            interface: type_name::<I>(),
+            named: None,
+            kind: ServiceKind::Component,
+        }
+    }
+
+    /// The component of the interface `I` named `Tag` was overridden
+    pub fn named_component<I: ?Sized, Tag>() -> Self {
+        ServiceOverride {
+            interface: type_name::<I>(),
+            named: Some(type_name::<Tag>()),
+            kind: ServiceKind::Component,
+        }
+    }
+
+    /// The provider of the interface `I` was overridden
+    pub fn provider<I: ?Sized>() -> Self {
+        ServiceOverride {
+            interface: type_name::<I>(),
+            named: None,
+            kind: ServiceKind::Provider,
+        }
+    }
+}
+
+/// A dependency of a service on an interface. The interface is resolved by
+/// the module which the service is registered in, which may import it from a
+/// submodule.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct GraphEdge {
+    /// The type name of the module which the dependent service is registered in
+    pub module: &'static str,
+    /// The type name of the dependent component or provider
+    pub from: &'static str,
+    /// The type name of the interface which is depended on
+    pub to: &'static str,
+    /// The type name of the tag, if a named component is depended on
+    pub named: Option<&'static str>,
+    /// How the dependency is resolved
+    pub kind: DependencyKind,
+}
+
+/// The services of a module (and its submodules) and their dependencies, as
+/// returned by [`Module::dependency_graph`]. Use it to audit the wiring of a
+/// module, for example in tests, or render it via the [`graph`] module.
+///
+/// # Example
+/// ```
+/// use shaku::{module, Component, Interface, Module};
+/// use std::sync::Arc;
+///
+/// trait Logger: Interface {}
+/// trait DateLogger: Interface {}
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Logger)]
+/// struct LoggerImpl;
+/// impl Logger for LoggerImpl {}
+///
+/// #[derive(Component)]
+/// #[shaku(interface = DateLogger)]
+/// struct DateLoggerImpl {
+///     #[shaku(inject)]
+///     logger: Arc<dyn Logger>,
+/// }
+/// impl DateLogger for DateLoggerImpl {}
+///
+/// module! {
+///     MyModule {
+///         components = [LoggerImpl, DateLoggerImpl],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = MyModule::builder().build();
+/// let graph = module.dependency_graph();
+///
+/// let dependencies: Vec<&str> = graph
+///     .dependencies_of(std::any::type_name::<DateLoggerImpl>())
+///     .map(|edge| edge.to)
+///     .collect();
+/// assert_eq!(dependencies, vec![std::any::type_name::<dyn Logger>()]);
+/// # }
+/// ```
+///
+/// [`Module::dependency_graph`]: trait.Module.html#method.dependency_graph
+/// [`graph`]: graph/index.html
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DependencyGraph {
+    nodes: Vec<GraphNode>,
+    edges: Vec<GraphEdge>,
+}
+
+impl DependencyGraph {
+    /// Create an empty graph
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a node to the graph. A node which is already in the graph is
+    /// ignored.
+    pub fn add_node(&mut self, node: GraphNode) {
+        if !self.nodes.contains(&node) {
+            self.nodes.push(node);
+        }
+    }
+
+    /// Add an edge to the graph. An edge which is already in the graph is
+    /// ignored.
+    pub fn add_edge(&mut self, edge: GraphEdge) {
+        if !self.edges.contains(&edge) {
+            self.edges.push(edge);
+        }
+    }
+
+    /// Add a service and its dependencies to the graph
+    pub fn add_service(&mut self, node: GraphNode, dependencies: Vec<Dependency>) {
+        for dependency in dependencies {
+            self.add_edge(GraphEdge {
+                module: node.module,
+                from: node.service,
+                to: dependency.interface,
+                named: dependency.named,
+                kind: dependency.kind,
+            });
+        }
+
+        self.add_node(node);
+    }
+
+    /// Mark the services of the module `module` which were overridden
+    pub fn mark_overrides(&mut self, module: &str, overrides: &[ServiceOverride]) {
+        for node in self.nodes.iter_mut().filter(|node| node.module == module) {
+            node.overridden |= overrides.iter().any(|service_override| {
+                service_override.interface == node.interface
+                    && service_override.named == node.named
+                    && service_override.kind == node.kind
+            });
+        }
+    }
+
+    /// Add the nodes and edges of another graph, such as a submodule's graph
+    pub fn merge(&mut self, other: DependencyGraph) {
+        for node in other.nodes {
+            self.add_node(node);
+        }
+
+        for edge in other.edges {
+            self.add_edge(edge);
+        }
+    }
+
+    /// The services in the graph, in registration order. Submodule services
+    /// come before the module's own services.
+    pub fn nodes(&self) -> &[GraphNode] {
+        &self.nodes
+    }
+
+    /// The dependencies in the graph
+    pub fn edges(&self) -> &[GraphEdge] {
+        &self.edges
+    }
+
+    /// Get the nodes of the services which implement the interface
+    pub fn implementations<'a>(
+        &'a self,
+        interface: &'a str,
+    ) -> impl Iterator<Item = &'a GraphNode> + 'a {
+        self.nodes
+            .iter()
+            .filter(move |node| node.interface == interface)
+    }
+
+    /// Get the services which resolve the dependency. These are the services
+    /// of the dependent's module if it has any, otherwise the services of its
+    /// submodules.
+    pub fn resolve_edge<'a>(&'a self, edge: &GraphEdge) -> Vec<&'a GraphNode> {
+        let candidates: Vec<&GraphNode> = self
+            .nodes
+            .iter()
+            .filter(|node| node.satisfies(edge))
+            .collect();
+
+        if candidates.iter().any(|node| node.module == edge.module) {
+            candidates
+                .into_iter()
+                .filter(|node| node.module == edge.module)
+                .collect()
+        } else {
+            candidates
+        }
+    }
+
+    /// Get the dependencies of the service
+    pub fn dependencies_of<'a>(
+        &'a self,
+        service: &'a str,
+    ) -> impl Iterator<Item = &'a GraphEdge> + 'a {
+        self.edges.iter().filter(move |edge| edge.from == service)
+    }
+
+    /// Get the dependencies on the interface
+    pub fn dependents_of<'a>(
+        &'a self,
+        interface: &'a str,
+    ) -> impl Iterator<Item = &'a GraphEdge> + 'a {
+        self.edges.iter().filter(move |edge| edge.to == interface)
+    }
+}
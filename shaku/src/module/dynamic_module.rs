@@ -0,0 +1,437 @@
+use crate::{
+    AnyComponent, BuildError, Component, Interface, InterfaceInfo, Lifecycle, Module,
+    ModuleBuildContext, ModuleBuilder, ModuleInstance, Provider, ProviderFn, ResolveAny,
+};
+use std::any::{type_name, Any, TypeId};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::sync::Arc;
+
+#[cfg(not(feature = "thread_safe"))]
+type AnyService = Box<dyn Any>;
+#[cfg(feature = "thread_safe")]
+type AnyService = Box<dyn Any + Send + Sync>;
+
+/// A module whose components and providers are registered at runtime, such as
+/// plugins which are discovered when the application starts. Services are
+/// registered on the builder via [`ModuleBuilder::with_component`] and
+/// [`ModuleBuilder::with_provider`], and are keyed by their interface.
+///
+/// Since the services are only known at runtime, they are resolved via
+/// [`resolve`] and [`provide`], which return an error if the interface is not
+/// registered. The registered components can also be resolved by the
+/// `TypeId` of their interface via [`ResolveAny`].
+///
+/// The module does not implement [`HasComponent`] or [`HasProvider`] for an
+/// interface unless it is declared via the [`dynamic_interfaces`] macro. The
+/// declared interfaces can be injected into the registered services, and
+/// imported when the module is the submodule of a module created via the
+/// [`module`] macro. If a declared interface is not registered, building a
+/// service which depends on it fails with
+/// [`BuildError::UnregisteredComponent`], and resolving it via
+/// [`HasComponent::resolve`] panics.
+///
+/// # Example
+/// ```
+/// use shaku::{Component, DynamicModule, HasComponent, Interface};
+/// use std::sync::Arc;
+///
+/// trait Plugin: Interface {
+///     fn name(&self) -> String;
+/// }
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Plugin)]
+/// struct HelloPlugin;
+/// impl Plugin for HelloPlugin {
+///     fn name(&self) -> String {
+///         "hello".to_string()
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = DynamicModule::builder()
+///     .with_component::<HelloPlugin>()
+///     .build();
+///
+/// let plugin: Arc<dyn Plugin> = module.resolve::<dyn Plugin>().unwrap();
+/// assert_eq!(plugin.name(), "hello");
+/// # }
+/// ```
+///
+/// [`ModuleBuilder::with_component`]: struct.ModuleBuilder.html#method.with_component
+/// [`ModuleBuilder::with_provider`]: struct.ModuleBuilder.html#method.with_provider
+/// [`resolve`]: #method.resolve
+/// [`provide`]: #method.provide
+/// [`ResolveAny`]: trait.ResolveAny.html
+/// [`HasComponent`]: trait.HasComponent.html
+/// [`HasProvider`]: trait.HasProvider.html
+/// [`dynamic_interfaces`]: macro.dynamic_interfaces.html
+/// [`module`]: macro.module.html
+/// [`BuildError::UnregisteredComponent`]: enum.BuildError.html#variant.UnregisteredComponent
+/// [`HasComponent::resolve`]: trait.HasComponent.html#tymethod.resolve
+pub struct DynamicModule {
+    /// The `Arc<I>` of each component, keyed by the `TypeId` of `I`
+    components: HashMap<TypeId, AnyComponent>,
+    /// The interfaces of the components, in registration order
+    interfaces: Vec<InterfaceInfo>,
+    /// The `Arc<ProviderFn<DynamicModule, I>>` of each provider, keyed by the
+    /// `TypeId` of `I`
+    providers: HashMap<TypeId, AnyService>,
+    lifecycle: Arc<Lifecycle>,
+}
+
+/// The components and providers which are registered on the builder of a
+/// [`DynamicModule`]. This is the module's [`Module::Submodules`].
+///
+/// [`DynamicModule`]: struct.DynamicModule.html
+/// [`Module::Submodules`]: trait.Module.html#associatedtype.Submodules
+#[derive(Default)]
+pub struct DynamicRegistrations {
+    /// The components, in registration order
+    components: Vec<Registration<BuildComponentFn>>,
+    providers: Vec<Registration<ProviderFnFactory>>,
+}
+
+/// Builds a component and returns it as an `Arc<I>`
+type BuildComponentFn =
+    fn(&mut ModuleBuildContext<DynamicModule>) -> Result<AnyComponent, BuildError>;
+
+/// Creates the provider fn of a provider, as an `Arc<ProviderFn<DynamicModule, I>>`
+type ProviderFnFactory = fn(&ModuleBuildContext<DynamicModule>) -> AnyService;
+
+/// A service registered for the interface
+struct Registration<F> {
+    interface: InterfaceInfo,
+    create: F,
+}
+
+impl DynamicRegistrations {
+    fn component(&self, interface: TypeId) -> Option<BuildComponentFn> {
+        find_registration(&self.components, interface)
+    }
+}
+
+fn find_registration<F: Copy>(registrations: &[Registration<F>], interface: TypeId) -> Option<F> {
+    registrations
+        .iter()
+        .find(|registration| registration.interface.type_id == interface)
+        .map(|registration| registration.create)
+}
+
+/// Register the service, replacing the previous registration of its interface
+fn register<F>(registrations: &mut Vec<Registration<F>>, interface: InterfaceInfo, create: F) {
+    registrations.retain(|registration| registration.interface != interface);
+    registrations.push(Registration { interface, create });
+}
+
+impl DynamicModule {
+    /// Create a builder for the module. Register the module's services on the
+    /// builder via [`ModuleBuilder::with_component`] and
+    /// [`ModuleBuilder::with_provider`].
+    ///
+    /// [`ModuleBuilder::with_component`]: struct.ModuleBuilder.html#method.with_component
+    /// [`ModuleBuilder::with_provider`]: struct.ModuleBuilder.html#method.with_provider
+    pub fn builder() -> ModuleBuilder<Self> {
+        ModuleBuilder::with_submodules(DynamicRegistrations::default())
+    }
+
+    /// Get the component which is registered for the interface `I`.
+    pub fn resolve<I: Interface + ?Sized>(&self) -> Result<Arc<I>, ResolveError> {
+        self.component::<I>()
+            .cloned()
+            .ok_or_else(ResolveError::new::<I>)
+    }
+
+    /// Create a service with the provider which is registered for the
+    /// interface `I`. An error is returned if no provider is registered, or
+    /// if the provider fails.
+    pub fn provide<I: ?Sized + 'static>(&self) -> Result<Box<I>, Box<dyn Error>> {
+        let provider_fn = self
+            .providers
+            .get(&TypeId::of::<I>())
+            .and_then(|provider_fn| provider_fn.downcast_ref::<Arc<ProviderFn<Self, I>>>())
+            .ok_or_else(ResolveError::new::<I>)?;
+
+        provider_fn(self)
+    }
+
+    /// Check if a component or a provider is registered for the interface `I`
+    pub fn contains<I: ?Sized + 'static>(&self) -> bool {
+        let interface = TypeId::of::<I>();
+
+        self.components.contains_key(&interface) || self.providers.contains_key(&interface)
+    }
+
+    /// Build the component registered for the interface `I`. Used by the
+    /// [`dynamic_interfaces`] macro.
+    ///
+    /// [`dynamic_interfaces`]: macro.dynamic_interfaces.html
+    #[doc(hidden)]
+    pub fn try_build_registered<I: Interface + ?Sized>(
+        context: &mut ModuleBuildContext<Self>,
+    ) -> Result<Arc<I>, BuildError> {
+        let build = context.submodules().component(TypeId::of::<I>()).ok_or(
+            BuildError::UnregisteredComponent {
+                interface: type_name::<I>(),
+            },
+        )?;
+        let component = build(context)?;
+
+        // The registration of the interface always builds an Arc<I>
+        Ok(Arc::clone(component.downcast_ref::<Arc<I>>().unwrap()))
+    }
+
+    /// Get a reference to the component registered for the interface `I`,
+    /// panicking if it is not registered. Used by the [`dynamic_interfaces`]
+    /// macro.
+    ///
+    /// [`dynamic_interfaces`]: macro.dynamic_interfaces.html
+    #[doc(hidden)]
+    pub fn resolve_registered_ref<I: Interface + ?Sized>(&self) -> &I {
+        self.component::<I>()
+            .unwrap_or_else(|| panic!("{}", ResolveError::new::<I>()))
+    }
+
+    fn component<I: Interface + ?Sized>(&self) -> Option<&Arc<I>> {
+        self.components
+            .get(&TypeId::of::<I>())
+            .and_then(|component| component.downcast_ref::<Arc<I>>())
+    }
+}
+
+/// Declare the interfaces of the services which can be registered on a
+/// [`DynamicModule`]. The module implements [`HasComponent`] (or
+/// [`HasProvider`]) for each declared interface, so the interface can be
+/// injected into the registered services, and imported when the module is a
+/// submodule. Interfaces which are not declared can only be resolved via
+/// [`DynamicModule::resolve`] and [`DynamicModule::provide`].
+///
+/// The interfaces must be defined in the crate which declares them.
+///
+/// # Example
+/// ```
+/// use shaku::{dynamic_interfaces, Component, DynamicModule, HasComponent, Interface};
+/// use std::sync::Arc;
+///
+/// trait Plugin: Interface {}
+/// trait Registry: Interface {}
+///
+/// dynamic_interfaces! {
+///     components = [dyn Plugin],
+///     providers = []
+/// }
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Plugin)]
+/// struct PluginImpl;
+/// impl Plugin for PluginImpl {}
+///
+/// // The registry can inject the plugin, since its interface is declared
+/// #[derive(Component)]
+/// #[shaku(interface = Registry)]
+/// struct RegistryImpl {
+///     #[shaku(inject)]
+///     plugin: Arc<dyn Plugin>,
+/// }
+/// impl Registry for RegistryImpl {}
+///
+/// # fn main() {
+/// let module = DynamicModule::builder()
+///     .with_component::<RegistryImpl>()
+///     .with_component::<PluginImpl>()
+///     .build();
+///
+/// assert!(module.resolve::<dyn Registry>().is_ok());
+/// # }
+/// ```
+///
+/// [`DynamicModule`]: struct.DynamicModule.html
+/// [`HasComponent`]: trait.HasComponent.html
+/// [`HasProvider`]: trait.HasProvider.html
+/// [`DynamicModule::resolve`]: struct.DynamicModule.html#method.resolve
+/// [`DynamicModule::provide`]: struct.DynamicModule.html#method.provide
+#[macro_export]
+macro_rules! dynamic_interfaces {
+    (
+        components = [$($component:ty),* $(,)?],
+        providers = [$($provider:ty),* $(,)?] $(,)?
+    ) => {
+        $(
+            impl $crate::HasComponent<$component> for $crate::DynamicModule {
+                fn build_component(
+                    context: &mut $crate::ModuleBuildContext<Self>,
+                ) -> ::std::sync::Arc<$component> {
+                    Self::try_build_registered::<$component>(context)
+                        .unwrap_or_else(|error| panic!("{}", error))
+                }
+
+                fn try_build_component(
+                    context: &mut $crate::ModuleBuildContext<Self>,
+                ) -> ::std::result::Result<::std::sync::Arc<$component>, $crate::BuildError> {
+                    Self::try_build_registered::<$component>(context)
+                }
+
+                fn resolve(&self) -> ::std::sync::Arc<$component> {
+                    $crate::DynamicModule::resolve::<$component>(self)
+                        .unwrap_or_else(|error| panic!("{}", error))
+                }
+
+                fn resolve_ref(&self) -> &$component {
+                    self.resolve_registered_ref::<$component>()
+                }
+
+                fn try_resolve(
+                    &self,
+                ) -> ::std::result::Result<::std::sync::Arc<$component>, $crate::BuildError> {
+                    $crate::DynamicModule::resolve::<$component>(self).map_err(|error| {
+                        $crate::BuildError::UnregisteredComponent {
+                            interface: error.interface,
+                        }
+                    })
+                }
+            }
+        )*
+
+        $(
+            impl $crate::HasProvider<$provider> for $crate::DynamicModule {
+                fn provide(
+                    &self,
+                ) -> ::std::result::Result<
+                    ::std::boxed::Box<$provider>,
+                    ::std::boxed::Box<dyn ::std::error::Error>,
+                > {
+                    $crate::DynamicModule::provide::<$provider>(self)
+                }
+            }
+        )*
+    };
+}
+
+impl<P> ModuleBuilder<DynamicModule, P> {
+    /// Register a component of a [`DynamicModule`], replacing the component
+    /// previously registered for its interface.
+    ///
+    /// [`DynamicModule`]: struct.DynamicModule.html
+    pub fn with_component<C: Component<DynamicModule>>(mut self) -> Self {
+        register(
+            &mut self.submodules_mut().components,
+            InterfaceInfo::of::<C::Interface>(),
+            build_component::<C>,
+        );
+        self
+    }
+
+    /// Register a provider of a [`DynamicModule`], replacing the provider
+    /// previously registered for its interface.
+    ///
+    /// [`DynamicModule`]: struct.DynamicModule.html
+    pub fn with_provider<P2: Provider<DynamicModule>>(mut self) -> Self {
+        register(
+            &mut self.submodules_mut().providers,
+            InterfaceInfo::of::<P2::Interface>(),
+            provider_fn::<P2>,
+        );
+        self
+    }
+}
+
+fn build_component<C: Component<DynamicModule>>(
+    context: &mut ModuleBuildContext<DynamicModule>,
+) -> Result<AnyComponent, BuildError> {
+    let component = context.try_build_component::<C>()?;
+
+    Ok(Arc::new(component))
+}
+
+fn provider_fn<P: Provider<DynamicModule>>(
+    context: &ModuleBuildContext<DynamicModule>,
+) -> AnyService {
+    Box::new(context.registered_provider_fn::<P>())
+}
+
+impl Module for DynamicModule {
+    type Submodules = DynamicRegistrations;
+
+    fn build(context: ModuleBuildContext<Self>) -> Self {
+        Self::try_build(context).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn try_build(mut context: ModuleBuildContext<Self>) -> Result<Self, BuildError> {
+        let registrations = context.submodules();
+        let components: Vec<(InterfaceInfo, BuildComponentFn)> = registrations
+            .components
+            .iter()
+            .map(|registration| (registration.interface, registration.create))
+            .collect();
+        let providers = registrations
+            .providers
+            .iter()
+            .map(|registration| {
+                let provider_fn = (registration.create)(&context);
+                (registration.interface.type_id, provider_fn)
+            })
+            .collect();
+
+        let mut built_components = HashMap::new();
+        let mut interfaces = Vec::new();
+        for (interface, build) in components {
+            built_components.insert(interface.type_id, build(&mut context)?);
+            interfaces.push(interface);
+        }
+
+        Ok(DynamicModule {
+            components: built_components,
+            interfaces,
+            providers,
+            lifecycle: Arc::clone(context.lifecycle()),
+        })
+    }
+
+    fn shutdown(&self) {
+        self.lifecycle.shutdown()
+    }
+}
+
+impl ModuleInstance for DynamicModule {
+    fn lifecycle(&self) -> Option<Arc<Lifecycle>> {
+        Some(Arc::clone(&self.lifecycle))
+    }
+}
+
+impl ResolveAny for DynamicModule {
+    fn resolve_any(&self, interface: TypeId) -> Option<AnyComponent> {
+        self.components.get(&interface).cloned()
+    }
+
+    fn interfaces(&self) -> Vec<InterfaceInfo> {
+        self.interfaces.clone()
+    }
+}
+
+/// An interface was resolved from a [`DynamicModule`], but no service is
+/// registered for it.
+///
+/// [`DynamicModule`]: struct.DynamicModule.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResolveError {
+    /// The type name of the interface
+    pub interface: &'static str,
+}
+
+impl ResolveError {
+    fn new<I: ?Sized>() -> Self {
+        ResolveError {
+            interface: type_name::<I>(),
+        }
+    }
+}
+
+impl Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "No service is registered for {}", self.interface)
+    }
+}
+
+impl Error for ResolveError {}
@@ -0,0 +1,74 @@
+use std::sync::{Arc, Mutex};
+
+/// A function which is called when a module is shut down. See
+/// [`Lifecycle::add_stop_hook`].
+///
+/// [`Lifecycle::add_stop_hook`]: struct.Lifecycle.html#method.add_stop_hook
+#[cfg(not(feature = "thread_safe"))]
+pub type StopHook = Box<dyn FnOnce()>;
+/// A function which is called when a module is shut down. See
+/// [`Lifecycle::add_stop_hook`].
+///
+/// [`Lifecycle::add_stop_hook`]: struct.Lifecycle.html#method.add_stop_hook
+#[cfg(feature = "thread_safe")]
+pub type StopHook = Box<dyn FnOnce() + Send + Sync>;
+
+/// Tracks the stop hooks of a module's components, in the order the
+/// components were built, and the lifecycles of its submodules. Used to
+/// implement [`Module::shutdown`].
+///
+/// [`Module::shutdown`]: trait.Module.html#method.shutdown
+#[derive(Default)]
+pub struct Lifecycle {
+    stop_hooks: Mutex<Vec<StopHook>>,
+    submodules: Mutex<Vec<Arc<Lifecycle>>>,
+}
+
+impl Lifecycle {
+    /// Create an empty lifecycle
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a hook to run on shutdown. Hooks run in reverse registration
+    /// order, so a component should register its hook after it is built (and
+    /// therefore after its dependencies are built).
+    pub fn add_stop_hook(&self, hook: StopHook) {
+        self.stop_hooks.lock().unwrap().push(hook);
+    }
+
+    /// Register the lifecycle of a submodule. Submodules are shut down after
+    /// this module's components, in reverse registration order. A lifecycle
+    /// which is already registered is ignored.
+    pub fn add_submodule(&self, submodule: Arc<Lifecycle>) {
+        let mut submodules = self.submodules.lock().unwrap();
+
+        if !submodules.iter().any(|s| Arc::ptr_eq(s, &submodule)) {
+            submodules.push(submodule);
+        }
+    }
+
+    /// Run the stop hooks of the components which were started, in reverse
+    /// order. The submodules are not shut down, since they are owned by the
+    /// caller. Used when the module fails to build.
+    pub(crate) fn abort(&self) {
+        let stop_hooks: Vec<StopHook> = self.stop_hooks.lock().unwrap().drain(..).collect();
+        for hook in stop_hooks.into_iter().rev() {
+            hook();
+        }
+    }
+
+    /// Run the stop hooks in reverse order, then shut down the submodules.
+    /// Each hook is only run once, even if this is called multiple times or
+    /// the lifecycle is shared by multiple modules.
+    pub fn shutdown(&self) {
+        // The locks are released before running the hooks, in case a hook
+        // resolves a lazy component (which would register another hook).
+        self.abort();
+
+        let submodules: Vec<Arc<Lifecycle>> = self.submodules.lock().unwrap().drain(..).collect();
+        for submodule in submodules.into_iter().rev() {
+            submodule.shutdown();
+        }
+    }
+}
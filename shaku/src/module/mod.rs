@@ -1,12 +1,36 @@
 //! This module handles building and resolving services.
 
+mod build_error;
+mod build_observer;
+#[cfg(feature = "serde")]
+mod configurable_module;
+mod dependency_graph;
+mod dynamic_module;
+mod lifecycle;
 mod module_build_context;
 mod module_builder;
 mod module_traits;
+pub(crate) mod parameter_state;
 
+pub use self::build_error::{
+    BuildError, CircularDependencyError, DependencyStep, ParameterViolation, UnusedEntry,
+};
+pub use self::build_observer::{
+    BuildObserver, BuildReport, ComponentBuildInfo, ComponentReport, ComponentSource,
+    ComponentTiming,
+};
+#[cfg(feature = "serde")]
+pub use self::configurable_module::ConfigurableModule;
+#[cfg(feature = "serde")]
+pub(crate) use self::configurable_module::ParametersVisitor;
+pub use self::dependency_graph::{
+    Dependency, DependencyGraph, DependencyKind, GraphEdge, GraphNode, ServiceKind, ServiceOverride,
+};
+pub use self::dynamic_module::{DynamicModule, DynamicRegistrations, ResolveError};
+pub use self::lifecycle::{Lifecycle, StopHook};
 pub use self::module_build_context::ModuleBuildContext;
 pub use self::module_builder::ModuleBuilder;
-pub use self::module_traits::{Module, ModuleInterface};
+pub use self::module_traits::{Module, ModuleInstance, ModuleInterface};
 
 #[cfg(not(feature = "thread_safe"))]
 type AnyType = dyn anymap2::any::Any;
@@ -18,5 +42,5 @@ type ParamAnyType = dyn anymap2::any::Any;
 #[cfg(feature = "thread_safe")]
 type ParamAnyType = dyn anymap2::any::Any + Send;
 
-type ComponentMap = anymap2::Map<AnyType>;
+pub(crate) type ComponentMap = anymap2::Map<AnyType>;
 type ParameterMap = anymap2::Map<ParamAnyType>;
@@ -1,10 +1,24 @@
-use crate::module::{ComponentMap, ParameterMap};
+use crate::map_component::Keyed;
+use crate::module::{AnyType, ComponentMap, ParamAnyType, ParameterMap};
+use crate::multi_component::Multi;
+use crate::named_component::{Tagged, Untagged};
 use crate::parameters::ComponentParameters;
-use crate::{Component, HasProvider, Provider, ProviderFn};
-use crate::{ComponentFn, Module};
+use crate::trace;
+#[cfg(feature = "async")]
+use crate::{AsyncComponent, AsyncProvider, AsyncProviderFn, BoxFuture, HasAsyncProvider};
+use crate::{BuildError, CircularDependencyError, Component, DependencyStep};
+use crate::{BuildObserver, ComponentBuildInfo, ComponentSource, ComponentTiming};
+use crate::{ComponentDecoratorFn, ComponentFn, HasComponentMap, HasComponents};
+use crate::{HasNamedComponent, Module, ProviderDecoratorFn};
+use crate::{HasProvider, Interface, Lifecycle, ParameterViolation, Provider, ProviderFn};
+use crate::{Scope, ScopedProviderFn, ServiceOverride};
+use anymap2::any::IntoBox;
 use std::any::{type_name, TypeId};
-use std::fmt::{self, Debug};
-use std::sync::Arc;
+use std::collections::HashSet;
+use std::error::Error;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 /// Builds a [`Module`] and its associated components. Build context, such as
 /// parameters and resolved components, are stored in this struct.
@@ -14,9 +28,37 @@ pub struct ModuleBuildContext<M: Module> {
     resolved_components: ComponentMap,
     component_fn_overrides: ComponentMap,
     provider_overrides: ComponentMap,
+    component_decorators: ComponentMap,
+    applied_component_decorators: ComponentMap,
+    provider_decorators: ComponentMap,
+    overrides: Vec<ServiceOverride>,
     parameters: ParameterMap,
     submodules: M::Submodules,
     resolve_chain: Vec<ResolveStep>,
+    lifecycle: Arc<Lifecycle>,
+    observers: Vec<Arc<dyn BuildObserver>>,
+    observed: Observed,
+    parameter_violations: Vec<ParameterViolation>,
+    used_entries: Arc<Mutex<HashSet<TypeId>>>,
+}
+
+/// Tracks the builds which are reported to the observers
+#[derive(Default)]
+struct Observed {
+    /// Set once the module is built, so later builds are reported as lazy
+    module_built: Arc<AtomicBool>,
+    /// The components which were reported, to report each instance override
+    /// once
+    reported: Vec<TypeId>,
+    /// The time spent building the dependencies of each component which is
+    /// being built
+    dependency_time: Vec<Duration>,
+}
+
+/// A component build which is being observed
+struct ObservedBuild {
+    info: ComponentBuildInfo,
+    start: Instant,
 }
 
 /// Tracks the current resolution chain. Used to detect circular dependencies.
@@ -26,113 +68,796 @@ struct ResolveStep {
     component_type_id: TypeId,
     interface_type_name: &'static str,
     interface_type_id: TypeId,
-}
-
-impl Debug for ResolveStep {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.component_type_name)
-    }
+    tag_type_id: TypeId,
 }
 
 impl<M: Module> ModuleBuildContext<M> {
     /// Create the build context
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         parameters: ParameterMap,
         component_overrides: ComponentMap,
         component_fn_overrides: ComponentMap,
         provider_overrides: ComponentMap,
+        component_decorators: ComponentMap,
+        provider_decorators: ComponentMap,
+        overrides: Vec<ServiceOverride>,
+        observers: Vec<Arc<dyn BuildObserver>>,
         submodules: M::Submodules,
     ) -> Self {
         ModuleBuildContext {
             resolved_components: component_overrides,
             component_fn_overrides,
             provider_overrides,
+            component_decorators,
+            applied_component_decorators: ComponentMap::new(),
+            provider_decorators,
+            overrides,
             parameters,
             submodules,
             resolve_chain: Vec::new(),
+            lifecycle: Arc::new(Lifecycle::new()),
+            observers,
+            observed: Observed::default(),
+            parameter_violations: Vec::new(),
+            used_entries: Arc::new(Mutex::new(HashSet::new())),
         }
     }
 
+    /// A flag which is set once the module is built. Components built after
+    /// that are reported to the observers as lazy.
+    pub(crate) fn module_built_flag(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.observed.module_built)
+    }
+
+    /// The types of the builder entries (parameters and overrides) which
+    /// were used, including those used after the module is built. See
+    /// [`ModuleBuilder::strict`].
+    ///
+    /// [`ModuleBuilder::strict`]: struct.ModuleBuilder.html#method.strict
+    pub(crate) fn used_entries(&self) -> Arc<Mutex<HashSet<TypeId>>> {
+        Arc::clone(&self.used_entries)
+    }
+
+    /// Mark the builder entry stored as `T` as used
+    fn mark_used<T: 'static>(&self) {
+        self.used_entries.lock().unwrap().insert(TypeId::of::<T>());
+    }
+
     /// Access this module's submodules
     pub fn submodules(&self) -> &M::Submodules {
         &self.submodules
     }
 
+    /// Access the lifecycle of the module being built. Components register
+    /// their stop hooks here, in the order they are built.
+    pub fn lifecycle(&self) -> &Arc<Lifecycle> {
+        &self.lifecycle
+    }
+
+    /// The services which were overridden via the [`ModuleBuilder`]. Modules
+    /// store these to mark the overridden services in their
+    /// [`DependencyGraph`].
+    ///
+    /// [`ModuleBuilder`]: struct.ModuleBuilder.html
+    /// [`DependencyGraph`]: struct.DependencyGraph.html
+    pub fn overrides(&self) -> &[ServiceOverride] {
+        &self.overrides
+    }
+
+    /// Decorate the replacement of a `#[reloadable]` component, and store it
+    /// so the components which are built later (such as lazy components) are
+    /// injected with the new instance. Modules call this when a
+    /// `#[reloadable]` component is replaced, after starting the replacement
+    /// via [`Component::start`]. Returns the decorated component.
+    ///
+    /// [`Component::start`]: trait.Component.html#method.start
+    pub fn replace_resolved_component<I: Interface + ?Sized>(
+        &mut self,
+        component: Arc<I>,
+    ) -> Arc<I> {
+        let component = self.decorate_component::<I, Untagged>(component);
+        self.resolved_components
+            .insert(Tagged::<Untagged, _>::new(Arc::clone(&component)));
+
+        component
+    }
+
     /// Resolve a component by building it if it is not already resolved or
     /// overridden.
+    ///
+    /// # Panics
+    /// Panics if the component fails to build. See [`try_build_component`].
+    ///
+    /// [`try_build_component`]: #method.try_build_component
     pub fn build_component<C: Component<M>>(&mut self) -> Arc<C::Interface> {
+        self.try_build_component::<C>()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Resolve a component by building it if it is not already resolved or
+    /// overridden. An error is returned if the component, or one of its
+    /// dependencies, fails to build.
+    pub fn try_build_component<C: Component<M>>(
+        &mut self,
+    ) -> Result<Arc<C::Interface>, BuildError> {
+        self.try_build_tagged_component::<C, Untagged>()
+    }
+
+    /// Resolve a named component by building it if it is not already resolved
+    /// or overridden.
+    ///
+    /// # Panics
+    /// Panics if the component fails to build. See
+    /// [`try_build_named_component`].
+    ///
+    /// [`try_build_named_component`]: #method.try_build_named_component
+    pub fn build_named_component<C: Component<M>, Tag: 'static>(&mut self) -> Arc<C::Interface>
+    where
+        M: HasNamedComponent<C::Interface, Tag>,
+    {
+        self.try_build_named_component::<C, Tag>()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Resolve a named component by building it if it is not already resolved
+    /// or overridden. Named components are stored separately from each other
+    /// and from the unnamed component of the same interface.
+    pub fn try_build_named_component<C: Component<M>, Tag: 'static>(
+        &mut self,
+    ) -> Result<Arc<C::Interface>, BuildError>
+    where
+        M: HasNamedComponent<C::Interface, Tag>,
+    {
+        self.try_build_tagged_component::<C, Tag>()
+    }
+
+    /// Resolve a multi-bound component by building it if it is not already
+    /// resolved.
+    ///
+    /// # Panics
+    /// Panics if the component fails to build. See
+    /// [`try_build_multi_component`].
+    ///
+    /// [`try_build_multi_component`]: #method.try_build_multi_component
+    pub fn build_multi_component<C: Component<M>>(&mut self) -> Arc<C::Interface>
+    where
+        M: HasComponents<C::Interface>,
+    {
+        self.try_build_multi_component::<C>()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Resolve a multi-bound component by building it if it is not already
+    /// resolved. Each component in a set is stored separately, and separately
+    /// from the unnamed component of the same interface.
+    pub fn try_build_multi_component<C: Component<M>>(
+        &mut self,
+    ) -> Result<Arc<C::Interface>, BuildError>
+    where
+        M: HasComponents<C::Interface>,
+    {
+        self.try_build_tagged_component::<C, Multi<C>>()
+    }
+
+    /// Resolve a keyed component by building it if it is not already
+    /// resolved.
+    ///
+    /// # Panics
+    /// Panics if the component fails to build. See
+    /// [`try_build_keyed_component`].
+    ///
+    /// [`try_build_keyed_component`]: #method.try_build_keyed_component
+    pub fn build_keyed_component<C: Component<M>>(&mut self) -> Arc<C::Interface>
+    where
+        M: HasComponentMap<C::Interface>,
+    {
+        self.try_build_keyed_component::<C>()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Resolve a keyed component by building it if it is not already
+    /// resolved. Keyed components are stored separately from multi-bound and
+    /// unnamed components of the same interface.
+    pub fn try_build_keyed_component<C: Component<M>>(
+        &mut self,
+    ) -> Result<Arc<C::Interface>, BuildError>
+    where
+        M: HasComponentMap<C::Interface>,
+    {
+        self.try_build_tagged_component::<C, Keyed<C>>()
+    }
+
+    /// Validate the parameters of a component before any component is built.
+    /// The violations are collected until [`check_parameter_violations`] is
+    /// called, so the violations of every component are reported together.
+    /// Components which are already resolved or overridden are not validated.
+    ///
+    /// [`check_parameter_violations`]: #method.check_parameter_violations
+    pub fn validate_component<C: Component<M>>(&mut self) {
+        self.validate_tagged_component::<C, Untagged>()
+    }
+
+    /// Validate the parameters of a named component before any component is
+    /// built. See [`validate_component`].
+    ///
+    /// [`validate_component`]: #method.validate_component
+    pub fn validate_named_component<C: Component<M>, Tag: 'static>(&mut self)
+    where
+        M: HasNamedComponent<C::Interface, Tag>,
+    {
+        self.validate_tagged_component::<C, Tag>()
+    }
+
+    /// Validate the parameters of a multi-bound component before any
+    /// component is built. See [`validate_component`].
+    ///
+    /// [`validate_component`]: #method.validate_component
+    pub fn validate_multi_component<C: Component<M>>(&mut self)
+    where
+        M: HasComponents<C::Interface>,
+    {
+        self.validate_tagged_component::<C, Multi<C>>()
+    }
+
+    /// Validate the parameters of a keyed component before any component is
+    /// built. See [`validate_component`].
+    ///
+    /// [`validate_component`]: #method.validate_component
+    pub fn validate_keyed_component<C: Component<M>>(&mut self)
+    where
+        M: HasComponentMap<C::Interface>,
+    {
+        self.validate_tagged_component::<C, Keyed<C>>()
+    }
+
+    /// Validate the parameters of an async component before any component is
+    /// built. See [`validate_component`].
+    ///
+    /// [`validate_component`]: #method.validate_component
+    #[cfg(feature = "async")]
+    pub fn validate_async_component<C: AsyncComponent<M>>(&mut self) {
+        if self.is_resolved_or_overridden::<C::Interface, Untagged>() {
+            return;
+        }
+
+        let violations = match self
+            .parameters
+            .get::<Tagged<Untagged, ComponentParameters<C, C::Parameters>>>()
+        {
+            Some(parameters) => C::validate_parameters(&parameters.value.value),
+            // Missing parameters are reported when the component is built
+            None => match C::default_parameters() {
+                Ok(parameters) => C::validate_parameters(&parameters),
+                Err(_) => return,
+            },
+        };
+        self.parameter_violations.extend(violations);
+    }
+
+    /// Fail the build if the parameters of any component validated via
+    /// [`validate_component`] (or the other `validate_*` methods) are
+    /// invalid. The [`module`] macro calls this before building any
+    /// component, so components with invalid parameters are never built.
+    ///
+    /// [`validate_component`]: #method.validate_component
+    /// [`module`]: macro.module.html
+    pub fn check_parameter_violations(&mut self) -> Result<(), BuildError> {
+        let violations: Vec<ParameterViolation> = self.parameter_violations.drain(..).collect();
+        self.check_parameters(violations)
+    }
+
+    fn validate_tagged_component<C: Component<M>, Tag: 'static>(&mut self) {
+        if self.is_resolved_or_overridden::<C::Interface, Tag>() {
+            return;
+        }
+
+        let violations = match self
+            .parameters
+            .get::<Tagged<Tag, ComponentParameters<C, C::Parameters>>>()
+        {
+            Some(parameters) => C::validate_parameters(&parameters.value.value),
+            // Missing parameters are reported when the component is built
+            None => match C::default_parameters() {
+                Ok(parameters) => C::validate_parameters(&parameters),
+                Err(_) => return,
+            },
+        };
+        self.parameter_violations.extend(violations);
+    }
+
+    /// Check if the component of the interface `I` (stored under `Tag`) was
+    /// already resolved or overridden, so it will not be built
+    fn is_resolved_or_overridden<I: Interface + ?Sized, Tag: 'static>(&self) -> bool {
+        self.resolved_components.contains::<Tagged<Tag, Arc<I>>>()
+            || self
+                .component_fn_overrides
+                .contains::<Tagged<Tag, ComponentFn<M, I>>>()
+    }
+
+    fn try_build_tagged_component<C: Component<M>, Tag: 'static>(
+        &mut self,
+    ) -> Result<Arc<C::Interface>, BuildError> {
         // First check resolved components (which includes overridden component instances)
+        if let Some(component) = self
+            .resolved_components
+            .get::<Tagged<Tag, Arc<C::Interface>>>()
+        {
+            let component = Arc::clone(&component.value);
+            self.mark_used::<Tagged<Tag, Arc<C::Interface>>>();
+            self.observe_instance_override::<C, C::Interface, Tag>();
+
+            // Overridden instances are decorated the first time they are resolved
+            if !self.has_decorators::<C::Interface, Tag>() {
+                return Ok(component);
+            }
+
+            self.add_resolve_step::<C, C::Interface, Tag>()?;
+            let component = self.decorate_component::<C::Interface, Tag>(component);
+            self.resolve_chain.pop();
+            self.resolved_components
+                .insert(Tagged::<Tag, _>::new(Arc::clone(&component)));
+
+            return Ok(component);
+        }
+
+        self.add_resolve_step::<C, C::Interface, Tag>()?;
+
+        // Second check overridden component fn set (will be placed into resolved components)
+        let component_fn = self.take_component_fn::<Tagged<Tag, ComponentFn<M, C::Interface>>>();
+        let source = match component_fn {
+            Some(_) => ComponentSource::FnOverride,
+            None => ComponentSource::Built,
+        };
+        let observed = self.observe_start::<C, C::Interface, Tag>(source);
+        let component = trace::build_component::<C, C::Interface, _>(source, || {
+            let component = match component_fn {
+                Some(component_fn) => Ok(Arc::from((component_fn.value)(self))),
+                // Third resolve the concrete component
+                None => self.build_concrete_component::<C, Tag>(),
+            }
+            .map(|component| self.decorate_component::<C::Interface, Tag>(component));
+            trace::record_error(component, "component build failed")
+        });
+        self.observe_finish(observed, component.is_ok());
+
+        // Resolution is finished, pop the component off the chain
+        self.resolve_chain.pop();
+
+        let component = component?;
         self.resolved_components
-            .get::<Arc<C::Interface>>()
-            .cloned()
-            // Second check overridden component fn set (will be placed into resolved components)
-            .or_else(|| {
-                let component_fn = self
-                    .component_fn_overrides
-                    .remove::<ComponentFn<M, C::Interface>>()?;
-                self.add_resolve_step::<C>();
-
-                // Build the component
-                let component = component_fn(self);
-                let component = Arc::from(component);
-                self.resolved_components
-                    .insert::<Arc<C::Interface>>(Arc::clone(&component));
+            .insert(Tagged::<Tag, _>::new(Arc::clone(&component)));
 
-                // Resolution was successful, pop the component off the chain
-                self.resolve_chain.pop();
+        Ok(component)
+    }
 
-                Some(component)
-            })
-            // Third resolve the concrete component
-            .unwrap_or_else(|| {
-                self.add_resolve_step::<C>();
-
-                // Build the component
-                let parameters = self
-                    .parameters
-                    .remove::<ComponentParameters<C, C::Parameters>>()
-                    .unwrap_or_default();
-                let component = C::build(self, parameters.value);
-                let component = Arc::from(component);
-                self.resolved_components
-                    .insert::<Arc<C::Interface>>(Arc::clone(&component));
+    fn has_decorators<I: Interface + ?Sized, Tag: 'static>(&self) -> bool {
+        self.component_decorators
+            .contains::<Tagged<Tag, Vec<ComponentDecoratorFn<M, I>>>>()
+    }
 
-                // Resolution was successful, pop the component off the chain
-                self.resolve_chain.pop();
+    /// Wrap the component with its decorators, in registration order. The
+    /// decorators are then kept aside, so they are only applied to the
+    /// component once, but can still decorate its `#[reloadable]`
+    /// replacements.
+    fn decorate_component<I: Interface + ?Sized, Tag: 'static>(
+        &mut self,
+        component: Arc<I>,
+    ) -> Arc<I> {
+        let decorators = match self
+            .component_decorators
+            .remove::<Tagged<Tag, Vec<ComponentDecoratorFn<M, I>>>>()
+            .or_else(|| {
+                self.applied_component_decorators
+                    .remove::<Tagged<Tag, Vec<ComponentDecoratorFn<M, I>>>>()
+            }) {
+            Some(decorators) => decorators,
+            None => return component,
+        };
 
-                component
-            })
+        let component = decorators
+            .value
+            .iter()
+            .fold(component, |component, decorator| {
+                Arc::from(decorator(component, self))
+            });
+        self.applied_component_decorators.insert(decorators);
+
+        component
+    }
+
+    /// Get the decorators of a provider, which are applied to each provided
+    /// service
+    fn provider_decorators<I: ?Sized + 'static>(&self) -> Vec<Arc<ProviderDecoratorFn<M, I>>> {
+        self.provider_decorators
+            .get::<Vec<Arc<ProviderDecoratorFn<M, I>>>>()
+            .cloned()
+            .unwrap_or_default()
     }
 
     /// Get a provider function from the given provider impl, or an overridden
-    /// one if configured during module build.
+    /// one if configured during module build. The provided services are
+    /// wrapped with the provider's decorators.
     pub fn provider_fn<P: Provider<M>>(&self) -> Arc<ProviderFn<M, P::Interface>>
     where
         M: HasProvider<P::Interface>,
     {
-        self.provider_overrides
-            .get::<Arc<ProviderFn<M, P::Interface>>>()
-            .cloned()
-            .unwrap_or_else(|| Arc::new(Box::new(P::provide)))
+        self.registered_provider_fn::<P>()
+    }
+
+    /// Like [`provider_fn`], but for a provider which the module does not
+    /// implement [`HasProvider`] for, such as one registered at runtime on a
+    /// [`DynamicModule`].
+    ///
+    /// [`provider_fn`]: #method.provider_fn
+    /// [`HasProvider`]: trait.HasProvider.html
+    /// [`DynamicModule`]: struct.DynamicModule.html
+    pub(crate) fn registered_provider_fn<P: Provider<M>>(
+        &self,
+    ) -> Arc<ProviderFn<M, P::Interface>> {
+        let provider_fn = self.provider_override::<Arc<ProviderFn<M, P::Interface>>>();
+        let decorators = self.provider_decorators::<P::Interface>();
+
+        Arc::new(Box::new(move |module: &M| {
+            let service = trace::provide::<P, P::Interface, _, _>(|| match &provider_fn {
+                Some(provider_fn) => provider_fn(module),
+                None => P::provide(module),
+            })?;
+
+            Ok(decorate_service(service, module, &decorators))
+        }))
+    }
+
+    /// Get a scoped provider function from the given provider impl. If the
+    /// provider was overridden during module build, the override is called
+    /// with the scope's module instead. The provided services are wrapped with
+    /// the provider's decorators.
+    pub fn scoped_provider_fn<P: Provider<M>>(&self) -> Arc<ScopedProviderFn<M, P::Interface>>
+    where
+        M: HasProvider<P::Interface>,
+    {
+        let provider_fn = self.provider_override::<Arc<ProviderFn<M, P::Interface>>>();
+        let decorators = self.provider_decorators::<P::Interface>();
+
+        Arc::new(Box::new(move |scope: &Scope<M>| {
+            let service = trace::provide::<P, P::Interface, _, _>(|| match &provider_fn {
+                Some(provider_fn) => provider_fn(scope.module()),
+                None => P::provide_scoped(scope),
+            })?;
+
+            Ok(decorate_service(service, scope.module(), &decorators))
+        }))
+    }
+
+    /// Get an async provider function from the given async provider impl, or
+    /// an overridden one if configured during module build.
+    #[cfg(feature = "async")]
+    pub fn async_provider_fn<P: AsyncProvider<M>>(&self) -> Arc<AsyncProviderFn<M, P::Interface>>
+    where
+        M: HasAsyncProvider<P::Interface>,
+    {
+        let provider_fn = self.provider_override::<Arc<AsyncProviderFn<M, P::Interface>>>();
+
+        Arc::new(Box::new(move |module: &M| {
+            trace::provide_async::<P, P::Interface, _, _>(match &provider_fn {
+                Some(provider_fn) => provider_fn(module),
+                None => P::provide_async(module),
+            })
+        }))
+    }
+
+    fn build_concrete_component<C: Component<M>, Tag: 'static>(
+        &mut self,
+    ) -> Result<Arc<C::Interface>, BuildError> {
+        let parameters = self
+            .take_parameters::<Tagged<Tag, ComponentParameters<C, C::Parameters>>>()
+            .map(|parameters| parameters.value.value);
+        let parameters = match parameters {
+            Some(parameters) => parameters,
+            None => C::default_parameters()?,
+        };
+        self.check_parameters(C::validate_parameters(&parameters))?;
+
+        C::try_build_shared(self, parameters).map_err(component_build_error::<C>)
+    }
+
+    /// Take the parameters stored as `T`, marking them as used
+    fn take_parameters<T: IntoBox<ParamAnyType>>(&mut self) -> Option<T> {
+        let parameters = self.parameters.remove::<T>()?;
+        self.mark_used::<T>();
+
+        Some(parameters)
+    }
+
+    /// Take the component override fn stored as `T`, marking it as used
+    fn take_component_fn<T: IntoBox<AnyType>>(&mut self) -> Option<T> {
+        let component_fn = self.component_fn_overrides.remove::<T>()?;
+        self.mark_used::<T>();
+
+        Some(component_fn)
+    }
+
+    /// Get the provider override stored as `T`, marking it as used
+    fn provider_override<T: IntoBox<AnyType> + Clone>(&self) -> Option<T> {
+        let provider_fn = self.provider_overrides.get::<T>().cloned()?;
+        self.mark_used::<T>();
+
+        Some(provider_fn)
+    }
+
+    /// Fail the build of a component if its parameters are invalid, so it is
+    /// never built
+    fn check_parameters(&self, violations: Vec<ParameterViolation>) -> Result<(), BuildError> {
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(BuildError::InvalidParameters { violations })
+        }
+    }
+
+    /// Resolve an async component by building it if it is not already
+    /// resolved or overridden. An error is returned if the component, or one
+    /// of its dependencies, fails to build.
+    #[cfg(feature = "async")]
+    pub fn build_async_component<C: AsyncComponent<M>>(
+        &mut self,
+    ) -> BoxFuture<'_, Result<Arc<C::Interface>, BuildError>> {
+        Box::pin(async move {
+            // First check resolved components (which includes overridden component instances)
+            if let Some(component) = self
+                .resolved_components
+                .get::<Tagged<Untagged, Arc<C::Interface>>>()
+            {
+                let component = Arc::clone(&component.value);
+                self.mark_used::<Tagged<Untagged, Arc<C::Interface>>>();
+                self.observe_instance_override::<C, C::Interface, Untagged>();
+
+                // Overridden instances are decorated the first time they are resolved
+                if !self.has_decorators::<C::Interface, Untagged>() {
+                    return Ok(component);
+                }
+
+                self.add_resolve_step::<C, C::Interface, Untagged>()?;
+                let component = self.decorate_component::<C::Interface, Untagged>(component);
+                self.resolve_chain.pop();
+                self.resolved_components
+                    .insert(Tagged::<Untagged, _>::new(Arc::clone(&component)));
+
+                return Ok(component);
+            }
+
+            self.add_resolve_step::<C, C::Interface, Untagged>()?;
+
+            // Second check overridden component fn set (will be placed into resolved components)
+            let component_fn =
+                self.take_component_fn::<Tagged<Untagged, ComponentFn<M, C::Interface>>>();
+            let source = match component_fn {
+                Some(_) => ComponentSource::FnOverride,
+                None => ComponentSource::Built,
+            };
+            let observed = self.observe_start::<C, C::Interface, Untagged>(source);
+            let build = Box::pin(async {
+                let component = match component_fn {
+                    Some(component_fn) => Ok(Arc::from((component_fn.value)(self))),
+                    // Third resolve the concrete component
+                    None => self.build_concrete_async_component::<C>().await,
+                }
+                .map(|component| self.decorate_component::<C::Interface, Untagged>(component));
+                trace::record_error(component, "component build failed")
+            });
+            let component = trace::build_component_async::<C, C::Interface, _>(source, build).await;
+            self.observe_finish(observed, component.is_ok());
+
+            // Resolution is finished, pop the component off the chain
+            self.resolve_chain.pop();
+
+            let component = component?;
+            self.resolved_components
+                .insert(Tagged::<Untagged, _>::new(Arc::clone(&component)));
+
+            Ok(component)
+        })
+    }
+
+    /// Get an async component which was already built via
+    /// [`build_async_component`]. Since async components cannot be built
+    /// synchronously, an error is returned if it has not been built yet.
+    ///
+    /// [`build_async_component`]: #method.build_async_component
+    #[cfg(feature = "async")]
+    pub fn try_get_async_component<C: AsyncComponent<M>>(
+        &mut self,
+    ) -> Result<Arc<C::Interface>, BuildError> {
+        if let Some(component) = self
+            .resolved_components
+            .get::<Tagged<Untagged, Arc<C::Interface>>>()
+        {
+            let component = Arc::clone(&component.value);
+            self.mark_used::<Tagged<Untagged, Arc<C::Interface>>>();
+            self.observe_instance_override::<C, C::Interface, Untagged>();
+
+            // Overridden instances are decorated the first time they are resolved
+            if !self.has_decorators::<C::Interface, Untagged>() {
+                return Ok(component);
+            }
+
+            self.add_resolve_step::<C, C::Interface, Untagged>()?;
+            let component = self.decorate_component::<C::Interface, Untagged>(component);
+            self.resolve_chain.pop();
+            self.resolved_components
+                .insert(Tagged::<Untagged, _>::new(Arc::clone(&component)));
+
+            return Ok(component);
+        }
+
+        // The component may still be overridden by a (synchronous) fn
+        if let Some(component_fn) =
+            self.take_component_fn::<Tagged<Untagged, ComponentFn<M, C::Interface>>>()
+        {
+            self.add_resolve_step::<C, C::Interface, Untagged>()?;
+            let observed =
+                self.observe_start::<C, C::Interface, Untagged>(ComponentSource::FnOverride);
+            let component =
+                trace::build_component::<C, C::Interface, _>(ComponentSource::FnOverride, || {
+                    let component = Arc::from((component_fn.value)(self));
+                    self.decorate_component::<C::Interface, Untagged>(component)
+                });
+            self.observe_finish(observed, true);
+            self.resolve_chain.pop();
+
+            self.resolved_components
+                .insert(Tagged::<Untagged, _>::new(Arc::clone(&component)));
+            return Ok(component);
+        }
+
+        Err(BuildError::AsyncComponent {
+            component: type_name::<C>(),
+        })
+    }
+
+    #[cfg(feature = "async")]
+    async fn build_concrete_async_component<C: AsyncComponent<M>>(
+        &mut self,
+    ) -> Result<Arc<C::Interface>, BuildError> {
+        let parameters = self
+            .take_parameters::<Tagged<Untagged, ComponentParameters<C, C::Parameters>>>()
+            .map(|parameters| parameters.value.value);
+        let parameters = match parameters {
+            Some(parameters) => parameters,
+            None => C::default_parameters()?,
+        };
+        self.check_parameters(C::validate_parameters(&parameters))?;
+
+        C::build_shared_async(self, parameters)
+            .await
+            .map_err(component_build_error::<C>)
+    }
+
+    /// Notify the observers that a component build started. Returns `None`
+    /// if there are no observers.
+    fn observe_start<C: 'static, I: ?Sized + 'static, Tag: 'static>(
+        &mut self,
+        source: ComponentSource,
+    ) -> Option<ObservedBuild> {
+        if self.observers.is_empty() {
+            return None;
+        }
+
+        self.observed
+            .reported
+            .push(TypeId::of::<Tagged<Tag, Arc<I>>>());
+        self.observed.dependency_time.push(Duration::default());
+
+        let info = ComponentBuildInfo {
+            component: type_name::<C>(),
+            interface: type_name::<I>(),
+            source,
+            lazy: self.observed.module_built.load(Ordering::SeqCst),
+        };
+        for observer in &self.observers {
+            observer.component_started(&info);
+        }
+
+        Some(ObservedBuild {
+            info,
+            start: Instant::now(),
+        })
+    }
+
+    /// Notify the observers that a component build finished. The build time
+    /// is added to the dependency time of the component which depends on it.
+    fn observe_finish(&mut self, observed: Option<ObservedBuild>, succeeded: bool) {
+        let observed = match observed {
+            Some(observed) => observed,
+            None => return,
+        };
+
+        let total = observed.start.elapsed();
+        let dependency_time = self.observed.dependency_time.pop().unwrap_or_default();
+        if let Some(parent_dependency_time) = self.observed.dependency_time.last_mut() {
+            *parent_dependency_time += total;
+        }
+
+        if !succeeded {
+            return;
+        }
+
+        let timing = ComponentTiming {
+            total,
+            own: total.checked_sub(dependency_time).unwrap_or_default(),
+        };
+        for observer in &self.observers {
+            observer.component_finished(&observed.info, timing);
+        }
+    }
+
+    /// Report an overridden component instance the first time it is resolved.
+    /// Every other resolved component was reported when it was built.
+    fn observe_instance_override<C: 'static, I: ?Sized + 'static, Tag: 'static>(&mut self) {
+        if self.observers.is_empty()
+            || self
+                .observed
+                .reported
+                .contains(&TypeId::of::<Tagged<Tag, Arc<I>>>())
+        {
+            return;
+        }
+
+        let observed = self.observe_start::<C, I, Tag>(ComponentSource::InstanceOverride);
+        self.observe_finish(observed, true);
     }
 
-    fn add_resolve_step<C: Component<M>>(&mut self) {
+    fn add_resolve_step<C: 'static, I: ?Sized + 'static, Tag: 'static>(
+        &mut self,
+    ) -> Result<(), BuildError> {
         let step = ResolveStep {
             component_type_name: type_name::<C>(),
             component_type_id: TypeId::of::<C>(),
-            interface_type_name: type_name::<C::Interface>(),
-            interface_type_id: TypeId::of::<C::Interface>(),
+            interface_type_name: type_name::<I>(),
+            interface_type_id: TypeId::of::<I>(),
+            tag_type_id: TypeId::of::<Tag>(),
         };
 
         // Check for a circular dependency
         if self.resolve_chain.contains(&step) {
-            panic!(
-                "Circular dependency detected while resolving {}. Resolution chain: {:?}",
-                step.interface_type_name, self.resolve_chain
-            );
+            let chain = self
+                .resolve_chain
+                .iter()
+                .chain(Some(&step))
+                .map(|step| DependencyStep {
+                    component: step.component_type_name,
+                    interface: step.interface_type_name,
+                })
+                .collect();
+
+            return Err(CircularDependencyError::new(chain).into());
         }
 
         // Add this component to the chain
         self.resolve_chain.push(step);
+        Ok(())
+    }
+}
+
+/// Wrap a provided service with its decorators, in registration order
+fn decorate_service<M: Module, I: ?Sized>(
+    service: Box<I>,
+    module: &M,
+    decorators: &[Arc<ProviderDecoratorFn<M, I>>],
+) -> Box<I> {
+    decorators
+        .iter()
+        .fold(service, |service, decorator| decorator(service, module))
+}
+
+/// Wrap an error returned by the component `C`. Errors from dependencies are
+/// passed through as-is.
+fn component_build_error<C>(error: Box<dyn Error + Send + Sync>) -> BuildError {
+    match error.downcast::<BuildError>() {
+        Ok(error) => *error,
+        Err(error) => BuildError::ComponentBuild {
+            component: type_name::<C>(),
+            source: error,
+        },
     }
 }
@@ -1,22 +1,67 @@
 use crate::component::Interface;
+use crate::map_component::Keyed;
+use crate::module::parameter_state::{OverrideComponent, RequiredParametersSet, SetParameters};
 use crate::module::{ComponentMap, ParameterMap};
+use crate::multi_component::Multi;
+use crate::named_component::{Tagged, Untagged};
 use crate::parameters::ComponentParameters;
 use crate::provider::ProviderFn;
-use crate::{Component, ComponentFn, HasComponent, HasProvider, Module, ModuleBuildContext};
+use crate::trace;
+#[cfg(feature = "async")]
+use crate::{AsyncComponent, AsyncProviderFn, HasAsyncProvider};
+use crate::{
+    BuildError, BuildObserver, Component, ComponentDecoratorFn, ComponentFn, HasComponent,
+    HasComponentMap, HasComponents, HasNamedComponent, HasProvider, Lifecycle, Module,
+    ModuleBuildContext, ProviderDecoratorFn, ServiceOverride, UnusedEntry,
+};
+use std::any::{type_name, TypeId};
+use std::collections::HashSet;
 use std::marker::PhantomData;
-use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+#[cfg(feature = "serde")]
+use {crate::module::ParametersVisitor, crate::ConfigurableModule, serde_crate::Deserializer};
 
 /// Builds a [`Module`]. Component parameters can be set, and both components and providers
 /// implementations can be overridden.
 ///
+/// `P` tracks which components still need their parameters set. Builders
+/// created by the [`module`] macro start with every component's parameters
+/// unset, and can only be built once the parameters of every component with
+/// required parameters (which have neither `#[shaku(default)]` nor
+/// `#[shaku(env)]`) have been set. See [`build`] for the compile error
+/// otherwise. With `P = ()`, missing parameters are only detected when the
+/// module is built. See [`unchecked`].
+///
 /// [`Module`]: trait.Module.html
-pub struct ModuleBuilder<M: Module> {
+/// [`module`]: macro.module.html
+/// [`build`]: #method.build
+/// [`unchecked`]: #method.unchecked
+pub struct ModuleBuilder<M: Module, P = ()> {
     parameters: ParameterMap,
     submodules: M::Submodules,
     component_overrides: ComponentMap,
     component_fn_overrides: ComponentMap,
     provider_overrides: ComponentMap,
+    component_decorators: ComponentMap,
+    provider_decorators: ComponentMap,
+    overrides: Vec<ServiceOverride>,
+    observers: Vec<Arc<dyn BuildObserver>>,
+    entries: Vec<BuilderEntry>,
+    strict: bool,
     _module: PhantomData<M>,
+    // fn() -> P so the state doesn't affect Send/Sync
+    _parameters: PhantomData<fn() -> P>,
+}
+
+/// A parameter or override entry set on the builder, which is checked by
+/// strict builders
+#[derive(Clone)]
+struct BuilderEntry {
+    /// The type the entry is stored as
+    key: TypeId,
+    entry: UnusedEntry,
 }
 
 impl<M: Module> ModuleBuilder<M> {
@@ -28,30 +73,132 @@ impl<M: Module> ModuleBuilder<M> {
             component_overrides: ComponentMap::new(),
             component_fn_overrides: ComponentMap::new(),
             provider_overrides: ComponentMap::new(),
+            component_decorators: ComponentMap::new(),
+            provider_decorators: ComponentMap::new(),
+            overrides: Vec::new(),
+            observers: Vec::new(),
+            entries: Vec::new(),
+            strict: false,
             _module: PhantomData,
+            _parameters: PhantomData,
+        }
+    }
+}
+
+impl<M: Module, P> ModuleBuilder<M, P> {
+    /// Stop tracking the component parameters at compile time. Components
+    /// with required parameters which are not set will fail the build instead.
+    pub fn unchecked(self) -> ModuleBuilder<M> {
+        self.with_parameter_state()
+    }
+
+    /// Change the tracked state of the component parameters
+    #[doc(hidden)]
+    pub fn with_parameter_state<Q>(self) -> ModuleBuilder<M, Q> {
+        ModuleBuilder {
+            parameters: self.parameters,
+            submodules: self.submodules,
+            component_overrides: self.component_overrides,
+            component_fn_overrides: self.component_fn_overrides,
+            provider_overrides: self.provider_overrides,
+            component_decorators: self.component_decorators,
+            provider_decorators: self.provider_decorators,
+            overrides: self.overrides,
+            observers: self.observers,
+            entries: self.entries,
+            strict: self.strict,
+            _module: PhantomData,
+            _parameters: PhantomData,
         }
     }
 
     /// Set the parameters of the specified component. If the parameters are not
     /// manually set, the defaults will be used.
-    pub fn with_component_parameters<C: Component<M>>(mut self, params: C::Parameters) -> Self
+    pub fn with_component_parameters<C: Component<M>>(
+        mut self,
+        params: C::Parameters,
+    ) -> ModuleBuilder<M, <M as SetParameters<C, Untagged, P>>::Output>
     where
-        M: HasComponent<C::Interface>,
+        M: HasComponent<C::Interface> + SetParameters<C, Untagged, P>,
     {
         self.parameters
-            .insert(ComponentParameters::<C, C::Parameters>::new(params));
-        self
+            .insert(Tagged::<Untagged, _>::new(ComponentParameters::<
+                C,
+                C::Parameters,
+            >::new(params)));
+        self.add_entry::<Tagged<Untagged, ComponentParameters<C, C::Parameters>>>(
+            UnusedEntry::Parameters {
+                component: type_name::<C>(),
+                named: None,
+            },
+        );
+        self.with_parameter_state()
+    }
+
+    /// Set the parameters of the specified keyed component. If the
+    /// parameters are not manually set, the defaults will be used.
+    pub fn with_keyed_component_parameters<C: Component<M>>(
+        mut self,
+        params: C::Parameters,
+    ) -> ModuleBuilder<M, <M as SetParameters<C, Keyed<C>, P>>::Output>
+    where
+        M: HasComponentMap<C::Interface> + SetParameters<C, Keyed<C>, P>,
+    {
+        self.parameters
+            .insert(Tagged::<Keyed<C>, _>::new(ComponentParameters::<
+                C,
+                C::Parameters,
+            >::new(params)));
+        self.add_entry::<Tagged<Keyed<C>, ComponentParameters<C, C::Parameters>>>(
+            UnusedEntry::Parameters {
+                component: type_name::<C>(),
+                named: None,
+            },
+        );
+        self.with_parameter_state()
+    }
+
+    /// Set the parameters of the specified async component. If the parameters
+    /// are not manually set, the defaults will be used.
+    #[cfg(feature = "async")]
+    pub fn with_async_component_parameters<C: AsyncComponent<M>>(
+        mut self,
+        params: C::Parameters,
+    ) -> ModuleBuilder<M, <M as SetParameters<C, Untagged, P>>::Output>
+    where
+        M: HasComponent<C::Interface> + SetParameters<C, Untagged, P>,
+    {
+        self.parameters
+            .insert(Tagged::<Untagged, _>::new(ComponentParameters::<
+                C,
+                C::Parameters,
+            >::new(params)));
+        self.add_entry::<Tagged<Untagged, ComponentParameters<C, C::Parameters>>>(
+            UnusedEntry::Parameters {
+                component: type_name::<C>(),
+                named: None,
+            },
+        );
+        self.with_parameter_state()
     }
 
     /// Override a component implementation. This method is best used when the
     /// overriding component has no injected dependencies.
-    pub fn with_component_override<I: Interface + ?Sized>(mut self, component: Box<I>) -> Self
+    pub fn with_component_override<I: Interface + ?Sized>(
+        mut self,
+        component: Box<I>,
+    ) -> ModuleBuilder<M, <M as OverrideComponent<I, Untagged, P>>::Output>
     where
-        M: HasComponent<I>,
+        M: HasComponent<I> + OverrideComponent<I, Untagged, P>,
     {
         self.component_overrides
-            .insert::<Arc<I>>(Arc::from(component));
-        self
+            .insert(Tagged::<Untagged, Arc<I>>::new(Arc::from(component)));
+        self.overrides.push(ServiceOverride::component::<I>());
+        self.add_entry::<Tagged<Untagged, Arc<I>>>(UnusedEntry::ComponentOverride {
+            interface: type_name::<I>(),
+            named: None,
+        });
+        self.with_parameter_state()
     }
 
     /// Override a component implementation. This method is best used when the
@@ -59,12 +206,143 @@ impl<M: Module> ModuleBuilder<M> {
     pub fn with_component_override_fn<I: Interface + ?Sized>(
         mut self,
         component_fn: ComponentFn<M, I>,
-    ) -> Self
+    ) -> ModuleBuilder<M, <M as OverrideComponent<I, Untagged, P>>::Output>
     where
-        M: HasComponent<I>,
+        M: HasComponent<I> + OverrideComponent<I, Untagged, P>,
     {
-        self.component_fn_overrides.insert(component_fn);
-        self
+        self.component_fn_overrides
+            .insert(Tagged::<Untagged, _>::new(component_fn));
+        self.overrides.push(ServiceOverride::component::<I>());
+        self.add_entry::<Tagged<Untagged, ComponentFn<M, I>>>(UnusedEntry::ComponentOverride {
+            interface: type_name::<I>(),
+            named: None,
+        });
+        self.with_parameter_state()
+    }
+
+    /// Set the parameters of the specified named component. If the parameters
+    /// are not manually set, the defaults will be used.
+    pub fn with_named_component_parameters<C: Component<M>, Tag: 'static>(
+        mut self,
+        params: C::Parameters,
+    ) -> ModuleBuilder<M, <M as SetParameters<C, Tag, P>>::Output>
+    where
+        M: HasNamedComponent<C::Interface, Tag> + SetParameters<C, Tag, P>,
+    {
+        self.parameters.insert(Tagged::<Tag, _>::new(
+            ComponentParameters::<C, C::Parameters>::new(params),
+        ));
+        self.add_entry::<Tagged<Tag, ComponentParameters<C, C::Parameters>>>(
+            UnusedEntry::Parameters {
+                component: type_name::<C>(),
+                named: Some(type_name::<Tag>()),
+            },
+        );
+        self.with_parameter_state()
+    }
+
+    /// Set the parameters of the specified multi-bound component. If the
+    /// parameters are not manually set, the defaults will be used.
+    pub fn with_multi_component_parameters<C: Component<M>>(
+        mut self,
+        params: C::Parameters,
+    ) -> ModuleBuilder<M, <M as SetParameters<C, Multi<C>, P>>::Output>
+    where
+        M: HasComponents<C::Interface> + SetParameters<C, Multi<C>, P>,
+    {
+        self.parameters
+            .insert(Tagged::<Multi<C>, _>::new(ComponentParameters::<
+                C,
+                C::Parameters,
+            >::new(params)));
+        self.add_entry::<Tagged<Multi<C>, ComponentParameters<C, C::Parameters>>>(
+            UnusedEntry::Parameters {
+                component: type_name::<C>(),
+                named: None,
+            },
+        );
+        self.with_parameter_state()
+    }
+
+    /// Set the parameters of the module's components from configuration,
+    /// such as a TOML, JSON, or YAML file. The configuration is a map of
+    /// component names to their parameters:
+    ///
+    /// ```toml
+    /// [DatabaseImpl]
+    /// url = "postgres://localhost/app"
+    ///
+    /// ["ReplicaDatabaseImpl(Replica)"]
+    /// url = "postgres://replica/app"
+    /// ```
+    ///
+    /// Components are named by their type in the [`module`] macro, without
+    /// the path or generics. Named components are followed by their tag.
+    /// Parameters which are left out fall back to their `#[shaku(default)]`,
+    /// and components which are left out keep their current parameters.
+    /// Unknown component names are an error.
+    ///
+    /// The configuration is only known at runtime, so the returned builder
+    /// is [`unchecked`].
+    ///
+    /// Requires the `serde` feature. See [`ConfigurableModule`].
+    ///
+    /// [`module`]: macro.module.html
+    /// [`unchecked`]: #method.unchecked
+    /// [`ConfigurableModule`]: trait.ConfigurableModule.html
+    #[cfg(feature = "serde")]
+    pub fn with_parameters_from<'de, D: Deserializer<'de>>(
+        self,
+        config: D,
+    ) -> Result<ModuleBuilder<M>, D::Error>
+    where
+        M: ConfigurableModule<'de>,
+    {
+        config.deserialize_map(ParametersVisitor::new(self.unchecked()))
+    }
+
+    /// Override a named component implementation. See
+    /// [`with_component_override`].
+    ///
+    /// [`with_component_override`]: #method.with_component_override
+    pub fn with_named_component_override<I: Interface + ?Sized, Tag: 'static>(
+        mut self,
+        component: Box<I>,
+    ) -> ModuleBuilder<M, <M as OverrideComponent<I, Tag, P>>::Output>
+    where
+        M: HasNamedComponent<I, Tag> + OverrideComponent<I, Tag, P>,
+    {
+        self.component_overrides
+            .insert(Tagged::<Tag, Arc<I>>::new(Arc::from(component)));
+        self.overrides
+            .push(ServiceOverride::named_component::<I, Tag>());
+        self.add_entry::<Tagged<Tag, Arc<I>>>(UnusedEntry::ComponentOverride {
+            interface: type_name::<I>(),
+            named: Some(type_name::<Tag>()),
+        });
+        self.with_parameter_state()
+    }
+
+    /// Override a named component implementation. See
+    /// [`with_component_override_fn`].
+    ///
+    /// [`with_component_override_fn`]: #method.with_component_override_fn
+    pub fn with_named_component_override_fn<I: Interface + ?Sized, Tag: 'static>(
+        mut self,
+        component_fn: ComponentFn<M, I>,
+    ) -> ModuleBuilder<M, <M as OverrideComponent<I, Tag, P>>::Output>
+    where
+        M: HasNamedComponent<I, Tag> + OverrideComponent<I, Tag, P>,
+    {
+        self.component_fn_overrides
+            .insert(Tagged::<Tag, _>::new(component_fn));
+        self.overrides
+            .push(ServiceOverride::named_component::<I, Tag>());
+        self.add_entry::<Tagged<Tag, ComponentFn<M, I>>>(UnusedEntry::ComponentOverride {
+            interface: type_name::<I>(),
+            named: Some(type_name::<Tag>()),
+        });
+        self.with_parameter_state()
     }
 
     /// Override a provider implementation.
@@ -76,17 +354,344 @@ impl<M: Module> ModuleBuilder<M> {
         M: HasProvider<I>,
     {
         self.provider_overrides.insert(Arc::new(provider_fn));
+        self.overrides.push(ServiceOverride::provider::<I>());
+        self.add_entry::<Arc<ProviderFn<M, I>>>(UnusedEntry::ProviderOverride {
+            interface: type_name::<I>(),
+        });
+        self
+    }
+
+    /// Override an async provider implementation.
+    #[cfg(feature = "async")]
+    pub fn with_async_provider_override<I: 'static + ?Sized>(
+        mut self,
+        provider_fn: AsyncProviderFn<M, I>,
+    ) -> Self
+    where
+        M: HasAsyncProvider<I>,
+    {
+        self.provider_overrides.insert(Arc::new(provider_fn));
+        self.overrides.push(ServiceOverride::provider::<I>());
+        self.add_entry::<Arc<AsyncProviderFn<M, I>>>(UnusedEntry::ProviderOverride {
+            interface: type_name::<I>(),
+        });
+        self
+    }
+
+    /// Decorate a component. The decorator is given the component after it is
+    /// built (or overridden), and returns a component which wraps it, for
+    /// example to add caching, metrics, or retries. The build context can be
+    /// used to resolve the decorator's own dependencies.
+    ///
+    /// Decorators are applied in registration order, so the last decorator
+    /// registered is the outermost one. They are also applied to the
+    /// replacements of `#[reloadable]` components.
+    ///
+    /// This only decorates the unnamed component of the interface. Named,
+    /// multi-bound and keyed components are decorated separately, via
+    /// [`with_named_component_decorator`], [`with_multi_component_decorator`]
+    /// and [`with_keyed_component_decorator`].
+    ///
+    /// [`with_named_component_decorator`]: #method.with_named_component_decorator
+    /// [`with_multi_component_decorator`]: #method.with_multi_component_decorator
+    /// [`with_keyed_component_decorator`]: #method.with_keyed_component_decorator
+    pub fn with_component_decorator<I: Interface + ?Sized>(
+        mut self,
+        decorator: ComponentDecoratorFn<M, I>,
+    ) -> Self
+    where
+        M: HasComponent<I>,
+    {
+        self.add_component_decorator::<I, Untagged>(decorator);
+        self
+    }
+
+    /// Decorate the specified named component. See
+    /// [`with_component_decorator`].
+    ///
+    /// [`with_component_decorator`]: #method.with_component_decorator
+    pub fn with_named_component_decorator<I: Interface + ?Sized, Tag: 'static>(
+        mut self,
+        decorator: ComponentDecoratorFn<M, I>,
+    ) -> Self
+    where
+        M: HasNamedComponent<I, Tag>,
+    {
+        self.add_component_decorator::<I, Tag>(decorator);
+        self
+    }
+
+    /// Decorate the specified multi-bound component. The other components of
+    /// the interface are not decorated. See [`with_component_decorator`].
+    ///
+    /// [`with_component_decorator`]: #method.with_component_decorator
+    pub fn with_multi_component_decorator<C: Component<M>>(
+        mut self,
+        decorator: ComponentDecoratorFn<M, C::Interface>,
+    ) -> Self
+    where
+        M: HasComponents<C::Interface>,
+    {
+        self.add_component_decorator::<C::Interface, Multi<C>>(decorator);
+        self
+    }
+
+    /// Decorate the specified keyed component. The other components of the
+    /// map are not decorated. See [`with_component_decorator`].
+    ///
+    /// [`with_component_decorator`]: #method.with_component_decorator
+    pub fn with_keyed_component_decorator<C: Component<M>>(
+        mut self,
+        decorator: ComponentDecoratorFn<M, C::Interface>,
+    ) -> Self
+    where
+        M: HasComponentMap<C::Interface>,
+    {
+        self.add_component_decorator::<C::Interface, Keyed<C>>(decorator);
+        self
+    }
+
+    /// Decorate a provider. The decorator is given each service after it is
+    /// provided (by the provider or its override), and returns a service which
+    /// wraps it. See [`with_component_decorator`].
+    ///
+    /// [`with_component_decorator`]: #method.with_component_decorator
+    pub fn with_provider_decorator<I: 'static + ?Sized>(
+        mut self,
+        decorator: ProviderDecoratorFn<M, I>,
+    ) -> Self
+    where
+        M: HasProvider<I>,
+    {
+        self.provider_decorators
+            .entry::<Vec<Arc<ProviderDecoratorFn<M, I>>>>()
+            .or_default()
+            .push(Arc::new(decorator));
+        self
+    }
+
+    /// Fail the build if a parameter or override set on the builder was not
+    /// used while building the module, so misconfigured tests don't pass
+    /// vacuously. For example, parameters set for a component which was
+    /// overridden are never used, and neither is an override of a lazy
+    /// component which is not resolved while the module is built. See
+    /// [`BuildError::UnusedEntries`].
+    ///
+    /// [`BuildError::UnusedEntries`]: enum.BuildError.html#variant.UnusedEntries
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    /// Register an observer which is notified as components are built,
+    /// including lazy components which are built after the module. See
+    /// [`BuildObserver`] and [`BuildReport`].
+    ///
+    /// [`BuildObserver`]: trait.BuildObserver.html
+    /// [`BuildReport`]: struct.BuildReport.html
+    pub fn with_observer<O: BuildObserver>(mut self, observer: O) -> Self {
+        self.observers.push(Arc::new(observer));
         self
     }
 
     /// Build the module
-    pub fn build(self) -> M {
-        M::build(ModuleBuildContext::new(
+    ///
+    /// # Panics
+    /// Panics if the module fails to build. See [`try_build`] for the possible
+    /// errors.
+    ///
+    /// # Compile errors
+    /// This only compiles once the parameters of every component with required
+    /// parameters have been set. Otherwise, the compiler reports an `E0283`
+    /// error: "type annotations needed ... cannot infer type of the type
+    /// parameter `Proof`". The component with missing parameters is named by
+    /// the `ParametersUnset<Component>` in the error's notes. To fix it, either
+    /// set the parameters with [`with_component_parameters`], or give the
+    /// parameters a `#[shaku(default)]`. Alternatively, [`try_build`] and
+    /// [`unchecked`] skip this check, and report missing parameters with
+    /// [`BuildError::MissingParameter`] when the module is built.
+    ///
+    /// [`try_build`]: #method.try_build
+    /// [`with_component_parameters`]: #method.with_component_parameters
+    /// [`unchecked`]: #method.unchecked
+    /// [`BuildError::MissingParameter`]: enum.BuildError.html#variant.MissingParameter
+    pub fn build<Proof>(self) -> M
+    where
+        M: RequiredParametersSet<P, Proof>,
+    {
+        self.try_build().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Build the module, returning an error instead of panicking if a
+    /// component could not be built. See [`BuildError`] for the possible
+    /// errors. Unlike [`build`], this can be called before every required
+    /// parameter is set, in which case [`BuildError::MissingParameter`] is
+    /// returned.
+    ///
+    /// [`BuildError`]: enum.BuildError.html
+    /// [`build`]: #method.build
+    /// [`BuildError::MissingParameter`]: enum.BuildError.html#variant.MissingParameter
+    pub fn try_build(self) -> Result<M, BuildError> {
+        let observers = self.observers.clone();
+        let start = Instant::now();
+        let strict_entries = self.strict_entries();
+        let context = self.into_context();
+        let module_built = context.module_built_flag();
+        let used_entries = context.used_entries();
+        let lifecycle = Arc::clone(context.lifecycle());
+
+        let module = trace::module_build::<M, _>(|| {
+            let module = M::try_build(context)
+                .and_then(|module| check_unused_entries(module, strict_entries, &used_entries));
+            trace::record_error(module, "module build failed")
+        })
+        .map_err(|error| abort_build(&lifecycle, error))?;
+        module_built.store(true, Ordering::SeqCst);
+        notify_module_built::<M>(&observers, start);
+        Ok(module)
+    }
+
+    /// Build the module asynchronously. [`AsyncComponent`]s are awaited in
+    /// dependency order, then the rest of the module is built.
+    ///
+    /// # Panics
+    /// Panics if the module fails to build. See [`try_build_async`] for the
+    /// possible errors.
+    ///
+    /// # Compile errors
+    /// Like [`build`], this only compiles once every required parameter is
+    /// set.
+    ///
+    /// [`AsyncComponent`]: trait.AsyncComponent.html
+    /// [`try_build_async`]: #method.try_build_async
+    /// [`build`]: #method.build
+    #[cfg(feature = "async")]
+    pub async fn build_async<Proof>(self) -> M
+    where
+        M: RequiredParametersSet<P, Proof>,
+    {
+        self.try_build_async()
+            .await
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Build the module asynchronously, returning an error instead of
+    /// panicking if a component could not be built. See [`BuildError`] for
+    /// the possible errors. Like [`try_build`], this can be called before
+    /// every required parameter is set.
+    ///
+    /// [`BuildError`]: enum.BuildError.html
+    /// [`try_build`]: #method.try_build
+    #[cfg(feature = "async")]
+    pub async fn try_build_async(self) -> Result<M, BuildError> {
+        let observers = self.observers.clone();
+        let start = Instant::now();
+        let strict_entries = self.strict_entries();
+        let context = self.into_context();
+        let module_built = context.module_built_flag();
+        let used_entries = context.used_entries();
+        let lifecycle = Arc::clone(context.lifecycle());
+
+        let module = trace::module_build_async::<M, _>(Box::pin(async move {
+            let module = M::try_build_async(context)
+                .await
+                .and_then(|module| check_unused_entries(module, strict_entries, &used_entries));
+            trace::record_error(module, "module build failed")
+        }))
+        .await
+        .map_err(|error| abort_build(&lifecycle, error))?;
+        module_built.store(true, Ordering::SeqCst);
+        notify_module_built::<M>(&observers, start);
+        Ok(module)
+    }
+
+    /// Record a parameter or override entry, stored as `T`
+    fn add_component_decorator<I: Interface + ?Sized, Tag: 'static>(
+        &mut self,
+        decorator: ComponentDecoratorFn<M, I>,
+    ) {
+        self.component_decorators
+            .entry::<Tagged<Tag, Vec<ComponentDecoratorFn<M, I>>>>()
+            .or_insert_with(|| Tagged::new(Vec::new()))
+            .value
+            .push(decorator);
+    }
+
+    fn add_entry<T: 'static>(&mut self, entry: UnusedEntry) {
+        let key = TypeId::of::<T>();
+
+        if !self.entries.iter().any(|existing| existing.key == key) {
+            self.entries.push(BuilderEntry { key, entry });
+        }
+    }
+
+    /// The entries to check after the build, if the builder is strict
+    fn strict_entries(&self) -> Option<Vec<BuilderEntry>> {
+        if self.strict {
+            Some(self.entries.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Modules which register their services on the builder store them in
+    /// their submodules, such as [`DynamicModule`].
+    ///
+    /// [`DynamicModule`]: struct.DynamicModule.html
+    pub(crate) fn submodules_mut(&mut self) -> &mut M::Submodules {
+        &mut self.submodules
+    }
+
+    fn into_context(self) -> ModuleBuildContext<M> {
+        ModuleBuildContext::new(
             self.parameters,
             self.component_overrides,
             self.component_fn_overrides,
             self.provider_overrides,
+            self.component_decorators,
+            self.provider_decorators,
+            self.overrides,
+            self.observers,
             self.submodules,
-        ))
+        )
+    }
+}
+
+/// Fail the build of a strict builder if any of its entries were not used
+fn check_unused_entries<M>(
+    module: M,
+    strict_entries: Option<Vec<BuilderEntry>>,
+    used_entries: &Mutex<HashSet<TypeId>>,
+) -> Result<M, BuildError> {
+    let strict_entries = match strict_entries {
+        Some(strict_entries) => strict_entries,
+        None => return Ok(module),
+    };
+
+    let used_entries = used_entries.lock().unwrap();
+    let entries: Vec<UnusedEntry> = strict_entries
+        .into_iter()
+        .filter(|entry| !used_entries.contains(&entry.key))
+        .map(|entry| entry.entry)
+        .collect();
+
+    if entries.is_empty() {
+        Ok(module)
+    } else {
+        Err(BuildError::UnusedEntries { entries })
+    }
+}
+
+/// Stop the components which were started before the build failed
+fn abort_build(lifecycle: &Lifecycle, error: BuildError) -> BuildError {
+    lifecycle.abort();
+    error
+}
+
+fn notify_module_built<M>(observers: &[Arc<dyn BuildObserver>], start: Instant) {
+    let duration = start.elapsed();
+
+    for observer in observers {
+        observer.module_built(type_name::<M>(), duration);
     }
 }
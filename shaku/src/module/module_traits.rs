@@ -1,5 +1,9 @@
-use crate::ModuleBuildContext;
+use crate::{BuildError, DependencyGraph, HasReloadableComponent, Interface, Lifecycle};
+use crate::{ModuleBuildContext, Scope};
 use std::any::Any;
+use std::sync::Arc;
+#[cfg(feature = "async")]
+use {crate::BoxFuture, std::future};
 
 /// A module represents a group of services. By implementing traits such as [`HasComponent`] on a
 /// module, service dependencies are checked at compile time. At runtime, modules hold the
@@ -35,13 +39,138 @@ use std::any::Any;
 /// [`module`]: macro.module.html
 pub trait Module: ModuleInterface {
     /// A container for this module's submodules.
+    #[cfg(not(all(feature = "thread_safe", feature = "async")))]
     type Submodules;
 
+    /// A container for this module's submodules. The submodules are held
+    /// across awaits by [`ModuleBuilder::build_async`], so they must be
+    /// `Send`.
+    ///
+    /// [`ModuleBuilder::build_async`]: struct.ModuleBuilder.html#method.build_async
+    #[cfg(all(feature = "thread_safe", feature = "async"))]
+    type Submodules: Send;
+
     /// Create the module instance by resolving the components this module
     /// provides.
     fn build(context: ModuleBuildContext<Self>) -> Self
     where
         Self: Sized;
+
+    /// Fallible version of [`build`]. Modules created via the [`module`]
+    /// macro report all build errors through this function.
+    ///
+    /// By default, this calls [`build`].
+    ///
+    /// [`build`]: #tymethod.build
+    /// [`module`]: macro.module.html
+    fn try_build(context: ModuleBuildContext<Self>) -> Result<Self, BuildError>
+    where
+        Self: Sized,
+    {
+        Ok(Self::build(context))
+    }
+
+    /// Async version of [`try_build`]. Modules created via the [`module`]
+    /// macro build their [`AsyncComponent`]s here before building the rest
+    /// of the module.
+    ///
+    /// By default, this calls [`try_build`].
+    ///
+    /// [`try_build`]: #method.try_build
+    /// [`module`]: macro.module.html
+    /// [`AsyncComponent`]: trait.AsyncComponent.html
+    #[cfg(feature = "async")]
+    fn try_build_async(
+        context: ModuleBuildContext<Self>,
+    ) -> BoxFuture<'static, Result<Self, BuildError>>
+    where
+        Self: Sized,
+    {
+        Box::pin(future::ready(Self::try_build(context)))
+    }
+
+    /// Shut down the module by running the stop hooks of its components in
+    /// reverse dependency order (the reverse of the order the components were
+    /// built in), then shutting down its submodules. Components shared with
+    /// other modules are only stopped once. See [`Lifecycle`].
+    ///
+    /// By default, this does nothing.
+    ///
+    /// [`Lifecycle`]: struct.Lifecycle.html
+    fn shutdown(&self) {}
+
+    /// Create a new [`Scope`], which caches the services of `#[scoped]`
+    /// providers until it is dropped.
+    ///
+    /// [`Scope`]: struct.Scope.html
+    fn create_scope(&self) -> Scope<'_, Self>
+    where
+        Self: Sized,
+    {
+        Scope::new(self)
+    }
+
+    /// Replace a `#[reloadable]` component, returning the previous instance.
+    /// See [`HasReloadableComponent`] for how replacements affect the other
+    /// services.
+    ///
+    /// [`HasReloadableComponent`]: trait.HasReloadableComponent.html
+    fn replace<I: Interface + ?Sized>(
+        &self,
+        component: Box<<Self as HasReloadableComponent<I>>::Component>,
+    ) -> Arc<I>
+    where
+        Self: HasReloadableComponent<I> + Sized,
+    {
+        self.replace_component(component)
+    }
+
+    /// Get the services of this module and its submodules, and the
+    /// dependencies between them. See [`DependencyGraph`].
+    ///
+    /// Modules created via the [`module`] macro include the graphs of the
+    /// submodules which services are imported from.
+    ///
+    /// By default, this returns an empty graph.
+    ///
+    /// [`DependencyGraph`]: struct.DependencyGraph.html
+    /// [`module`]: macro.module.html
+    fn dependency_graph(&self) -> DependencyGraph {
+        DependencyGraph::new()
+    }
+}
+
+/// The parts of a built module which are used by the modules it is a submodule
+/// of, such as its lifecycle and dependency graph. Submodules may be trait objects, so this is a supertrait of the service
+/// traits (such as [`HasComponent`] and [`HasProvider`]) instead of a part of
+/// [`Module`]. Modules created via the [`module`] macro implement this.
+///
+/// [`HasComponent`]: trait.HasComponent.html
+/// [`HasProvider`]: trait.HasProvider.html
+/// [`Module`]: trait.Module.html
+/// [`module`]: macro.module.html
+pub trait ModuleInstance: ModuleInterface {
+    /// Get the [`Lifecycle`] of this module. Modules use this to shut down the
+    /// submodules they import services from.
+    ///
+    /// By default, this returns `None`.
+    ///
+    /// [`Lifecycle`]: struct.Lifecycle.html
+    fn lifecycle(&self) -> Option<Arc<Lifecycle>> {
+        None
+    }
+
+    /// Get the [`DependencyGraph`] of this module. Modules use this to include
+    /// the graphs of the submodules they import services from. See
+    /// [`Module::dependency_graph`].
+    ///
+    /// By default, this returns `None`.
+    ///
+    /// [`DependencyGraph`]: struct.DependencyGraph.html
+    /// [`Module::dependency_graph`]: trait.Module.html#method.dependency_graph
+    fn module_graph(&self) -> Option<DependencyGraph> {
+        None
+    }
 }
 
 #[cfg(not(feature = "thread_safe"))]
@@ -0,0 +1,182 @@
+#[cfg(feature = "async")]
+use crate::AsyncComponent;
+use crate::{Component, Module};
+use std::marker::PhantomData;
+
+/// Indicates that a component has required parameters, which have no default
+/// value. The [`module`] macro only allows [`ModuleBuilder::build`] to be
+/// called once the parameters of these components have been set.
+///
+/// This trait is implemented by the `Component` and `AsyncComponent` derives
+/// when a parameter has neither `#[shaku(default)]` nor `#[shaku(env)]`.
+/// Components without it, such as manually implemented components, are not
+/// tracked.
+///
+/// [`module`]: macro.module.html
+/// [`ModuleBuilder::build`]: struct.ModuleBuilder.html#method.build
+pub trait RequiredParameters {}
+
+/// The parameters of the component `C` have not been set on the
+/// [`ModuleBuilder`].
+///
+/// [`ModuleBuilder`]: struct.ModuleBuilder.html
+pub struct ParametersUnset<C: ?Sized>(PhantomData<C>);
+
+/// The parameters of a component have been set on the [`ModuleBuilder`].
+///
+/// [`ModuleBuilder`]: struct.ModuleBuilder.html
+pub struct ParametersSet;
+
+/// Proves that an unset component is ready because it is not tracked. See
+/// [`ParametersReady`].
+///
+/// [`ParametersReady`]: trait.ParametersReady.html
+pub struct ParametersUntracked;
+
+/// Proves nothing. A component with [`RequiredParameters`] which are unset
+/// matches both this and [`ParametersUntracked`], so the proof is ambiguous
+/// and the module can't be built. See [`ParametersReady`].
+///
+/// [`RequiredParameters`]: trait.RequiredParameters.html
+/// [`ParametersUntracked`]: struct.ParametersUntracked.html
+/// [`ParametersReady`]: trait.ParametersReady.html
+pub struct ParametersRequired;
+
+/// The state of a component's parameters which allows the module to be built.
+/// `Proof` is inferred when the module is built. It can't be inferred for a
+/// component with [`RequiredParameters`] which are unset, since two impls
+/// apply, which fails the build with "type annotations needed".
+///
+/// [`RequiredParameters`]: trait.RequiredParameters.html
+pub trait ParametersReady<Proof> {}
+
+impl ParametersReady<ParametersSet> for ParametersSet {}
+
+impl<C: ?Sized> ParametersReady<ParametersUntracked> for ParametersUnset<C> {}
+
+impl<C: RequiredParameters + ?Sized> ParametersReady<ParametersRequired> for ParametersUnset<C> {}
+
+/// Tracks the parameters set on a [`ModuleBuilder`]. `S` is the current
+/// state of the module's components, and `Output` is the state after the
+/// parameters of the component `C` (stored under `Tag`) are set.
+///
+/// This trait is implemented by the [`module`] macro. Builders without any
+/// tracked state (`S = ()`) don't track parameters at compile time.
+///
+/// [`ModuleBuilder`]: struct.ModuleBuilder.html
+/// [`module`]: macro.module.html
+pub trait SetParameters<C: ?Sized, Tag, S> {
+    /// The state after the parameters are set
+    type Output;
+}
+
+impl<M: ?Sized, C: ?Sized, Tag> SetParameters<C, Tag, ()> for M {
+    type Output = ();
+}
+
+impl<M: ?Sized, C: ?Sized, Tag, I: ?Sized, Tag2, S>
+    SetParameters<C, Tag, ComponentOverridden<I, Tag2, S>> for M
+where
+    M: SetParameters<C, Tag, S>,
+{
+    type Output = ComponentOverridden<I, Tag2, <M as SetParameters<C, Tag, S>>::Output>;
+}
+
+/// A component of the interface `I` (stored under `Tag`) has been overridden
+/// on the [`ModuleBuilder`] in the state `S`. The interface is mapped to the
+/// overridden component when the module is built, via [`ApplyOverride`].
+///
+/// [`ModuleBuilder`]: struct.ModuleBuilder.html
+/// [`ApplyOverride`]: trait.ApplyOverride.html
+pub struct ComponentOverridden<I: ?Sized, Tag, S>(PhantomData<(Tag, S)>, PhantomData<I>);
+
+/// Tracks the components overridden on a [`ModuleBuilder`]. `S` is the
+/// current state of the module's components, and `Output` is the state after
+/// the component of the interface `I` (stored under `Tag`) is overridden.
+///
+/// This trait is implemented by the [`module`] macro. Builders without any
+/// tracked state (`S = ()`) don't track overrides at compile time.
+///
+/// [`ModuleBuilder`]: struct.ModuleBuilder.html
+/// [`module`]: macro.module.html
+pub trait OverrideComponent<I: ?Sized, Tag, S> {
+    /// The state after the component is overridden
+    type Output;
+}
+
+impl<M: ?Sized, I: ?Sized, Tag> OverrideComponent<I, Tag, ()> for M {
+    type Output = ();
+}
+
+impl<M: ?Sized, I: ?Sized, Tag, I2: ?Sized, Tag2, S>
+    OverrideComponent<I, Tag, ComponentOverridden<I2, Tag2, S>> for M
+{
+    type Output = ComponentOverridden<I, Tag, ComponentOverridden<I2, Tag2, S>>;
+}
+
+/// Maps an overridden interface `I` (stored under `Tag`) to the component
+/// which implements it. `Output` is the state `S` after the component is
+/// overridden. Overridden components are not built, so their parameters
+/// don't need to be set. `Index` identifies the component, and is inferred
+/// when the module is built.
+///
+/// This trait is implemented by the [`module`] macro, for the module's
+/// components and the ones imported from submodules.
+///
+/// [`module`]: macro.module.html
+pub trait ApplyOverride<I: ?Sized, Tag, S, Index> {
+    /// The state after the component is overridden
+    type Output;
+}
+
+impl<M: ?Sized, I: ?Sized, Tag, I2: ?Sized, Tag2, S, Index>
+    ApplyOverride<I, Tag, ComponentOverridden<I2, Tag2, S>, Index> for M
+where
+    M: ApplyOverride<I, Tag, S, Index>,
+{
+    type Output = ComponentOverridden<I2, Tag2, <M as ApplyOverride<I, Tag, S, Index>>::Output>;
+}
+
+/// Indicates that `Self` is the interface of the component `C` in the module
+/// `M`. Used by the [`module`] macro to find the component of an overridden
+/// interface. See [`ApplyOverride`].
+///
+/// [`module`]: macro.module.html
+/// [`ApplyOverride`]: trait.ApplyOverride.html
+pub trait ComponentInterface<C, M> {}
+
+impl<I: ?Sized, C: Component<M, Interface = I>, M: Module> ComponentInterface<C, M> for I {}
+
+/// Indicates that `Self` is the interface of the async component `C` in the
+/// module `M`. See [`ComponentInterface`].
+///
+/// [`ComponentInterface`]: trait.ComponentInterface.html
+#[cfg(feature = "async")]
+pub trait AsyncComponentInterface<C, M> {}
+
+#[cfg(feature = "async")]
+impl<I: ?Sized, C: AsyncComponent<M, Interface = I>, M: Module> AsyncComponentInterface<C, M>
+    for I
+{
+}
+
+/// Indicates that every required parameter has been set in the state `S`,
+/// so the module can be built. `Proof` has the [`ApplyOverride`] index of each
+/// override, followed by the [`ParametersReady`] proof of each component.
+///
+/// This trait is implemented by the [`module`] macro.
+///
+/// [`ApplyOverride`]: trait.ApplyOverride.html
+/// [`ParametersReady`]: trait.ParametersReady.html
+/// [`module`]: macro.module.html
+pub trait RequiredParametersSet<S, Proof> {}
+
+impl<M: ?Sized> RequiredParametersSet<(), ()> for M {}
+
+impl<M: ?Sized, I: ?Sized, Tag, S, Index, Proof>
+    RequiredParametersSet<ComponentOverridden<I, Tag, S>, (Index, Proof)> for M
+where
+    M: ApplyOverride<I, Tag, S, Index>,
+    M: RequiredParametersSet<<M as ApplyOverride<I, Tag, S, Index>>::Output, Proof>,
+{
+}
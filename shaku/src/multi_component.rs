@@ -0,0 +1,85 @@
+//! This module contains the trait definition for multi-bound components
+
+use crate::module::ModuleInstance;
+use crate::{BuildError, Interface, Module, ModuleBuildContext};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// Indicates that a module contains a set of components which implement the
+/// interface. This is useful for plugin-style systems, such as middlewares or
+/// event listeners, where any number of components implement the same trait.
+///
+/// Components are added to the set with `#[multi(dyn Interface)]` in the
+/// [module macro], and the whole set is injected with
+/// `#[shaku(inject)] listeners: Vec<Arc<dyn Listener>>`. The set also contains
+/// the components imported from submodules with `#[multi] dyn Interface`.
+///
+/// [module macro]: macro.module.html
+pub trait HasComponents<I: Interface + ?Sized>: ModuleInstance {
+    /// Build the components during module build. Usually this involves
+    /// calling [`ModuleBuildContext::build_multi_component`] with each
+    /// implementation.
+    ///
+    /// [`ModuleBuildContext::build_multi_component`]: struct.ModuleBuildContext.html#method.build_multi_component
+    fn build_components(context: &mut ModuleBuildContext<Self>) -> Vec<Arc<I>>
+    where
+        Self: Module + Sized;
+
+    /// Fallible version of [`build_components`]. Usually this involves
+    /// calling [`ModuleBuildContext::try_build_multi_component`] with each
+    /// implementation.
+    ///
+    /// By default, this calls [`build_components`].
+    ///
+    /// [`build_components`]: #tymethod.build_components
+    /// [`ModuleBuildContext::try_build_multi_component`]: struct.ModuleBuildContext.html#method.try_build_multi_component
+    fn try_build_components(
+        context: &mut ModuleBuildContext<Self>,
+    ) -> Result<Vec<Arc<I>>, BuildError>
+    where
+        Self: Module + Sized,
+    {
+        Ok(Self::build_components(context))
+    }
+
+    /// Get all of the components, in the order they were declared in the
+    /// module (the module's own components first, then the submodules').
+    ///
+    /// # Example
+    /// ```
+    /// # use shaku::{module, Component, Interface, HasComponents};
+    /// # use std::sync::Arc;
+    /// #
+    /// # trait Listener: Interface {}
+    /// #
+    /// # #[derive(Component)]
+    /// # #[shaku(interface = Listener)]
+    /// # struct ListenerA;
+    /// # impl Listener for ListenerA {}
+    /// #
+    /// # #[derive(Component)]
+    /// # #[shaku(interface = Listener)]
+    /// # struct ListenerB;
+    /// # impl Listener for ListenerB {}
+    /// #
+    /// # module! {
+    /// #     TestModule {
+    /// #         components = [#[multi(dyn Listener)] ListenerA, #[multi(dyn Listener)] ListenerB],
+    /// #         providers = []
+    /// #     }
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # let module = TestModule::builder().build();
+    /// #
+    /// let listeners: Vec<Arc<dyn Listener>> = module.resolve_all();
+    /// # assert_eq!(listeners.len(), 2);
+    /// # }
+    /// ```
+    fn resolve_all(&self) -> Vec<Arc<I>>;
+}
+
+/// The tag of a multi-bound component. Each component in a set is stored
+/// under its own tag, so they don't overwrite each other.
+#[doc(hidden)]
+pub struct Multi<C: ?Sized>(PhantomData<C>);
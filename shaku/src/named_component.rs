@@ -0,0 +1,125 @@
+//! This module contains the trait definition for named components
+
+use crate::module::ModuleInstance;
+use crate::{BuildError, Interface, Module, ModuleBuildContext};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// Indicates that a module contains a component which implements the
+/// interface, qualified by the `Tag` type. This allows a module to contain
+/// multiple components which implement the same interface, such as a primary
+/// and a replica database.
+///
+/// Tags are usually empty structs, for example `struct Replica;`. Named
+/// components are declared with `#[named(Replica)]` in the [module macro],
+/// and injected with `#[shaku(inject, named = Replica)]`.
+///
+/// [module macro]: macro.module.html
+pub trait HasNamedComponent<I: Interface + ?Sized, Tag: 'static>: ModuleInstance {
+    /// Build the component during module build. Usually this involves calling
+    /// [`ModuleBuildContext::build_named_component`] with the implementation.
+    ///
+    /// [`ModuleBuildContext::build_named_component`]: struct.ModuleBuildContext.html#method.build_named_component
+    fn build_named_component(context: &mut ModuleBuildContext<Self>) -> Arc<I>
+    where
+        Self: Module + Sized;
+
+    /// Fallible version of [`build_named_component`]. Usually this involves
+    /// calling [`ModuleBuildContext::try_build_named_component`] with the
+    /// implementation.
+    ///
+    /// By default, this calls [`build_named_component`].
+    ///
+    /// [`build_named_component`]: #tymethod.build_named_component
+    /// [`ModuleBuildContext::try_build_named_component`]: struct.ModuleBuildContext.html#method.try_build_named_component
+    fn try_build_named_component(
+        context: &mut ModuleBuildContext<Self>,
+    ) -> Result<Arc<I>, BuildError>
+    where
+        Self: Module + Sized,
+    {
+        Ok(Self::build_named_component(context))
+    }
+
+    /// Get a reference to the component. The ownership of the component is
+    /// shared via `Arc`.
+    ///
+    /// # Example
+    /// ```
+    /// # use shaku::{module, Component, Interface, HasNamedComponent};
+    /// # use std::sync::Arc;
+    /// #
+    /// # trait Foo: Interface {}
+    /// # struct Special;
+    /// #
+    /// # #[derive(Component)]
+    /// # #[shaku(interface = Foo)]
+    /// # struct FooImpl;
+    /// # impl Foo for FooImpl {}
+    /// #
+    /// # module! {
+    /// #     TestModule {
+    /// #         components = [#[named(Special)] FooImpl],
+    /// #         providers = []
+    /// #     }
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # let module = TestModule::builder().build();
+    /// #
+    /// let foo: Arc<dyn Foo> = HasNamedComponent::<dyn Foo, Special>::resolve_named(&module);
+    /// # }
+    /// ```
+    fn resolve_named(&self) -> Arc<I>;
+
+    /// Get a reference to the component.
+    ///
+    /// # Example
+    /// ```
+    /// # use shaku::{module, Component, Interface, HasNamedComponent};
+    /// #
+    /// # trait Foo: Interface {}
+    /// # struct Special;
+    /// #
+    /// # #[derive(Component)]
+    /// # #[shaku(interface = Foo)]
+    /// # struct FooImpl;
+    /// # impl Foo for FooImpl {}
+    /// #
+    /// # module! {
+    /// #     TestModule {
+    /// #         components = [#[named(Special)] FooImpl],
+    /// #         providers = []
+    /// #     }
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # let module = TestModule::builder().build();
+    /// #
+    /// let foo: &dyn Foo = HasNamedComponent::<dyn Foo, Special>::resolve_named_ref(&module);
+    /// # }
+    /// ```
+    fn resolve_named_ref(&self) -> &I;
+}
+
+/// Used to store values (resolved components, overrides, parameters) in the
+/// build context under a tag, so named components don't overwrite each other
+/// or the unnamed component of the same interface.
+pub(crate) struct Tagged<Tag, T> {
+    pub(crate) value: T,
+    // fn() -> Tag so the tag doesn't affect Send/Sync
+    _tag: PhantomData<fn() -> Tag>,
+}
+
+impl<Tag, T> Tagged<Tag, T> {
+    pub(crate) fn new(value: T) -> Self {
+        Self {
+            value,
+            _tag: PhantomData,
+        }
+    }
+}
+
+/// The tag of components which are not named
+#[doc(hidden)]
+pub struct Untagged;
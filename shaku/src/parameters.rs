@@ -1,4 +1,9 @@
+use crate::BuildError;
+use std::any::type_name;
+use std::env::{self, VarError};
+use std::error::Error;
 use std::marker::PhantomData;
+use std::str::FromStr;
 
 /// Used to store the parameters of a component. This is used instead of
 /// directly storing the parameters to avoid mixing up parameters of the same
@@ -30,3 +35,47 @@ impl<C, P: Default> Default for ComponentParameters<C, P> {
         }
     }
 }
+
+/// Create the default parameters of the component `C`, unless some of them
+/// are required. Used by [`Component::default_parameters`].
+///
+/// [`Component::default_parameters`]: trait.Component.html#method.default_parameters
+pub(crate) fn default_parameters<C: ?Sized, P: Default>(
+    required_parameters: &'static [&'static str],
+) -> Result<P, BuildError> {
+    match required_parameters.first() {
+        Some(parameter) => Err(BuildError::MissingParameter {
+            component: type_name::<C>(),
+            parameter,
+        }),
+        None => Ok(P::default()),
+    }
+}
+
+/// Read the parameter of the component `C` from an environment variable,
+/// parsing it with `FromStr`. Returns `None` if the variable is not set. This
+/// is used by the derive macro for `#[shaku(env = "...")]` parameters.
+pub fn env_parameter<C: ?Sized, T>(
+    variable: &'static str,
+    parameter: &'static str,
+) -> Result<Option<T>, BuildError>
+where
+    T: FromStr,
+    T::Err: Into<Box<dyn Error + Send + Sync>>,
+{
+    let invalid = |source: Box<dyn Error + Send + Sync>| BuildError::InvalidEnvVar {
+        component: type_name::<C>(),
+        parameter,
+        variable,
+        source,
+    };
+
+    match env::var(variable) {
+        Ok(value) => value
+            .parse()
+            .map(Some)
+            .map_err(|error: T::Err| invalid(error.into())),
+        Err(VarError::NotPresent) => Ok(None),
+        Err(error) => Err(invalid(error.into())),
+    }
+}
@@ -1,7 +1,7 @@
 //! This module contains trait definitions for provided services and interfaces
 
-use crate::module::ModuleInterface;
-use crate::Module;
+use crate::module::ModuleInstance;
+use crate::{Dependency, Module, Scope};
 use std::error::Error;
 
 /// Like [`Component`]s, providers provide a service by implementing an interface.
@@ -22,6 +22,30 @@ pub trait Provider<M: Module>: 'static {
     /// Provides the service, possibly resolving other components/providers
     /// to do so.
     fn provide(module: &M) -> Result<Box<Self::Interface>, Box<dyn Error>>;
+
+    /// Provides the service within a [`Scope`]. Scoped dependencies (provided
+    /// services which are injected as an `Arc`) are shared with the rest of
+    /// the scope, and other provided dependencies are also provided within
+    /// the scope.
+    ///
+    /// By default, this calls [`provide`] with the scope's module.
+    ///
+    /// [`Scope`]: struct.Scope.html
+    /// [`provide`]: #tymethod.provide
+    fn provide_scoped(scope: &Scope<M>) -> Result<Box<Self::Interface>, Box<dyn Error>> {
+        Self::provide(scope.module())
+    }
+
+    /// The dependencies of this provider, which are included in
+    /// [`Module::dependency_graph`]. The derive macro lists every injected
+    /// and provided property.
+    ///
+    /// By default, this returns no dependencies.
+    ///
+    /// [`Module::dependency_graph`]: trait.Module.html#method.dependency_graph
+    fn dependencies() -> Vec<Dependency> {
+        Vec::new()
+    }
 }
 
 /// The type signature of [`Provider::provide`]. This is used when overriding a
@@ -39,8 +63,23 @@ pub type ProviderFn<M, I> = Box<dyn (Fn(&M) -> Result<Box<I>, Box<dyn Error>>)>;
 #[cfg(feature = "thread_safe")]
 pub type ProviderFn<M, I> = Box<dyn (Fn(&M) -> Result<Box<I>, Box<dyn Error>>) + Send + Sync>;
 
+/// The type signature of a provider decorator. The decorator is given each
+/// provided service, and returns the service which wraps it. This is used
+/// when decorating a provider via [`ModuleBuilder::with_provider_decorator`]
+///
+/// [`ModuleBuilder::with_provider_decorator`]: struct.ModuleBuilder.html#method.with_provider_decorator
+#[cfg(not(feature = "thread_safe"))]
+pub type ProviderDecoratorFn<M, I> = Box<dyn Fn(Box<I>, &M) -> Box<I>>;
+/// The type signature of a provider decorator. The decorator is given each
+/// provided service, and returns the service which wraps it. This is used
+/// when decorating a provider via [`ModuleBuilder::with_provider_decorator`]
+///
+/// [`ModuleBuilder::with_provider_decorator`]: struct.ModuleBuilder.html#method.with_provider_decorator
+#[cfg(feature = "thread_safe")]
+pub type ProviderDecoratorFn<M, I> = Box<dyn (Fn(Box<I>, &M) -> Box<I>) + Send + Sync>;
+
 /// Indicates that a module contains a provider which implements the interface.
-pub trait HasProvider<I: ?Sized>: ModuleInterface {
+pub trait HasProvider<I: ?Sized>: ModuleInstance {
     /// Create a service using the provider registered with the interface `I`.
     /// Each call will create a new instance of the service.
     ///
@@ -70,4 +109,20 @@ pub trait HasProvider<I: ?Sized>: ModuleInterface {
     /// # }
     /// ```
     fn provide(&self) -> Result<Box<I>, Box<dyn Error>>;
+
+    /// Create a service within the [`Scope`], so it shares the scope's scoped
+    /// services. Each call will create a new instance of the service, even if
+    /// the provider is `#[scoped]` (see [`Scope::provide`]).
+    ///
+    /// By default, this calls [`provide`] on the scope's module.
+    ///
+    /// [`Scope`]: struct.Scope.html
+    /// [`Scope::provide`]: struct.Scope.html#method.provide
+    /// [`provide`]: #tymethod.provide
+    fn provide_scoped(scope: &Scope<Self>) -> Result<Box<I>, Box<dyn Error>>
+    where
+        Self: Module + Sized,
+    {
+        scope.module().provide()
+    }
 }
@@ -0,0 +1,109 @@
+//! This module contains the trait definition and storage for reloadable
+//! components
+
+use crate::{HasComponent, Interface};
+use std::mem;
+use std::sync::{Arc, RwLock};
+
+/// Indicates that a module contains a component which can be replaced after
+/// the module is built. Reloadable components are declared with
+/// `#[reloadable]` in the [module macro], and replaced via
+/// [`Module::replace`].
+///
+/// The replacement is an instance of the component declared in the module.
+/// It is started and decorated like the instance built by the module: its
+/// `on_start` hook runs when it is replaced, its `on_stop` hook runs when the
+/// module is shut down, and the module's decorators are applied to it.
+///
+/// After a replacement, [`HasComponent::resolve`] returns the new instance,
+/// and so do the services which resolve the component when they are created,
+/// such as providers and lazy components which have not been built yet.
+/// Components which were injected with the component keep the instance they
+/// were built with. To always use the latest instance, depend on a provider
+/// or resolve the component from the module instead of holding on to it.
+///
+/// Only the latest instance is kept by the module, so reloadable components
+/// can't be borrowed from it: [`HasComponent::resolve_ref`] panics.
+///
+/// # Example
+/// ```
+/// use shaku::{module, Component, HasComponent, Interface, Module};
+/// use std::sync::Arc;
+///
+/// trait Credentials: Interface {
+///     fn token(&self) -> &str;
+/// }
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Credentials)]
+/// struct CredentialsImpl {
+///     #[shaku(default = "first".to_string())]
+///     token: String,
+/// }
+/// impl Credentials for CredentialsImpl {
+///     fn token(&self) -> &str {
+///         &self.token
+///     }
+/// }
+///
+/// module! {
+///     MyModule {
+///         components = [#[reloadable] CredentialsImpl],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = MyModule::builder().build();
+/// let old = module.replace::<dyn Credentials>(Box::new(CredentialsImpl {
+///     token: "second".to_string(),
+/// }));
+///
+/// let credentials: Arc<dyn Credentials> = module.resolve();
+/// assert_eq!(credentials.token(), "second");
+/// assert_eq!(old.token(), "first");
+/// # }
+/// ```
+///
+/// [module macro]: macro.module.html
+/// [`Module::replace`]: trait.Module.html#method.replace
+/// [`HasComponent::resolve`]: trait.HasComponent.html#tymethod.resolve
+/// [`HasComponent::resolve_ref`]: trait.HasComponent.html#tymethod.resolve_ref
+pub trait HasReloadableComponent<I: Interface + ?Sized>: HasComponent<I> {
+    /// The component declared as `#[reloadable]`, which replacements are
+    /// instances of
+    type Component;
+
+    /// Replace the component, returning the previous instance. See
+    /// [`Module::replace`].
+    ///
+    /// [`Module::replace`]: trait.Module.html#method.replace
+    fn replace_component(&self, component: Box<Self::Component>) -> Arc<I>;
+}
+
+/// Holds the latest instance of a `#[reloadable]` component. This is used by
+/// the [module macro].
+///
+/// [module macro]: macro.module.html
+pub struct ReloadableCell<I: ?Sized> {
+    component: RwLock<Arc<I>>,
+}
+
+impl<I: ?Sized> ReloadableCell<I> {
+    /// Create a cell holding the initial instance of the component
+    pub fn new(component: Arc<I>) -> Self {
+        ReloadableCell {
+            component: RwLock::new(component),
+        }
+    }
+
+    /// Get the latest instance of the component
+    pub fn get(&self) -> Arc<I> {
+        Arc::clone(&self.component.read().unwrap())
+    }
+
+    /// Replace the component, returning the previous instance
+    pub fn replace(&self, component: Arc<I>) -> Arc<I> {
+        mem::replace(&mut *self.component.write().unwrap(), component)
+    }
+}
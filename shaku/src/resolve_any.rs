@@ -0,0 +1,102 @@
+//! This module contains the trait definition for type-erased resolution
+
+use std::any::{type_name, Any, TypeId};
+use std::sync::Arc;
+
+/// A type-erased component, as returned by [`ResolveAny::resolve_any`]. It
+/// contains the component's `Arc<I>`, where `I` is the interface.
+///
+/// [`ResolveAny::resolve_any`]: trait.ResolveAny.html#tymethod.resolve_any
+#[cfg(not(feature = "thread_safe"))]
+pub type AnyComponent = Arc<dyn Any>;
+/// A type-erased component, as returned by [`ResolveAny::resolve_any`]. It
+/// contains the component's `Arc<I>`, where `I` is the interface.
+///
+/// [`ResolveAny::resolve_any`]: trait.ResolveAny.html#tymethod.resolve_any
+#[cfg(feature = "thread_safe")]
+pub type AnyComponent = Arc<dyn Any + Send + Sync>;
+
+/// An interface which a module has a component for. See
+/// [`ResolveAny::interfaces`].
+///
+/// [`ResolveAny::interfaces`]: trait.ResolveAny.html#tymethod.interfaces
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct InterfaceInfo {
+    /// The `TypeId` of the interface, which can be passed to
+    /// [`ResolveAny::resolve_any`]
+    ///
+    /// [`ResolveAny::resolve_any`]: trait.ResolveAny.html#tymethod.resolve_any
+    pub type_id: TypeId,
+    /// The type name of the interface
+    pub type_name: &'static str,
+}
+
+impl InterfaceInfo {
+    /// The info of the interface `I`
+    pub fn of<I: ?Sized + 'static>() -> Self {
+        InterfaceInfo {
+            type_id: TypeId::of::<I>(),
+            type_name: type_name::<I>(),
+        }
+    }
+}
+
+/// Resolve components without naming their interface statically, such as in
+/// admin consoles or generic health checks. Modules created via the
+/// [module macro] implement this trait for their components and the
+/// components they import from submodules. Named and multi-bound components
+/// are not included, since they are not resolved by their interface alone.
+///
+/// # Example
+/// ```
+/// use shaku::{module, Component, Interface, ResolveAny};
+/// use std::any::TypeId;
+/// use std::sync::Arc;
+///
+/// trait Foo: Interface {}
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Foo)]
+/// struct FooImpl;
+/// impl Foo for FooImpl {}
+///
+/// module! {
+///     TestModule {
+///         components = [FooImpl],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = TestModule::builder().build();
+///
+/// for interface in module.interfaces() {
+///     println!("{}", interface.type_name);
+/// }
+///
+/// let foo = module.resolve_any(TypeId::of::<dyn Foo>()).unwrap();
+/// let foo: &Arc<dyn Foo> = foo.downcast_ref().unwrap();
+/// # }
+/// ```
+///
+/// [module macro]: macro.module.html
+pub trait ResolveAny {
+    /// Get the component of the interface with the `TypeId`. The returned
+    /// value contains an `Arc<I>`, where `I` is the interface. Returns `None`
+    /// if the module has no component for the interface.
+    ///
+    /// Like [`HasComponent::resolve`], lazy components are built if they have
+    /// not been resolved yet. Lazy async components can't be built
+    /// synchronously, so `None` is returned until they are resolved via
+    /// [`HasComponent::resolve_async`].
+    ///
+    /// [`HasComponent::resolve`]: trait.HasComponent.html#tymethod.resolve
+    /// [`HasComponent::resolve_async`]: trait.HasComponent.html#method.resolve_async
+    fn resolve_any(&self, interface: TypeId) -> Option<AnyComponent>;
+
+    /// The interfaces which can be resolved via [`resolve_any`], including
+    /// the ones imported from submodules.
+    ///
+    /// [`resolve_any`]: #tymethod.resolve_any
+    fn interfaces(&self) -> Vec<InterfaceInfo>;
+}
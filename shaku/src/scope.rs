@@ -0,0 +1,132 @@
+//! This module contains scopes, which cache scoped services for a unit of work
+
+use crate::module::ComponentMap;
+use crate::{HasProvider, Interface, Module};
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+
+/// A unit of work, such as an HTTP request or a database transaction, which is
+/// created via [`Module::create_scope`]. Services provided by `#[scoped]`
+/// providers are created once per scope and shared by everything resolved from
+/// the scope. They are dropped when the scope is dropped.
+///
+/// Components are singletons, so they are still resolved from the module (see
+/// [`module`]).
+///
+/// # Example
+/// ```
+/// use shaku::{module, HasProvider, Interface, Module, Provider};
+/// use std::sync::Arc;
+///
+/// trait Transaction: Interface {}
+/// trait UserRepository {}
+///
+/// #[derive(Provider)]
+/// #[shaku(interface = Transaction)]
+/// struct TransactionImpl;
+/// impl Transaction for TransactionImpl {}
+///
+/// #[derive(Provider)]
+/// #[shaku(interface = UserRepository)]
+/// struct UserRepositoryImpl {
+///     // Scoped services are injected as an Arc
+///     #[shaku(provide)]
+///     transaction: Arc<dyn Transaction>,
+/// }
+/// impl UserRepository for UserRepositoryImpl {}
+///
+/// module! {
+///     MyModule {
+///         components = [],
+///         providers = [#[scoped] TransactionImpl, UserRepositoryImpl]
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = MyModule::builder().build();
+/// let scope = module.create_scope();
+///
+/// let transaction1: Arc<dyn Transaction> = scope.provide().unwrap();
+/// let transaction2: Arc<dyn Transaction> = scope.provide().unwrap();
+/// assert!(Arc::ptr_eq(&transaction1, &transaction2));
+/// # }
+/// ```
+///
+/// [`Module::create_scope`]: trait.Module.html#method.create_scope
+/// [`module`]: #method.module
+pub struct Scope<'m, M: Module> {
+    module: &'m M,
+    services: Mutex<ComponentMap>,
+}
+
+impl<'m, M: Module> Scope<'m, M> {
+    /// Create an empty scope. Usually scopes are created via
+    /// [`Module::create_scope`].
+    ///
+    /// [`Module::create_scope`]: trait.Module.html#method.create_scope
+    pub fn new(module: &'m M) -> Self {
+        Scope {
+            module,
+            services: Mutex::new(ComponentMap::new()),
+        }
+    }
+
+    /// The module which this scope was created from. Use it to resolve
+    /// components and (unscoped) providers.
+    pub fn module(&self) -> &'m M {
+        self.module
+    }
+
+    /// Get the scoped service of the interface `I`, creating it if this is the
+    /// first time it is requested in this scope. Use
+    /// [`HasProvider::provide_scoped`] to create unscoped services within the
+    /// scope.
+    ///
+    /// [`HasProvider::provide_scoped`]: trait.HasProvider.html#method.provide_scoped
+    pub fn provide<I: Interface + ?Sized>(&self) -> Result<Arc<I>, Box<dyn Error>>
+    where
+        M: HasScopedProvider<I>,
+    {
+        if let Some(service) = self.lock_services().get::<Arc<I>>() {
+            return Ok(Arc::clone(service));
+        }
+
+        // The lock is not held while providing the service, since it may
+        // depend on other scoped services
+        let service = Arc::from(<M as HasProvider<I>>::provide_scoped(self)?);
+
+        // If the service was created in the meantime (by another thread), the
+        // first one wins
+        let mut services = self.lock_services();
+        let service = services.entry::<Arc<I>>().or_insert(service);
+        Ok(Arc::clone(service))
+    }
+
+    fn lock_services(&self) -> std::sync::MutexGuard<'_, ComponentMap> {
+        self.services
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Indicates that the module's provider of the interface is `#[scoped]`, so
+/// its services are cached by [`Scope::provide`].
+///
+/// Outside of a scope, [`HasProvider::provide`] creates the service (and its
+/// scoped dependencies) in a new scope each time.
+///
+/// [`Scope::provide`]: struct.Scope.html#method.provide
+/// [`HasProvider::provide`]: trait.HasProvider.html#tymethod.provide
+pub trait HasScopedProvider<I: Interface + ?Sized>: HasProvider<I> {}
+
+/// The type signature of [`Provider::provide_scoped`].
+///
+/// [`Provider::provide_scoped`]: trait.Provider.html#method.provide_scoped
+#[cfg(not(feature = "thread_safe"))]
+pub type ScopedProviderFn<M, I> = Box<dyn (Fn(&Scope<M>) -> Result<Box<I>, Box<dyn Error>>)>;
+/// The type signature of [`Provider::provide_scoped`].
+///
+/// [`Provider::provide_scoped`]: trait.Provider.html#method.provide_scoped
+#[cfg(feature = "thread_safe")]
+pub type ScopedProviderFn<M, I> =
+    Box<dyn (Fn(&Scope<M>) -> Result<Box<I>, Box<dyn Error>>) + Send + Sync>;
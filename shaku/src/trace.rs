@@ -0,0 +1,196 @@
+//! Spans which are emitted when the `tracing` feature is enabled. Without the
+//! feature, these helpers only call the given function or return the given
+//! future.
+
+// The type parameters only name the spans, so they are unused without the
+// feature
+#![cfg_attr(not(feature = "tracing"), allow(clippy::extra_unused_type_parameters))]
+
+#[cfg(feature = "async")]
+use crate::BoxFuture;
+use crate::ComponentSource;
+use std::fmt::Display;
+#[cfg(feature = "tracing")]
+use {std::any::type_name, tracing::Level};
+
+/// Run the module build in a `shaku::build_module` span
+pub(crate) fn module_build<M, T>(f: impl FnOnce() -> T) -> T {
+    #[cfg(feature = "tracing")]
+    {
+        module_span::<M>().in_scope(f)
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    f()
+}
+
+/// Run the async module build in a `shaku::build_module` span
+#[cfg(feature = "async")]
+pub(crate) fn module_build_async<M, T: 'static>(
+    future: BoxFuture<'static, T>,
+) -> BoxFuture<'static, T> {
+    #[cfg(feature = "tracing")]
+    {
+        use tracing::Instrument;
+
+        Box::pin(future.instrument(module_span::<M>()))
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    future
+}
+
+/// Run the component build in a `shaku::build_component` span
+pub(crate) fn build_component<C, I: ?Sized, T>(
+    source: ComponentSource,
+    f: impl FnOnce() -> T,
+) -> T {
+    #[cfg(feature = "tracing")]
+    {
+        component_span::<C, I>(source).in_scope(f)
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    {
+        let _ = source;
+        f()
+    }
+}
+
+/// Run the async component build in a `shaku::build_component` span
+#[cfg(feature = "async")]
+pub(crate) fn build_component_async<'a, C, I: ?Sized, T: 'a>(
+    source: ComponentSource,
+    future: BoxFuture<'a, T>,
+) -> BoxFuture<'a, T> {
+    #[cfg(feature = "tracing")]
+    {
+        use tracing::Instrument;
+
+        Box::pin(future.instrument(component_span::<C, I>(source)))
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    {
+        let _ = source;
+        future
+    }
+}
+
+/// Run a provider in a `shaku::provide` span
+pub(crate) fn provide<P, I: ?Sized, T, E: Display>(
+    f: impl FnOnce() -> Result<T, E>,
+) -> Result<T, E> {
+    #[cfg(feature = "tracing")]
+    {
+        provide_span::<P, I>().in_scope(|| record_error(f(), "provider failed"))
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    f()
+}
+
+/// Run an async provider in a `shaku::provide` span
+#[cfg(feature = "async")]
+pub(crate) fn provide_async<'a, P, I: ?Sized, T: 'a, E: Display + 'a>(
+    future: BoxFuture<'a, Result<T, E>>,
+) -> BoxFuture<'a, Result<T, E>> {
+    #[cfg(feature = "tracing")]
+    {
+        use tracing::Instrument;
+
+        let span = provide_span::<P, I>();
+        Box::pin(async move { record_error(future.await, "provider failed") }.instrument(span))
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    future
+}
+
+/// Initialize a lazy component in a `shaku::lazy_component` span. This is
+/// used by the [`module`] macro.
+///
+/// [`module`]: macro.module.html
+pub fn trace_lazy_component<C, I: ?Sized, T>(f: impl FnOnce() -> T) -> T {
+    #[cfg(feature = "tracing")]
+    {
+        lazy_span::<C, I>().in_scope(f)
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    f()
+}
+
+/// Initialize a lazy async component in a `shaku::lazy_component` span. This
+/// is used by the [`module`] macro.
+///
+/// [`module`]: macro.module.html
+#[cfg(feature = "async")]
+pub fn trace_lazy_component_async<'a, C, I: ?Sized, T: 'a>(
+    future: BoxFuture<'a, T>,
+) -> BoxFuture<'a, T> {
+    #[cfg(feature = "tracing")]
+    {
+        use tracing::Instrument;
+
+        Box::pin(future.instrument(lazy_span::<C, I>()))
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    future
+}
+
+#[cfg(feature = "tracing")]
+fn module_span<M>() -> tracing::Span {
+    tracing::span!(
+        Level::INFO,
+        "shaku::build_module",
+        module = type_name::<M>()
+    )
+}
+
+#[cfg(feature = "tracing")]
+fn component_span<C, I: ?Sized>(source: ComponentSource) -> tracing::Span {
+    tracing::span!(
+        Level::DEBUG,
+        "shaku::build_component",
+        component = type_name::<C>(),
+        interface = type_name::<I>(),
+        source = ?source
+    )
+}
+
+#[cfg(feature = "tracing")]
+fn provide_span<P, I: ?Sized>() -> tracing::Span {
+    tracing::span!(
+        Level::DEBUG,
+        "shaku::provide",
+        provider = type_name::<P>(),
+        interface = type_name::<I>()
+    )
+}
+
+#[cfg(feature = "tracing")]
+fn lazy_span<C, I: ?Sized>() -> tracing::Span {
+    tracing::span!(
+        Level::DEBUG,
+        "shaku::lazy_component",
+        component = type_name::<C>(),
+        interface = type_name::<I>()
+    )
+}
+
+/// Emit an error event (in the current span) if the result is an error
+pub(crate) fn record_error<T, E: Display>(result: Result<T, E>, message: &str) -> Result<T, E> {
+    #[cfg(feature = "tracing")]
+    {
+        if let Err(error) = &result {
+            tracing::error!(error = %error, "{}", message);
+        }
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    let _ = message;
+
+    result
+}
@@ -0,0 +1,449 @@
+//! Test async components and `ModuleBuilder::build_async`
+#![cfg(feature = "async")]
+
+use shaku::{module, AsyncComponent, BuildError, Component, HasComponent, Interface, Module};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+trait Config: Interface {
+    fn url(&self) -> String;
+}
+trait Database: Interface {
+    fn connection_count(&self) -> usize;
+}
+trait Repository: Interface {
+    fn connection_count(&self) -> usize;
+}
+trait Cache: Interface {
+    fn size(&self) -> usize;
+}
+trait Connection: Interface {
+    fn is_open(&self) -> bool;
+}
+
+#[derive(Component)]
+#[shaku(interface = Config)]
+struct ConfigImpl {
+    #[shaku(default = "db://localhost".to_string())]
+    url: String,
+}
+impl Config for ConfigImpl {
+    fn url(&self) -> String {
+        self.url.clone()
+    }
+}
+
+#[derive(AsyncComponent)]
+#[shaku(interface = Database, try_build = DatabaseImpl::connect)]
+struct DatabaseImpl {
+    #[shaku(inject)]
+    config: Arc<dyn Config>,
+    #[shaku(default)]
+    connection_count: usize,
+}
+impl Database for DatabaseImpl {
+    fn connection_count(&self) -> usize {
+        self.connection_count
+    }
+}
+
+impl DatabaseImpl {
+    async fn connect(mut self) -> Result<Self, String> {
+        if !self.config.url().starts_with("db://") {
+            return Err(format!("Invalid database url: {}", self.config.url()));
+        }
+
+        self.connection_count = 10;
+        Ok(self)
+    }
+}
+
+/// A sync component which depends on an async component
+#[derive(Component)]
+#[shaku(interface = Repository)]
+struct RepositoryImpl {
+    #[shaku(inject)]
+    database: Arc<dyn Database>,
+}
+impl Repository for RepositoryImpl {
+    fn connection_count(&self) -> usize {
+        self.database.connection_count()
+    }
+}
+
+static CACHE_BUILD_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(AsyncComponent)]
+#[shaku(interface = Cache)]
+struct CacheImpl {
+    #[shaku(inject)]
+    database: Arc<dyn Database>,
+}
+impl Cache for CacheImpl {
+    fn size(&self) -> usize {
+        self.database.connection_count() * 2
+    }
+}
+
+/// A manually implemented async component
+struct ManualCacheImpl;
+impl Cache for ManualCacheImpl {
+    fn size(&self) -> usize {
+        5
+    }
+}
+impl<M: shaku::Module> AsyncComponent<M> for ManualCacheImpl {
+    type Interface = dyn Cache;
+    type Parameters = ();
+
+    fn build_async(
+        _: &mut shaku::ModuleBuildContext<M>,
+        _: Self::Parameters,
+    ) -> shaku::BoxFuture<'_, Result<Box<dyn Cache>, Box<dyn std::error::Error + Send + Sync>>>
+    {
+        Box::pin(async {
+            CACHE_BUILD_COUNT.fetch_add(1, Ordering::SeqCst);
+            Ok(Box::new(ManualCacheImpl) as Box<dyn Cache>)
+        })
+    }
+}
+
+/// An async component with lifecycle hooks
+#[derive(AsyncComponent)]
+#[shaku(
+    interface = Connection,
+    on_start = ConnectionImpl::open,
+    on_stop = ConnectionImpl::close
+)]
+struct ConnectionImpl {
+    #[shaku(default)]
+    open: AtomicBool,
+}
+impl Connection for ConnectionImpl {
+    fn is_open(&self) -> bool {
+        self.open.load(Ordering::SeqCst)
+    }
+}
+impl ConnectionImpl {
+    fn open(&self) {
+        self.open.store(true, Ordering::SeqCst);
+    }
+
+    fn close(&self) {
+        self.open.store(false, Ordering::SeqCst);
+    }
+}
+
+/// An async component with a required parameter
+#[derive(AsyncComponent)]
+#[shaku(interface = Database)]
+struct RemoteDatabaseImpl {
+    #[allow(dead_code)]
+    url: String,
+}
+impl Database for RemoteDatabaseImpl {
+    fn connection_count(&self) -> usize {
+        100
+    }
+}
+
+/// A minimal executor which runs the future on the current thread
+fn block_on<F: Future>(future: F) -> F::Output {
+    struct ThreadWaker(Thread);
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut context = Context::from_waker(&waker);
+    let mut future = Box::pin(future);
+
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+module! {
+    TestModule {
+        components = [ConfigImpl, #[async] DatabaseImpl, RepositoryImpl, #[async] CacheImpl],
+        providers = []
+    }
+}
+
+module! {
+    LazyModule {
+        components = [
+            ConfigImpl,
+            #[async] DatabaseImpl,
+            #[lazy] RepositoryImpl,
+            #[lazy] #[async] ManualCacheImpl
+        ],
+        providers = []
+    }
+}
+
+module! {
+    RemoteModule {
+        components = [#[async] RemoteDatabaseImpl],
+        providers = []
+    }
+}
+
+module! {
+    ConnectionModule {
+        components = [#[async] ConnectionImpl],
+        providers = []
+    }
+}
+
+#[test]
+fn build_async_resolves_dependencies() {
+    block_on(async {
+        let module = TestModule::builder().build_async().await;
+
+        let repository: &dyn Repository = module.resolve_ref();
+        let cache: &dyn Cache = module.resolve_ref();
+        assert_eq!(repository.connection_count(), 10);
+        assert_eq!(cache.size(), 20);
+    });
+}
+
+#[test]
+fn build_async_error_is_propagated() {
+    block_on(async {
+        let result = TestModule::builder()
+            .with_component_parameters::<ConfigImpl>(ConfigImplParameters {
+                url: "http://localhost".to_string(),
+            })
+            .try_build_async()
+            .await;
+
+        match result {
+            Err(BuildError::ComponentBuild { component, source }) => {
+                assert_eq!(component, "async_components::DatabaseImpl");
+                assert_eq!(source.to_string(), "Invalid database url: http://localhost");
+            }
+            Err(e) => panic!("Unexpected error: {}", e),
+            Ok(_) => panic!("Expected the build to fail"),
+        }
+    });
+}
+
+#[test]
+fn async_component_parameters() {
+    block_on(async {
+        let module = TestModule::builder()
+            .with_async_component_parameters::<DatabaseImpl>(DatabaseImplParameters {
+                connection_count: 3,
+            })
+            .build_async()
+            .await;
+
+        // The try_build function overwrites the parameter
+        let database: &dyn Database = module.resolve_ref();
+        assert_eq!(database.connection_count(), 10);
+    });
+}
+
+#[test]
+fn try_build_requires_build_async() {
+    match TestModule::builder().try_build() {
+        Err(BuildError::AsyncComponent { component }) => {
+            assert_eq!(component, "async_components::DatabaseImpl");
+        }
+        Err(e) => panic!("Unexpected error: {}", e),
+        Ok(_) => panic!("Expected the build to fail"),
+    }
+}
+
+#[test]
+#[should_panic(
+    expected = "async_components::DatabaseImpl is an async component. Use ModuleBuilder::build_async to build the module."
+)]
+fn build_panics_without_build_async() {
+    TestModule::builder().build();
+}
+
+#[test]
+fn lazy_async_component() {
+    block_on(async {
+        let module = LazyModule::builder().build_async().await;
+        assert_eq!(CACHE_BUILD_COUNT.load(Ordering::SeqCst), 0);
+
+        let cache: Arc<dyn Cache> = module.resolve_async().await;
+        assert_eq!(cache.size(), 5);
+        assert_eq!(CACHE_BUILD_COUNT.load(Ordering::SeqCst), 1);
+
+        // The component is only built once, and can now be resolved synchronously
+        let cache_ref: &dyn Cache = module.resolve_ref();
+        assert!(Arc::ptr_eq(
+            &cache,
+            &HasComponent::<dyn Cache>::resolve(&module)
+        ));
+        assert_eq!(cache_ref.size(), 5);
+        assert_eq!(CACHE_BUILD_COUNT.load(Ordering::SeqCst), 1);
+
+        // Sync lazy components can be mixed with lazy async components
+        let repository: &dyn Repository = module.resolve_ref();
+        assert_eq!(repository.connection_count(), 10);
+    });
+}
+
+#[test]
+#[should_panic(
+    expected = "async_components::ManualCacheImpl has not been initialized yet. Use HasComponent::resolve_async to resolve it."
+)]
+fn lazy_async_component_requires_resolve_async() {
+    block_on(async {
+        let module = LazyModule::builder().build_async().await;
+        let _: &dyn Cache = module.resolve_ref();
+    });
+}
+
+#[test]
+fn override_async_component() {
+    block_on(async {
+        let module = TestModule::builder()
+            .with_component_override::<dyn Database>(Box::new(FakeDatabase))
+            .build_async()
+            .await;
+
+        let repository: &dyn Repository = module.resolve_ref();
+        assert_eq!(repository.connection_count(), 1);
+    });
+}
+
+struct FakeDatabase;
+impl Database for FakeDatabase {
+    fn connection_count(&self) -> usize {
+        1
+    }
+}
+
+#[test]
+fn async_component_lifecycle_hooks() {
+    block_on(async {
+        let module = ConnectionModule::builder().build_async().await;
+        let connection: &dyn Connection = module.resolve_ref();
+        assert!(connection.is_open());
+
+        module.shutdown();
+        assert!(!connection.is_open());
+    });
+}
+
+/// Counts one extra connection, to check that it wraps the database
+struct PooledDatabase(Arc<dyn Database>);
+impl Database for PooledDatabase {
+    fn connection_count(&self) -> usize {
+        self.0.connection_count() + 1
+    }
+}
+
+#[test]
+fn decorate_async_component() {
+    block_on(async {
+        let module = TestModule::builder()
+            .with_component_decorator::<dyn Database>(Box::new(|database, _| {
+                Box::new(PooledDatabase(database))
+            }))
+            .build_async()
+            .await;
+
+        let repository: &dyn Repository = module.resolve_ref();
+        assert_eq!(repository.connection_count(), 11);
+    });
+}
+
+#[test]
+fn decorate_overridden_async_component() {
+    block_on(async {
+        let module = TestModule::builder()
+            .with_component_override::<dyn Database>(Box::new(FakeDatabase))
+            .with_component_decorator::<dyn Database>(Box::new(|database, _| {
+                Box::new(PooledDatabase(database))
+            }))
+            .build_async()
+            .await;
+
+        let repository: &dyn Repository = module.resolve_ref();
+        assert_eq!(repository.connection_count(), 2);
+    });
+}
+
+#[test]
+fn decorate_async_component_override_fn() {
+    block_on(async {
+        let module = TestModule::builder()
+            .with_component_override_fn::<dyn Database>(Box::new(|_| Box::new(FakeDatabase)))
+            .with_component_decorator::<dyn Database>(Box::new(|database, _| {
+                Box::new(PooledDatabase(database))
+            }))
+            .build_async()
+            .await;
+
+        let repository: &dyn Repository = module.resolve_ref();
+        assert_eq!(repository.connection_count(), 2);
+    });
+}
+
+/// Async components overridden by a fn can be built synchronously, and are
+/// still decorated
+#[test]
+fn decorate_async_component_override_fn_sync() {
+    let module = RemoteModule::builder()
+        .with_component_override_fn::<dyn Database>(Box::new(|_| Box::new(FakeDatabase)))
+        .with_component_decorator::<dyn Database>(Box::new(|database, _| {
+            Box::new(PooledDatabase(database))
+        }))
+        .build();
+
+    let database: &dyn Database = module.resolve_ref();
+    assert_eq!(database.connection_count(), 2);
+}
+
+module! {
+    LazyCacheModule {
+        components = [ConfigImpl, #[async] DatabaseImpl, #[lazy] #[async] CacheImpl],
+        providers = []
+    }
+}
+
+/// Lazy async components can't be built by resolve_any
+#[test]
+fn resolve_any_lazy_async_component() {
+    use shaku::ResolveAny;
+    use std::any::TypeId;
+
+    block_on(async {
+        let module = LazyCacheModule::builder().build_async().await;
+        assert!(module.resolve_any(TypeId::of::<dyn Cache>()).is_none());
+
+        let cache: Arc<dyn Cache> = module.resolve_async().await;
+        let resolved = module.resolve_any(TypeId::of::<dyn Cache>()).unwrap();
+        let resolved = resolved.downcast_ref::<Arc<dyn Cache>>().unwrap();
+        assert!(Arc::ptr_eq(&cache, resolved));
+    });
+}
+
+/// Overridden async components don't need their required parameters set
+#[test]
+fn override_async_component_with_required_parameter() {
+    block_on(async {
+        let module = RemoteModule::builder()
+            .with_component_override::<dyn Database>(Box::new(FakeDatabase))
+            .build_async()
+            .await;
+
+        let database: &dyn Database = module.resolve_ref();
+        assert_eq!(database.connection_count(), 1);
+    });
+}
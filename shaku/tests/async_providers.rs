@@ -0,0 +1,188 @@
+//! Test async providers and `HasAsyncProvider::provide_async`
+#![cfg(feature = "async")]
+
+use shaku::{
+    module, AsyncProvider, BoxFuture, Component, HasAsyncProvider, HasComponent, Interface,
+};
+use std::error::Error;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+trait Pool: Interface {
+    fn checkout(&self) -> Result<usize, String>;
+}
+trait Transaction: Interface {
+    fn id(&self) -> usize;
+}
+trait Repository: Interface {
+    fn transaction_id(&self) -> usize;
+}
+
+#[derive(Component)]
+#[shaku(interface = Pool)]
+struct PoolImpl {
+    #[shaku(default)]
+    connections: AtomicUsize,
+    #[shaku(default = usize::MAX)]
+    max_connections: usize,
+}
+impl Pool for PoolImpl {
+    fn checkout(&self) -> Result<usize, String> {
+        let id = self.connections.fetch_add(1, Ordering::SeqCst);
+        if id >= self.max_connections {
+            return Err("The pool is exhausted".to_string());
+        }
+
+        Ok(id)
+    }
+}
+
+/// A manually implemented async provider, which awaits a component
+struct TransactionImpl(usize);
+impl Transaction for TransactionImpl {
+    fn id(&self) -> usize {
+        self.0
+    }
+}
+impl<M: shaku::Module + HasComponent<dyn Pool>> AsyncProvider<M> for TransactionImpl {
+    type Interface = dyn Transaction;
+
+    fn provide_async(
+        module: &M,
+    ) -> BoxFuture<'_, Result<Box<dyn Transaction>, Box<dyn Error + Send + Sync>>> {
+        Box::pin(async move {
+            let pool: &dyn Pool = module.resolve_ref();
+            let id = pool.checkout()?;
+
+            Ok(Box::new(TransactionImpl(id)) as Box<dyn Transaction>)
+        })
+    }
+}
+
+/// An async provider which depends on another async provider
+#[derive(AsyncProvider)]
+#[shaku(interface = Repository)]
+struct RepositoryImpl {
+    #[shaku(inject)]
+    _pool: Arc<dyn Pool>,
+    #[shaku(provide)]
+    transaction: Box<dyn Transaction>,
+}
+impl Repository for RepositoryImpl {
+    fn transaction_id(&self) -> usize {
+        self.transaction.id()
+    }
+}
+
+module! {
+    TestModule {
+        components = [PoolImpl],
+        providers = [#[async] TransactionImpl, #[async] RepositoryImpl]
+    }
+}
+
+module! {
+    ParentModule {
+        components = [],
+        providers = [],
+
+        use TestModule {
+            components = [dyn Pool],
+            providers = [#[async] dyn Repository]
+        }
+    }
+}
+
+/// A minimal executor which runs the future on the current thread
+fn block_on<F: Future>(future: F) -> F::Output {
+    struct ThreadWaker(Thread);
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut context = Context::from_waker(&waker);
+    let mut future = Box::pin(future);
+
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[test]
+fn provide_async_creates_new_services() {
+    block_on(async {
+        let module = TestModule::builder().build();
+
+        let repository1: Box<dyn Repository> = module.provide_async().await.unwrap();
+        let repository2: Box<dyn Repository> = module.provide_async().await.unwrap();
+        assert_eq!(repository1.transaction_id(), 0);
+        assert_eq!(repository2.transaction_id(), 1);
+    });
+}
+
+#[test]
+fn provide_async_error() {
+    block_on(async {
+        let module = TestModule::builder()
+            .with_component_parameters::<PoolImpl>(PoolImplParameters {
+                connections: AtomicUsize::new(0),
+                max_connections: 0,
+            })
+            .build();
+
+        let result: Result<Box<dyn Repository>, _> = module.provide_async().await;
+        match result {
+            Err(error) => assert_eq!(error.to_string(), "The pool is exhausted"),
+            Ok(_) => panic!("Expected the provider to fail"),
+        }
+    });
+}
+
+#[test]
+fn override_async_provider() {
+    block_on(async {
+        let module = TestModule::builder()
+            .with_async_provider_override::<dyn Transaction>(Box::new(|_: &TestModule| {
+                let future: BoxFuture<_> =
+                    Box::pin(async { Ok(Box::new(TransactionImpl(42)) as Box<dyn Transaction>) });
+                future
+            }))
+            .build();
+
+        let repository: Box<dyn Repository> = module.provide_async().await.unwrap();
+        assert_eq!(repository.transaction_id(), 42);
+    });
+}
+
+#[test]
+fn async_subprovider() {
+    block_on(async {
+        let submodule = Arc::new(TestModule::builder().build());
+        let module = ParentModule::builder(submodule).build();
+
+        let repository: Box<dyn Repository> = module.provide_async().await.unwrap();
+        assert_eq!(repository.transaction_id(), 0);
+    });
+}
+
+/// Async providers can be used through module traits
+#[test]
+fn async_provider_module_trait() {
+    trait RepositoryModule: HasAsyncProvider<dyn Repository> {}
+    impl RepositoryModule for TestModule {}
+
+    block_on(async {
+        let module: Arc<dyn RepositoryModule> = Arc::new(TestModule::builder().build());
+        let repository: Box<dyn Repository> = module.provide_async().await.unwrap();
+        assert_eq!(repository.transaction_id(), 0);
+    });
+}
@@ -0,0 +1,165 @@
+//! Test build observers and `BuildReport`
+
+use shaku::{module, BuildObserver, BuildReport, Component, ComponentBuildInfo, ComponentSource};
+use shaku::{ComponentTiming, HasComponent, Interface, Module, ModuleBuildContext};
+use std::any::type_name;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+trait Database: Interface {}
+trait Repository: Interface {}
+trait Cache: Interface {}
+trait Mailer: Interface {}
+
+/// Takes a while to build
+struct DatabaseImpl;
+impl Database for DatabaseImpl {}
+impl<M: Module> Component<M> for DatabaseImpl {
+    type Interface = dyn Database;
+    type Parameters = ();
+
+    fn build(_: &mut ModuleBuildContext<M>, _: Self::Parameters) -> Box<dyn Database> {
+        thread::sleep(Duration::from_millis(20));
+        Box::new(DatabaseImpl)
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Repository)]
+struct RepositoryImpl {
+    #[shaku(inject)]
+    #[allow(dead_code)]
+    database: Arc<dyn Database>,
+}
+impl Repository for RepositoryImpl {}
+
+#[derive(Component)]
+#[shaku(interface = Cache)]
+struct CacheImpl;
+impl Cache for CacheImpl {}
+
+#[derive(Component)]
+#[shaku(interface = Mailer)]
+struct MailerImpl;
+impl Mailer for MailerImpl {}
+
+module! {
+    TestModule {
+        components = [RepositoryImpl, DatabaseImpl, CacheImpl, #[lazy] MailerImpl],
+        providers = []
+    }
+}
+
+/// Records the started and finished callbacks
+#[derive(Clone, Default)]
+struct EventLog {
+    events: Arc<Mutex<Vec<String>>>,
+}
+impl BuildObserver for EventLog {
+    fn component_started(&self, info: &ComponentBuildInfo) {
+        self.events
+            .lock()
+            .unwrap()
+            .push(format!("start {}", info.component));
+    }
+
+    fn component_finished(&self, info: &ComponentBuildInfo, _: ComponentTiming) {
+        self.events
+            .lock()
+            .unwrap()
+            .push(format!("finish {}", info.component));
+    }
+}
+
+#[test]
+fn report_build_order() {
+    let report = BuildReport::new();
+    TestModule::builder().with_observer(report.clone()).build();
+
+    assert_eq!(
+        report.build_order(),
+        vec![
+            type_name::<DatabaseImpl>(),
+            type_name::<RepositoryImpl>(),
+            type_name::<CacheImpl>(),
+        ]
+    );
+    assert!(report.module_duration().is_some());
+}
+
+#[test]
+fn report_timings() {
+    let report = BuildReport::new();
+    TestModule::builder().with_observer(report.clone()).build();
+
+    let components = report.components();
+    let database = components[0].timing;
+    let repository = components[1].timing;
+    assert!(database.total >= Duration::from_millis(20));
+    assert_eq!(database.own, database.total);
+    assert!(repository.total >= database.total);
+    assert_eq!(repository.own, repository.total - database.total);
+
+    assert_eq!(
+        report.slowest()[0].info.component,
+        type_name::<DatabaseImpl>()
+    );
+}
+
+#[test]
+fn report_override_sources() {
+    let report = BuildReport::new();
+    TestModule::builder()
+        .with_observer(report.clone())
+        .with_component_override::<dyn Database>(Box::new(DatabaseImpl))
+        .with_component_override_fn::<dyn Cache>(Box::new(|_| Box::new(CacheImpl)))
+        .build();
+
+    let sources: Vec<(&str, ComponentSource)> = report
+        .components()
+        .iter()
+        .map(|report| (report.info.component, report.info.source))
+        .collect();
+    assert_eq!(
+        sources,
+        vec![
+            (
+                type_name::<DatabaseImpl>(),
+                ComponentSource::InstanceOverride
+            ),
+            (type_name::<RepositoryImpl>(), ComponentSource::Built),
+            (type_name::<CacheImpl>(), ComponentSource::FnOverride),
+        ]
+    );
+}
+
+#[test]
+fn report_lazy_components() {
+    let report = BuildReport::new();
+    let module = TestModule::builder().with_observer(report.clone()).build();
+    assert!(report.components().iter().all(|report| !report.info.lazy));
+
+    let _: &dyn Mailer = module.resolve_ref();
+    let mailer = report.components().pop().unwrap();
+    assert_eq!(mailer.info.component, type_name::<MailerImpl>());
+    assert!(mailer.info.lazy);
+}
+
+#[test]
+fn custom_observer() {
+    let log = EventLog::default();
+    TestModule::builder().with_observer(log.clone()).build();
+
+    assert_eq!(
+        *log.events.lock().unwrap(),
+        vec![
+            format!("start {}", type_name::<RepositoryImpl>()),
+            format!("start {}", type_name::<DatabaseImpl>()),
+            format!("finish {}", type_name::<DatabaseImpl>()),
+            format!("finish {}", type_name::<RepositoryImpl>()),
+            format!("start {}", type_name::<CacheImpl>()),
+            format!("finish {}", type_name::<CacheImpl>()),
+        ]
+    );
+}
@@ -1,7 +1,8 @@
 //! Runtime detection of circular dependencies (when not using the module macro). The module macro
 //! can detect cycles at compile time. See `ui/circular_dependency_compile_time.rs`.
 
-use shaku::{Component, HasComponent, Interface, ModuleBuildContext, ModuleBuilder};
+use shaku::{BuildError, Component, HasComponent, Interface, ModuleBuildContext, ModuleBuilder};
+use std::error::Error;
 use std::sync::Arc;
 
 trait Component1Trait: Interface {}
@@ -38,12 +39,26 @@ impl shaku::Module for TestModule {
             component2: Self::build_component(&mut context),
         }
     }
+
+    fn try_build(mut context: shaku::ModuleBuildContext<Self>) -> Result<Self, BuildError> {
+        Ok(Self {
+            component1: Self::try_build_component(&mut context)?,
+            component2: Self::try_build_component(&mut context)?,
+        })
+    }
 }
+impl shaku::ModuleInstance for TestModule {}
 impl shaku::HasComponent<dyn Component1Trait> for TestModule {
     fn build_component(context: &mut ModuleBuildContext<Self>) -> Arc<dyn Component1Trait> {
         context.build_component::<Component1>()
     }
 
+    fn try_build_component(
+        context: &mut ModuleBuildContext<Self>,
+    ) -> Result<Arc<dyn Component1Trait>, BuildError> {
+        context.try_build_component::<Component1>()
+    }
+
     fn resolve(&self) -> Arc<dyn Component1Trait> {
         Arc::clone(&self.component1)
     }
@@ -57,6 +72,12 @@ impl shaku::HasComponent<dyn Component2Trait> for TestModule {
         context.build_component::<Component2>()
     }
 
+    fn try_build_component(
+        context: &mut ModuleBuildContext<Self>,
+    ) -> Result<Arc<dyn Component2Trait>, BuildError> {
+        context.try_build_component::<Component2>()
+    }
+
     fn resolve(&self) -> Arc<dyn Component2Trait> {
         Arc::clone(&self.component2)
     }
@@ -71,9 +92,55 @@ impl shaku::HasComponent<dyn Component2Trait> for TestModule {
 /// detected during module build.
 #[test]
 #[should_panic(
-    expected = "Circular dependency detected while resolving dyn circular_dependency_runtime::Component1Trait. \
-    Resolution chain: [circular_dependency_runtime::Component1, circular_dependency_runtime::Component2]"
+    expected = "Circular dependency detected: circular_dependency_runtime::Component1 \
+    -> circular_dependency_runtime::Component2 -> circular_dependency_runtime::Component1"
 )]
 fn circular_dependency_runtime() {
     ModuleBuilder::<TestModule>::with_submodules(()).build();
 }
+
+/// The cycle is also returned as an error by the fallible build path
+#[test]
+fn circular_dependency_runtime_error() {
+    let result = ModuleBuilder::<TestModule>::with_submodules(()).try_build();
+
+    match result {
+        Err(BuildError::CircularDependency(error)) => {
+            let steps: Vec<(&str, &str)> = error
+                .chain()
+                .iter()
+                .map(|step| (step.component, step.interface))
+                .collect();
+            assert_eq!(
+                steps,
+                vec![
+                    (
+                        "circular_dependency_runtime::Component1",
+                        "dyn circular_dependency_runtime::Component1Trait"
+                    ),
+                    (
+                        "circular_dependency_runtime::Component2",
+                        "dyn circular_dependency_runtime::Component2Trait"
+                    ),
+                    (
+                        "circular_dependency_runtime::Component1",
+                        "dyn circular_dependency_runtime::Component1Trait"
+                    ),
+                ]
+            );
+        }
+        Err(e) => panic!("Unexpected error: {}", e),
+        Ok(_) => panic!("Expected the build to fail"),
+    }
+}
+
+/// Error reporters which walk the sources only print the cycle once
+#[test]
+fn circular_dependency_error_has_no_source() {
+    let error = ModuleBuilder::<TestModule>::with_submodules(())
+        .try_build()
+        .err()
+        .unwrap();
+
+    assert!(error.source().is_none());
+}
@@ -0,0 +1,225 @@
+//! Test decorating components and providers
+
+use shaku::{module, Component, HasComponent, HasProvider, Interface, Module, Provider};
+use shaku::{HasComponentMap, HasComponents, HasNamedComponent};
+use std::sync::Arc;
+
+trait Greeter: Interface {
+    fn greet(&self) -> String;
+}
+trait Prefix: Interface {
+    fn prefix(&self) -> String;
+}
+trait Request {
+    fn path(&self) -> String;
+}
+
+#[derive(Component)]
+#[shaku(interface = Greeter)]
+struct GreeterImpl;
+impl Greeter for GreeterImpl {
+    fn greet(&self) -> String {
+        "hello".to_string()
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Greeter)]
+struct LoudGreeterImpl;
+impl Greeter for LoudGreeterImpl {
+    fn greet(&self) -> String {
+        "HELLO".to_string()
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Prefix)]
+struct PrefixImpl;
+impl Prefix for PrefixImpl {
+    fn prefix(&self) -> String {
+        ">".to_string()
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = Request)]
+struct RequestImpl;
+impl Request for RequestImpl {
+    fn path(&self) -> String {
+        "/".to_string()
+    }
+}
+
+module! {
+    TestModule {
+        components = [GreeterImpl, PrefixImpl],
+        providers = [RequestImpl]
+    }
+}
+
+struct Loud;
+
+module! {
+    NamedModule {
+        components = [GreeterImpl, #[named(Loud)] LoudGreeterImpl],
+        providers = []
+    }
+}
+
+module! {
+    MultiModule {
+        components = [#[multi(dyn Greeter)] GreeterImpl, #[multi(dyn Greeter)] LoudGreeterImpl],
+        providers = []
+    }
+}
+
+module! {
+    MapModule {
+        components = [
+            #[multi(dyn Greeter)] #[key("quiet")] GreeterImpl,
+            #[multi(dyn Greeter)] #[key("loud")] LoudGreeterImpl
+        ],
+        providers = []
+    }
+}
+
+/// Wraps a greeter, adding a suffix to the greeting
+struct Suffixed {
+    inner: Arc<dyn Greeter>,
+    suffix: &'static str,
+}
+impl Greeter for Suffixed {
+    fn greet(&self) -> String {
+        format!("{}{}", self.inner.greet(), self.suffix)
+    }
+}
+
+fn suffix_decorator<M: Module>(
+    suffix: &'static str,
+) -> shaku::ComponentDecoratorFn<M, dyn Greeter> {
+    Box::new(move |inner, _| Box::new(Suffixed { inner, suffix }))
+}
+
+/// Decorators are applied in registration order, so the last one is outermost
+#[test]
+fn decorators_are_composed() {
+    let module = TestModule::builder()
+        .with_component_decorator(suffix_decorator("!"))
+        .with_component_decorator(suffix_decorator("?"))
+        .build();
+
+    let greeter: &dyn Greeter = module.resolve_ref();
+    assert_eq!(greeter.greet(), "hello!?");
+}
+
+/// Decorators can resolve their dependencies from the build context
+#[test]
+fn decorator_dependencies() {
+    struct Prefixed {
+        inner: Arc<dyn Greeter>,
+        prefix: Arc<dyn Prefix>,
+    }
+    impl Greeter for Prefixed {
+        fn greet(&self) -> String {
+            format!("{} {}", self.prefix.prefix(), self.inner.greet())
+        }
+    }
+
+    let module = TestModule::builder()
+        .with_component_decorator::<dyn Greeter>(Box::new(|inner, context| {
+            let prefix = TestModule::build_component(context);
+            Box::new(Prefixed { inner, prefix })
+        }))
+        .build();
+
+    let greeter: &dyn Greeter = module.resolve_ref();
+    assert_eq!(greeter.greet(), "> hello");
+}
+
+/// Overridden components are decorated too
+#[test]
+fn overrides_are_decorated() {
+    struct FakeGreeter;
+    impl Greeter for FakeGreeter {
+        fn greet(&self) -> String {
+            "fake".to_string()
+        }
+    }
+
+    let module = TestModule::builder()
+        .with_component_override::<dyn Greeter>(Box::new(FakeGreeter))
+        .with_component_decorator(suffix_decorator("!"))
+        .build();
+    let greeter: &dyn Greeter = module.resolve_ref();
+    assert_eq!(greeter.greet(), "fake!");
+
+    let module = TestModule::builder()
+        .with_component_override_fn::<dyn Greeter>(Box::new(|_| Box::new(FakeGreeter)))
+        .with_component_decorator(suffix_decorator("!"))
+        .build();
+    let greeter: &dyn Greeter = module.resolve_ref();
+    assert_eq!(greeter.greet(), "fake!");
+}
+
+/// Each provided service is decorated
+#[test]
+fn provider_decorator() {
+    struct Logged {
+        inner: Box<dyn Request>,
+    }
+    impl Request for Logged {
+        fn path(&self) -> String {
+            format!("logged {}", self.inner.path())
+        }
+    }
+
+    let module = TestModule::builder()
+        .with_provider_decorator::<dyn Request>(Box::new(|inner, _| Box::new(Logged { inner })))
+        .build();
+
+    for _ in 0..2 {
+        let request: Box<dyn Request> = module.provide().unwrap();
+        assert_eq!(request.path(), "logged /");
+    }
+}
+
+/// Named components are decorated separately from the unnamed component
+#[test]
+fn named_component_decorator() {
+    let module = NamedModule::builder()
+        .with_named_component_decorator::<dyn Greeter, Loud>(suffix_decorator("!"))
+        .build();
+
+    let greeter: &dyn Greeter = module.resolve_ref();
+    let loud_greeter: &dyn Greeter =
+        HasNamedComponent::<dyn Greeter, Loud>::resolve_named_ref(&module);
+    assert_eq!(greeter.greet(), "hello");
+    assert_eq!(loud_greeter.greet(), "HELLO!");
+}
+
+/// Only the decorated component of a multi-binding is decorated
+#[test]
+fn multi_component_decorator() {
+    let module = MultiModule::builder()
+        .with_multi_component_decorator::<LoudGreeterImpl>(suffix_decorator("!"))
+        .build();
+
+    let greetings: Vec<String> = HasComponents::<dyn Greeter>::resolve_all(&module)
+        .iter()
+        .map(|greeter| greeter.greet())
+        .collect();
+    assert_eq!(greetings, vec!["hello", "HELLO!"]);
+}
+
+/// Only the decorated component of a map binding is decorated
+#[test]
+fn keyed_component_decorator() {
+    let module = MapModule::builder()
+        .with_keyed_component_decorator::<LoudGreeterImpl>(suffix_decorator("!"))
+        .build();
+
+    let quiet = HasComponentMap::<dyn Greeter>::resolve_keyed(&module, "quiet").unwrap();
+    let loud = HasComponentMap::<dyn Greeter>::resolve_keyed(&module, "loud").unwrap();
+    assert_eq!(quiet.greet(), "hello");
+    assert_eq!(loud.greet(), "HELLO!");
+}
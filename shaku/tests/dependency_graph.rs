@@ -0,0 +1,210 @@
+//! Test the dependency graph of modules
+
+use shaku::{module, Component, DependencyGraph, DependencyKind, GraphEdge, GraphNode, Interface};
+use shaku::{Module, ModuleBuildContext, ModuleInstance, Provider, ServiceKind};
+use std::any::type_name;
+use std::sync::Arc;
+
+trait Logger: Interface {}
+trait Plugin: Interface {}
+trait DateLogger: Interface {}
+trait Report {}
+trait ReportPrinter {}
+
+#[derive(Component)]
+#[shaku(interface = Logger)]
+struct LoggerImpl;
+impl Logger for LoggerImpl {}
+
+#[derive(Component)]
+#[shaku(interface = Plugin)]
+struct PluginImpl;
+impl Plugin for PluginImpl {}
+
+#[derive(Component)]
+#[shaku(interface = DateLogger)]
+struct DateLoggerImpl {
+    #[shaku(inject)]
+    #[allow(dead_code)]
+    logger: Arc<dyn Logger>,
+    #[shaku(inject)]
+    #[allow(dead_code)]
+    plugins: Vec<Arc<dyn Plugin>>,
+    #[shaku(default)]
+    #[allow(dead_code)]
+    today: String,
+}
+impl DateLogger for DateLoggerImpl {}
+
+#[derive(Provider)]
+#[shaku(interface = Report)]
+struct ReportImpl {
+    #[shaku(inject)]
+    #[allow(dead_code)]
+    date_logger: Arc<dyn DateLogger>,
+}
+impl Report for ReportImpl {}
+
+#[derive(Provider)]
+#[shaku(interface = ReportPrinter)]
+struct ReportPrinterImpl {
+    #[shaku(provide)]
+    #[allow(dead_code)]
+    report: Box<dyn Report>,
+}
+impl ReportPrinter for ReportPrinterImpl {}
+
+module! {
+    TestModule {
+        components = [#[lazy] LoggerImpl, DateLoggerImpl, #[multi(dyn Plugin)] PluginImpl],
+        providers = [ReportImpl, ReportPrinterImpl]
+    }
+}
+
+module! {
+    LoggerModule {
+        components = [LoggerImpl],
+        providers = []
+    }
+}
+
+// The logger is imported from the submodule
+module! {
+    ParentModule {
+        components = [DateLoggerImpl, #[multi(dyn Plugin)] PluginImpl],
+        providers = [ReportImpl],
+
+        use LoggerModule {
+            components = [dyn Logger],
+            providers = []
+        }
+    }
+}
+
+/// A manually implemented module, which has no graph
+struct ManualModule;
+impl Module for ManualModule {
+    type Submodules = ();
+
+    fn build(_: ModuleBuildContext<Self>) -> Self {
+        ManualModule
+    }
+}
+
+fn node<S: ?Sized, I: ?Sized, M>(kind: ServiceKind, lazy: bool) -> GraphNode {
+    GraphNode {
+        service: type_name::<S>(),
+        interface: type_name::<I>(),
+        named: None,
+        kind,
+        lazy,
+        overridden: false,
+        module: type_name::<M>(),
+    }
+}
+
+fn edge<S: ?Sized, I: ?Sized, M>(kind: DependencyKind) -> GraphEdge {
+    GraphEdge {
+        module: type_name::<M>(),
+        from: type_name::<S>(),
+        to: type_name::<I>(),
+        named: None,
+        kind,
+    }
+}
+
+#[test]
+fn graph_contains_services() {
+    let module = TestModule::builder().build();
+    let graph = module.dependency_graph();
+
+    assert_eq!(
+        graph.nodes(),
+        &[
+            node::<LoggerImpl, dyn Logger, TestModule>(ServiceKind::Component, true),
+            node::<DateLoggerImpl, dyn DateLogger, TestModule>(ServiceKind::Component, false),
+            node::<PluginImpl, dyn Plugin, TestModule>(ServiceKind::Component, false),
+            node::<ReportImpl, dyn Report, TestModule>(ServiceKind::Provider, false),
+            node::<ReportPrinterImpl, dyn ReportPrinter, TestModule>(ServiceKind::Provider, false),
+        ][..]
+    );
+}
+
+#[test]
+fn graph_contains_dependencies() {
+    let module = TestModule::builder().build();
+    let graph = module.dependency_graph();
+
+    assert_eq!(
+        graph.edges(),
+        &[
+            edge::<DateLoggerImpl, dyn Logger, TestModule>(DependencyKind::Inject),
+            edge::<DateLoggerImpl, dyn Plugin, TestModule>(DependencyKind::Inject),
+            edge::<ReportImpl, dyn DateLogger, TestModule>(DependencyKind::Inject),
+            edge::<ReportPrinterImpl, dyn Report, TestModule>(DependencyKind::Provide),
+        ][..]
+    );
+
+    let dependents: Vec<&str> = graph
+        .dependents_of(type_name::<dyn DateLogger>())
+        .map(|edge| edge.from)
+        .collect();
+    assert_eq!(dependents, vec![type_name::<ReportImpl>()]);
+}
+
+#[test]
+fn graph_includes_submodules() {
+    let logger_module = Arc::new(LoggerModule::builder().build());
+    let module = ParentModule::builder(logger_module).build();
+    let graph = module.dependency_graph();
+
+    let loggers: Vec<&GraphNode> = graph.implementations(type_name::<dyn Logger>()).collect();
+    assert_eq!(
+        loggers,
+        vec![&node::<LoggerImpl, dyn Logger, LoggerModule>(
+            ServiceKind::Component,
+            false
+        )]
+    );
+    assert_eq!(graph.nodes().len(), 4);
+
+    let dependencies: Vec<&str> = graph
+        .dependencies_of(type_name::<DateLoggerImpl>())
+        .map(|edge| edge.to)
+        .collect();
+    assert_eq!(
+        dependencies,
+        vec![type_name::<dyn Logger>(), type_name::<dyn Plugin>()]
+    );
+}
+
+#[test]
+fn submodule_graph_via_trait() {
+    let module = LoggerModule::builder().build();
+
+    assert_eq!(
+        ModuleInstance::module_graph(&module),
+        Some(module.dependency_graph())
+    );
+}
+
+#[test]
+fn overridden_services_are_marked() {
+    let module = TestModule::builder()
+        .with_component_override::<dyn Logger>(Box::new(LoggerImpl))
+        .build();
+    let graph = module.dependency_graph();
+
+    let overridden: Vec<&str> = graph
+        .nodes()
+        .iter()
+        .filter(|node| node.overridden)
+        .map(|node| node.service)
+        .collect();
+    assert_eq!(overridden, vec![type_name::<LoggerImpl>()]);
+}
+
+#[test]
+fn manual_module_has_empty_graph() {
+    assert_eq!(ManualModule.dependency_graph(), DependencyGraph::new());
+}
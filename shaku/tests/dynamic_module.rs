@@ -0,0 +1,227 @@
+//! Test registering services at runtime with `DynamicModule`
+
+use shaku::{
+    dynamic_interfaces, module, BuildError, Component, DynamicModule, HasComponent, HasProvider,
+    Interface, Provider, ResolveError,
+};
+use std::sync::Arc;
+
+trait Plugin: Interface {
+    fn name(&self) -> String;
+}
+trait Registry: Interface {
+    fn plugin_name(&self) -> String;
+}
+trait Request {
+    fn plugin_name(&self) -> String;
+}
+trait Unregistered: Interface {}
+trait Consumer: Interface {
+    fn plugin_name(&self) -> String;
+}
+
+// The interfaces which can be injected, or imported from the module
+dynamic_interfaces! {
+    components = [dyn Plugin, dyn Registry, dyn Unregistered],
+    providers = [dyn Request]
+}
+
+#[derive(Component)]
+#[shaku(interface = Plugin)]
+struct PluginImpl {
+    #[shaku(default = "default".to_string())]
+    name: String,
+}
+impl Plugin for PluginImpl {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Registry)]
+struct RegistryImpl {
+    #[shaku(inject)]
+    plugin: Arc<dyn Plugin>,
+}
+impl Registry for RegistryImpl {
+    fn plugin_name(&self) -> String {
+        self.plugin.name()
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = Request)]
+struct RequestImpl {
+    #[shaku(inject)]
+    plugin: Arc<dyn Plugin>,
+}
+impl Request for RequestImpl {
+    fn plugin_name(&self) -> String {
+        self.plugin.name()
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Unregistered)]
+struct UnregisteredImpl;
+impl Unregistered for UnregisteredImpl {}
+
+#[derive(Component)]
+#[shaku(interface = Registry)]
+struct BrokenRegistry {
+    #[shaku(inject)]
+    #[allow(dead_code)]
+    unregistered: Arc<dyn Unregistered>,
+}
+impl Registry for BrokenRegistry {
+    fn plugin_name(&self) -> String {
+        String::new()
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Consumer)]
+struct ConsumerImpl {
+    #[shaku(inject)]
+    registry: Arc<dyn Registry>,
+}
+impl Consumer for ConsumerImpl {
+    fn plugin_name(&self) -> String {
+        self.registry.plugin_name()
+    }
+}
+
+module! {
+    ConsumerModule {
+        components = [ConsumerImpl],
+        providers = [],
+
+        use DynamicModule {
+            components = [dyn Registry],
+            providers = []
+        }
+    }
+}
+
+module! {
+    AppModule {
+        components = [],
+        providers = [],
+
+        use DynamicModule {
+            components = [dyn Registry],
+            providers = [dyn Request]
+        }
+    }
+}
+
+fn plugin_module() -> DynamicModule {
+    // The dependencies of a component can be registered after it
+    DynamicModule::builder()
+        .with_component::<RegistryImpl>()
+        .with_component::<PluginImpl>()
+        .with_provider::<RequestImpl>()
+        .with_component_parameters::<PluginImpl>(PluginImplParameters {
+            name: "plugin".to_string(),
+        })
+        .build()
+}
+
+#[test]
+fn resolve_components() {
+    let module = plugin_module();
+
+    let registry = module.resolve::<dyn Registry>().unwrap();
+    let plugin: &dyn Plugin = module.resolve_ref();
+    assert_eq!(registry.plugin_name(), "plugin");
+    assert_eq!(plugin.name(), "plugin");
+    assert!(module.contains::<dyn Plugin>());
+    assert!(!module.contains::<dyn Unregistered>());
+}
+
+#[test]
+fn resolve_unregistered() {
+    let module = plugin_module();
+
+    assert_eq!(
+        module.resolve::<dyn Unregistered>().err(),
+        Some(ResolveError {
+            interface: "dyn dynamic_module::Unregistered",
+        })
+    );
+}
+
+#[test]
+fn provide() {
+    let module = plugin_module();
+
+    let request: Box<dyn Request> = module.provide().unwrap();
+    assert_eq!(request.plugin_name(), "plugin");
+    assert!(module.provide::<dyn Plugin>().is_err());
+}
+
+/// Registering an interface again replaces the previous registration
+#[test]
+fn replace_registration() {
+    let module = DynamicModule::builder()
+        .with_component::<BrokenRegistry>()
+        .with_component::<RegistryImpl>()
+        .with_component::<PluginImpl>()
+        .build();
+
+    let registry: &dyn Registry = module.resolve_ref();
+    assert_eq!(registry.plugin_name(), "default");
+}
+
+#[test]
+fn unregistered_dependency() {
+    let result = DynamicModule::builder()
+        .with_component::<BrokenRegistry>()
+        .try_build();
+
+    match result {
+        Err(BuildError::UnregisteredComponent { interface }) => {
+            assert_eq!(interface, "dyn dynamic_module::Unregistered")
+        }
+        Err(e) => panic!("Unexpected error: {}", e),
+        Ok(_) => panic!("Expected the build to fail"),
+    }
+}
+
+/// A dynamic module can be the submodule of a static module
+#[test]
+fn submodule() {
+    let module = AppModule::builder(Arc::new(plugin_module())).build();
+
+    let registry: &dyn Registry = module.resolve_ref();
+    let request: Box<dyn Request> = module.provide().unwrap();
+    assert_eq!(registry.plugin_name(), "plugin");
+    assert_eq!(request.plugin_name(), "plugin");
+}
+
+/// A component imported from a dynamic module which is not registered fails
+/// the build of the module which depends on it
+#[test]
+fn submodule_unregistered_dependency() {
+    let plugins = DynamicModule::builder()
+        .with_component::<PluginImpl>()
+        .build();
+    let result = ConsumerModule::builder(Arc::new(plugins)).try_build();
+
+    match result {
+        Err(BuildError::UnregisteredComponent { interface }) => {
+            assert_eq!(interface, "dyn dynamic_module::Registry")
+        }
+        Err(e) => panic!("Unexpected error: {}", e),
+        Ok(_) => panic!("Expected the build to fail"),
+    }
+}
+
+#[test]
+fn submodule_dependency() {
+    let module = ConsumerModule::builder(Arc::new(plugin_module())).build();
+
+    let consumer: &dyn Consumer = module.resolve_ref();
+    assert_eq!(consumer.plugin_name(), "plugin");
+}
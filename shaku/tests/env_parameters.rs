@@ -0,0 +1,91 @@
+//! Test parameters which are read from environment variables
+
+use shaku::{module, BuildError, Component, HasComponent, Interface};
+use std::env;
+
+trait Database: Interface {
+    fn url(&self) -> &str;
+    fn pool_size(&self) -> usize;
+}
+
+#[derive(Component)]
+#[shaku(interface = Database)]
+struct DatabaseImpl {
+    #[shaku(env = "SHAKU_TEST_DATABASE_URL")]
+    url: String,
+    #[shaku(env = "SHAKU_TEST_POOL_SIZE", default = 4)]
+    pool_size: usize,
+}
+impl Database for DatabaseImpl {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn pool_size(&self) -> usize {
+        self.pool_size
+    }
+}
+
+module! {
+    TestModule {
+        components = [DatabaseImpl],
+        providers = []
+    }
+}
+
+// The tests share the environment, so they run sequentially in one test
+#[test]
+fn env_parameters() {
+    // The variable without a default is required
+    env::remove_var("SHAKU_TEST_DATABASE_URL");
+    env::remove_var("SHAKU_TEST_POOL_SIZE");
+    match TestModule::builder().try_build() {
+        Err(BuildError::MissingEnvVar {
+            component,
+            parameter,
+            variable,
+        }) => {
+            assert_eq!(component, "env_parameters::DatabaseImpl");
+            assert_eq!(parameter, "url");
+            assert_eq!(variable, "SHAKU_TEST_DATABASE_URL");
+        }
+        Err(e) => panic!("Unexpected error: {}", e),
+        Ok(_) => panic!("Expected the build to fail"),
+    }
+
+    // The variable with a default falls back to it
+    env::set_var("SHAKU_TEST_DATABASE_URL", "db://env");
+    let module = TestModule::builder().build();
+    let database: &dyn Database = module.resolve_ref();
+    assert_eq!(database.url(), "db://env");
+    assert_eq!(database.pool_size(), 4);
+
+    // Variables are parsed with FromStr
+    env::set_var("SHAKU_TEST_POOL_SIZE", "16");
+    let module = TestModule::builder().build();
+    let database: &dyn Database = module.resolve_ref();
+    assert_eq!(database.pool_size(), 16);
+
+    // Explicit parameters take precedence
+    let module = TestModule::builder()
+        .with_component_parameters::<DatabaseImpl>(DatabaseImplParameters {
+            url: "db://explicit".to_string(),
+            pool_size: 1,
+        })
+        .build();
+    let database: &dyn Database = module.resolve_ref();
+    assert_eq!(database.url(), "db://explicit");
+    assert_eq!(database.pool_size(), 1);
+
+    // Invalid values fail the build
+    env::set_var("SHAKU_TEST_POOL_SIZE", "many");
+    match TestModule::builder().try_build() {
+        Err(error @ BuildError::InvalidEnvVar { .. }) => assert_eq!(
+            error.to_string(),
+            "Invalid environment variable SHAKU_TEST_POOL_SIZE for \
+             `env_parameters::DatabaseImpl::pool_size`: invalid digit found in string"
+        ),
+        Err(e) => panic!("Unexpected error: {}", e),
+        Ok(_) => panic!("Expected the build to fail"),
+    }
+}
@@ -0,0 +1,162 @@
+//! Test rendering dependency graphs as DOT and Mermaid
+
+use shaku::graph::{to_dot, to_mermaid};
+use shaku::{module, Component, Dependency, DependencyGraph, GraphNode, Interface, Module};
+use shaku::{ServiceKind, ServiceOverride};
+use std::sync::Arc;
+
+trait Logger: Interface {}
+trait DateLogger: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = Logger)]
+struct LoggerImpl;
+impl Logger for LoggerImpl {}
+
+#[derive(Component)]
+#[shaku(interface = DateLogger)]
+struct DateLoggerImpl {
+    #[shaku(inject)]
+    #[allow(dead_code)]
+    logger: Arc<dyn Logger>,
+}
+impl DateLogger for DateLoggerImpl {}
+
+module! {
+    LoggerModule {
+        components = [LoggerImpl],
+        providers = []
+    }
+}
+
+module! {
+    DateLoggerModule {
+        components = [DateLoggerImpl],
+        providers = [],
+
+        use LoggerModule {
+            components = [dyn Logger],
+            providers = []
+        }
+    }
+}
+
+fn node(service: &'static str, interface: &'static str, kind: ServiceKind) -> GraphNode {
+    GraphNode {
+        service,
+        interface,
+        named: None,
+        kind,
+        lazy: false,
+        overridden: false,
+        module: "App",
+    }
+}
+
+fn dependency(interface: &'static str, provide: bool) -> Dependency {
+    if provide {
+        Dependency {
+            interface,
+            ..Dependency::provide::<()>()
+        }
+    } else {
+        Dependency {
+            interface,
+            ..Dependency::inject::<()>()
+        }
+    }
+}
+
+/// An app module with a database submodule
+fn test_graph() -> DependencyGraph {
+    let mut graph = DependencyGraph::new();
+    graph.add_service(
+        GraphNode {
+            module: "Db",
+            lazy: true,
+            ..node("Pool", "dyn Database", ServiceKind::Component)
+        },
+        Vec::new(),
+    );
+    graph.add_service(
+        node("Repo", "dyn Repository", ServiceKind::Provider),
+        vec![dependency("dyn Database", false)],
+    );
+    graph.add_service(
+        node("Handler", "dyn Handler", ServiceKind::Provider),
+        vec![
+            dependency("dyn Repository", true),
+            dependency("dyn Clock", false),
+        ],
+    );
+    graph.add_service(
+        node("Cache", "dyn Cache<\"x\">", ServiceKind::Component),
+        Vec::new(),
+    );
+    graph.mark_overrides(
+        "App",
+        &[ServiceOverride {
+            interface: "dyn Handler",
+            ..ServiceOverride::provider::<()>()
+        }],
+    );
+    graph
+}
+
+#[test]
+fn render_dot() {
+    assert_eq!(
+        to_dot(&test_graph()),
+        r#"digraph {
+    subgraph cluster_0 {
+        label="Db";
+        n0 [label="Pool\ndyn Database", shape=box, style="rounded,dashed"];
+    }
+    subgraph cluster_1 {
+        label="App";
+        n1 [label="Repo\ndyn Repository", shape=hexagon];
+        n2 [label="Handler\ndyn Handler", shape=hexagon, fillcolor=orange, style="filled"];
+        n3 [label="Cache\ndyn Cache<\"x\">", shape=box];
+    }
+    i0 [label="dyn Clock", shape=plaintext];
+    n1 -> n0;
+    n2 -> n1 [style=dashed];
+    n2 -> i0;
+}
+"#
+    );
+}
+
+#[test]
+fn render_mermaid() {
+    assert_eq!(
+        to_mermaid(&test_graph()),
+        r#"flowchart LR
+    subgraph m0 ["Db"]
+        n0(["Pool<br/>dyn Database"])
+    end
+    subgraph m1 ["App"]
+        n1{{"Repo<br/>dyn Repository"}}
+        n2{{"Handler<br/>dyn Handler"}}
+        n3["Cache<br/>dyn Cache#lt;#quot;x#quot;#gt;"]
+    end
+    i0>"dyn Clock"]
+    n1 --> n0
+    n2 -.-> n1
+    n2 --> i0
+    classDef overridden fill:orange
+    class n2 overridden
+"#
+    );
+}
+
+#[test]
+fn render_module_graph() {
+    let logger_module = Arc::new(LoggerModule::builder().build());
+    let module = DateLoggerModule::builder(logger_module).build();
+    let dot = to_dot(&module.dependency_graph());
+
+    assert!(dot.contains("label=\"graph_rendering::LoggerModule\";"));
+    assert!(dot.contains("label=\"graph_rendering::DateLoggerModule\";"));
+    assert!(dot.contains("n1 -> n0;"));
+}
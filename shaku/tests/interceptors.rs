@@ -0,0 +1,122 @@
+//! Test intercepting method calls with `#[shaku::interface]` proxies
+
+use shaku::{module, Component, HasComponent, Interceptor, Interface, MethodCall};
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[shaku::interface]
+trait Store: Interface {
+    fn get(&self, key: &str) -> Option<u32>;
+    fn set(&self, key: &str, value: u32) -> Result<(), String>;
+    fn clear(&self);
+
+    fn get_or_zero(&self, key: &str) -> u32
+    where
+        Self: Sized,
+    {
+        self.get(key).unwrap_or(0)
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Store)]
+struct StoreImpl;
+impl Store for StoreImpl {
+    fn get(&self, key: &str) -> Option<u32> {
+        if key == "answer" {
+            Some(42)
+        } else {
+            None
+        }
+    }
+
+    fn set(&self, key: &str, _value: u32) -> Result<(), String> {
+        if key.is_empty() {
+            Err("empty key".to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn clear(&self) {}
+}
+
+module! {
+    TestModule {
+        components = [StoreImpl],
+        providers = []
+    }
+}
+
+/// Records every callback as a string
+#[derive(Clone, Default)]
+struct Recorder {
+    calls: Arc<Mutex<Vec<String>>>,
+}
+
+impl Interceptor for Recorder {
+    fn before(&self, call: &MethodCall, arguments: &[&dyn Debug]) {
+        self.calls.lock().unwrap().push(format!(
+            "before {}::{}{:?}",
+            call.interface, call.method, arguments
+        ));
+    }
+
+    fn after(&self, call: &MethodCall, _elapsed: Duration) {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("after {}", call.method));
+    }
+
+    fn error(&self, call: &MethodCall, error: &dyn Debug, _elapsed: Duration) {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("error {}: {:?}", call.method, error));
+    }
+}
+
+#[test]
+fn calls_are_intercepted() {
+    let recorder = Recorder::default();
+    let interceptor = recorder.clone();
+    let module = TestModule::builder()
+        .with_component_decorator::<dyn Store>(Box::new(move |inner, _| {
+            Box::new(StoreProxy::new(inner, interceptor.clone()))
+        }))
+        .build();
+
+    let store: &dyn Store = module.resolve_ref();
+    assert_eq!(store.get("answer"), Some(42));
+    assert_eq!(store.set("answer", 1), Ok(()));
+    assert_eq!(store.set("", 1), Err("empty key".to_string()));
+    store.clear();
+
+    assert_eq!(
+        *recorder.calls.lock().unwrap(),
+        vec![
+            "before Store::get[\"answer\"]",
+            "after get",
+            "before Store::set[\"answer\", 1]",
+            "after set",
+            "before Store::set[\"\", 1]",
+            "error set: \"empty key\"",
+            "before Store::clear[]",
+            "after clear",
+        ]
+    );
+}
+
+/// The default callbacks do nothing
+#[test]
+fn default_interceptor() {
+    struct Noop;
+    impl Interceptor for Noop {}
+
+    let proxy = StoreProxy::new(Arc::new(StoreImpl), Noop);
+    assert_eq!(proxy.get("answer"), Some(42));
+    assert_eq!(proxy.get_or_zero("missing"), 0);
+    assert_eq!(proxy.inner().get("answer"), Some(42));
+}
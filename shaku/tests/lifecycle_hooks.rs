@@ -0,0 +1,320 @@
+//! Test `#[shaku(on_start, on_stop)]` and `Module::shutdown`
+
+use shaku::{module, Component, HasComponent, Interface, Module, Provider};
+use std::sync::{Arc, Mutex};
+
+trait Log: Interface {
+    fn push(&self, event: &'static str);
+    fn events(&self) -> Vec<&'static str>;
+}
+trait Database: Interface {}
+trait Repository: Interface {}
+trait Cache: Interface {}
+trait Connection {}
+
+#[derive(Component)]
+#[shaku(interface = Log)]
+struct LogImpl {
+    #[shaku(default)]
+    events: Mutex<Vec<&'static str>>,
+}
+impl Log for LogImpl {
+    fn push(&self, event: &'static str) {
+        self.events.lock().unwrap().push(event);
+    }
+
+    fn events(&self) -> Vec<&'static str> {
+        self.events.lock().unwrap().clone()
+    }
+}
+
+#[derive(Component)]
+#[shaku(
+    interface = Database,
+    on_start = DatabaseImpl::start,
+    on_stop = DatabaseImpl::stop
+)]
+struct DatabaseImpl {
+    #[shaku(inject)]
+    log: Arc<dyn Log>,
+}
+impl Database for DatabaseImpl {}
+impl DatabaseImpl {
+    fn start(&self) {
+        self.log.push("start database");
+    }
+
+    fn stop(&self) {
+        self.log.push("stop database");
+    }
+}
+
+#[derive(Component)]
+#[shaku(
+    interface = Repository,
+    on_start = RepositoryImpl::start,
+    on_stop = RepositoryImpl::stop
+)]
+struct RepositoryImpl {
+    #[shaku(inject)]
+    log: Arc<dyn Log>,
+    #[shaku(inject)]
+    #[allow(dead_code)]
+    database: Arc<dyn Database>,
+}
+impl Repository for RepositoryImpl {}
+impl RepositoryImpl {
+    fn start(&self) {
+        self.log.push("start repository");
+    }
+
+    fn stop(&self) {
+        self.log.push("stop repository");
+    }
+}
+
+/// A component with only a stop hook, which is checked before being built
+#[derive(Component)]
+#[shaku(
+    interface = Cache,
+    try_build = CacheImpl::check,
+    on_stop = CacheImpl::stop
+)]
+struct CacheImpl {
+    #[shaku(inject)]
+    log: Arc<dyn Log>,
+    capacity: usize,
+}
+impl Cache for CacheImpl {}
+impl CacheImpl {
+    fn check(self) -> Result<Self, String> {
+        if self.capacity == 0 {
+            return Err("The capacity must not be zero".to_string());
+        }
+
+        Ok(self)
+    }
+
+    fn stop(&self) {
+        self.log.push("stop cache");
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = Connection)]
+struct ConnectionImpl;
+impl Connection for ConnectionImpl {}
+
+module! {
+    TestModule {
+        // The repository is listed first, but its dependencies are built first
+        components = [RepositoryImpl, DatabaseImpl, LogImpl],
+        providers = []
+    }
+}
+
+module! {
+    LazyModule {
+        components = [#[lazy] RepositoryImpl, DatabaseImpl, LogImpl],
+        providers = []
+    }
+}
+
+module! {
+    BaseModule {
+        components = [DatabaseImpl, LogImpl],
+        providers = []
+    }
+}
+
+module! {
+    ParentModule {
+        components = [RepositoryImpl, CacheImpl],
+        providers = [],
+
+        use BaseModule {
+            components = [dyn Database, dyn Log],
+            providers = []
+        }
+    }
+}
+
+module! {
+    ConnectionModule {
+        components = [DatabaseImpl, LogImpl],
+        providers = [ConnectionImpl]
+    }
+}
+
+// Only providers are imported from the submodule
+module! {
+    ConnectionParentModule {
+        components = [],
+        providers = [],
+
+        use ConnectionModule {
+            components = [],
+            providers = [dyn Connection]
+        }
+    }
+}
+
+#[test]
+fn hooks_run_in_dependency_order() {
+    let module = TestModule::builder().build();
+    let log: &dyn Log = module.resolve_ref();
+    assert_eq!(log.events(), vec!["start database", "start repository"]);
+
+    module.shutdown();
+    assert_eq!(
+        log.events(),
+        vec![
+            "start database",
+            "start repository",
+            "stop repository",
+            "stop database"
+        ]
+    );
+}
+
+#[test]
+fn shutdown_is_only_run_once() {
+    let module = TestModule::builder().build();
+    module.shutdown();
+    module.shutdown();
+
+    let log: &dyn Log = module.resolve_ref();
+    assert_eq!(log.events().len(), 4);
+}
+
+#[test]
+fn lazy_component_hooks() {
+    let module = LazyModule::builder().build();
+    let log: Arc<dyn Log> = module.resolve();
+    assert_eq!(log.events(), vec!["start database"]);
+
+    let _repository: Arc<dyn Repository> = module.resolve();
+    module.shutdown();
+    assert_eq!(
+        log.events(),
+        vec![
+            "start database",
+            "start repository",
+            "stop repository",
+            "stop database"
+        ]
+    );
+}
+
+#[test]
+fn unresolved_lazy_component_is_not_stopped() {
+    let module = LazyModule::builder().build();
+    module.shutdown();
+
+    let log: &dyn Log = module.resolve_ref();
+    assert_eq!(log.events(), vec!["start database", "stop database"]);
+}
+
+#[test]
+fn submodule_components_are_stopped_after_parent_components() {
+    let base_module = Arc::new(BaseModule::builder().build());
+    let module = ParentModule::builder(base_module)
+        .with_component_parameters::<CacheImpl>(CacheImplParameters { capacity: 10 })
+        .build();
+    module.shutdown();
+
+    let log: &dyn Log = module.resolve_ref();
+    assert_eq!(
+        log.events(),
+        vec![
+            "start database",
+            "start repository",
+            "stop cache",
+            "stop repository",
+            "stop database"
+        ]
+    );
+}
+
+#[test]
+fn provider_only_submodule_is_stopped() {
+    let connection_module = Arc::new(ConnectionModule::builder().build());
+    let module = ConnectionParentModule::builder(Arc::clone(&connection_module)).build();
+    module.shutdown();
+
+    let log: &dyn Log = connection_module.resolve_ref();
+    assert_eq!(log.events(), vec!["start database", "stop database"]);
+}
+
+#[test]
+fn shared_submodule_is_stopped_once() {
+    let base_module = Arc::new(BaseModule::builder().build());
+    let parameters = || CacheImplParameters { capacity: 10 };
+    let module1 = ParentModule::builder(Arc::clone(&base_module))
+        .with_component_parameters::<CacheImpl>(parameters())
+        .build();
+    let module2 = ParentModule::builder(Arc::clone(&base_module))
+        .with_component_parameters::<CacheImpl>(parameters())
+        .build();
+
+    module1.shutdown();
+    module2.shutdown();
+    base_module.shutdown();
+
+    let log: &dyn Log = base_module.resolve_ref();
+    let events = log.events();
+    let database_stops = events.iter().filter(|e| **e == "stop database").count();
+    assert_eq!(database_stops, 1);
+    assert_eq!(events.iter().filter(|e| **e == "stop cache").count(), 2);
+}
+
+/// The components which were started before the build failed are stopped,
+/// but the submodules are not shut down
+#[test]
+fn failed_try_build_stops_started_components() {
+    let base_module = Arc::new(BaseModule::builder().build());
+    let result = ParentModule::builder(Arc::clone(&base_module)).try_build();
+    assert!(result.is_err());
+
+    let log: &dyn Log = base_module.resolve_ref();
+    assert_eq!(
+        log.events(),
+        vec!["start database", "start repository", "stop repository"]
+    );
+}
+
+/// Components are stopped if the build fails after they are built
+#[test]
+fn failed_strict_build_stops_components() {
+    let log = Arc::new(LogImpl {
+        events: Mutex::new(Vec::new()),
+    });
+    let result = TestModule::builder()
+        .with_component_override::<dyn Log>(Box::new(SharedLog(Arc::clone(&log))))
+        .with_component_parameters::<LogImpl>(LogImplParameters::default())
+        .strict()
+        .try_build();
+    assert!(result.is_err());
+
+    assert_eq!(
+        log.events(),
+        vec![
+            "start database",
+            "start repository",
+            "stop repository",
+            "stop database"
+        ]
+    );
+}
+
+/// Forwards to a log which outlives the module
+struct SharedLog(Arc<LogImpl>);
+impl Log for SharedLog {
+    fn push(&self, event: &'static str) {
+        self.0.push(event);
+    }
+
+    fn events(&self) -> Vec<&'static str> {
+        self.0.events()
+    }
+}
@@ -0,0 +1,214 @@
+//! Test keyed components, which are injected as a map
+
+use shaku::{module, BuildError, Component, HasComponent, HasComponentMap, HasComponents};
+use shaku::{HasProvider, Interface, Provider};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+trait Command: Interface {
+    fn run(&self) -> String;
+}
+trait Cli: Interface {
+    fn run(&self, name: &str) -> Option<String>;
+}
+trait CommandNames {
+    fn names(&self) -> Vec<&'static str>;
+}
+
+#[derive(Component)]
+#[shaku(interface = Command)]
+struct ExportCommand {
+    #[shaku(default = "csv".to_string())]
+    format: String,
+}
+impl Command for ExportCommand {
+    fn run(&self) -> String {
+        format!("export {}", self.format)
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Command)]
+struct ImportCommand;
+impl Command for ImportCommand {
+    fn run(&self) -> String {
+        "import".to_string()
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Command)]
+struct HelpCommand;
+impl Command for HelpCommand {
+    fn run(&self) -> String {
+        "help".to_string()
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Cli)]
+struct CliImpl {
+    #[shaku(inject)]
+    commands: HashMap<&'static str, Arc<dyn Command>>,
+}
+impl Cli for CliImpl {
+    fn run(&self, name: &str) -> Option<String> {
+        self.commands.get(name).map(|command| command.run())
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = CommandNames)]
+struct CommandNamesImpl {
+    #[shaku(inject)]
+    commands: HashMap<&'static str, Arc<dyn Command>>,
+}
+impl CommandNames for CommandNamesImpl {
+    fn names(&self) -> Vec<&'static str> {
+        let mut names: Vec<&'static str> = self.commands.keys().cloned().collect();
+        names.sort();
+        names
+    }
+}
+
+module! {
+    TestModule {
+        components = [
+            CliImpl,
+            #[multi(dyn Command)] #[key("export")] ExportCommand,
+            #[multi(dyn Command)] #[key("import")] ImportCommand
+        ],
+        providers = [CommandNamesImpl]
+    }
+}
+
+module! {
+    HelpModule {
+        components = [#[multi(dyn Command)] #[key("help")] HelpCommand],
+        providers = []
+    }
+}
+
+// The map is combined with the maps of the submodules
+module! {
+    ParentModule {
+        components = [CliImpl],
+        providers = [],
+
+        use TestModule {
+            components = [#[key] dyn Command],
+            providers = []
+        },
+
+        use HelpModule {
+            components = [#[key] dyn Command],
+            providers = []
+        }
+    }
+}
+
+// The "export" key is also used by TestModule
+module! {
+    DuplicateKeyModule {
+        components = [#[multi(dyn Command)] #[key("export")] HelpCommand],
+        providers = [],
+
+        use TestModule {
+            components = [#[key] dyn Command],
+            providers = []
+        }
+    }
+}
+
+// Keyed components are separate from the set of the same interface
+module! {
+    MixedModule {
+        components = [
+            #[multi(dyn Command)] #[key("help")] HelpCommand,
+            #[multi(dyn Command)] ImportCommand
+        ],
+        providers = []
+    }
+}
+
+#[test]
+fn inject_component_map() {
+    let module = TestModule::builder().build();
+
+    let cli: &dyn Cli = module.resolve_ref();
+    assert_eq!(cli.run("export"), Some("export csv".to_string()));
+    assert_eq!(cli.run("import"), Some("import".to_string()));
+    assert_eq!(cli.run("help"), None);
+
+    let command_names: Box<dyn CommandNames> = module.provide().unwrap();
+    assert_eq!(command_names.names(), vec!["export", "import"]);
+}
+
+#[test]
+fn resolve_keyed_component() {
+    let module = TestModule::builder().build();
+
+    let export: Arc<dyn Command> = module.resolve_keyed("export").unwrap();
+    let commands: HashMap<&'static str, Arc<dyn Command>> = module.resolve_map();
+    assert_eq!(export.run(), "export csv");
+    assert!(Arc::ptr_eq(&export, &commands["export"]));
+    assert!(HasComponentMap::<dyn Command>::resolve_keyed(&module, "help").is_none());
+}
+
+#[test]
+fn keyed_component_parameters() {
+    let module = TestModule::builder()
+        .with_keyed_component_parameters::<ExportCommand>(ExportCommandParameters {
+            format: "json".to_string(),
+        })
+        .build();
+
+    let cli: &dyn Cli = module.resolve_ref();
+    assert_eq!(cli.run("export"), Some("export json".to_string()));
+}
+
+#[test]
+fn component_map_from_submodules() {
+    let test_module = Arc::new(TestModule::builder().build());
+    let help_module = Arc::new(HelpModule::builder().build());
+    let module = ParentModule::builder(test_module, help_module).build();
+
+    let cli: &dyn Cli = module.resolve_ref();
+    assert_eq!(cli.run("export"), Some("export csv".to_string()));
+    assert_eq!(cli.run("help"), Some("help".to_string()));
+
+    let help: Arc<dyn Command> = module.resolve_keyed("help").unwrap();
+    assert_eq!(help.run(), "help");
+}
+
+#[test]
+fn duplicate_key_from_submodule() {
+    let test_module = Arc::new(TestModule::builder().build());
+    let result = DuplicateKeyModule::builder(test_module).try_build();
+
+    match result {
+        Err(BuildError::DuplicateKey { key, .. }) => assert_eq!(key, "export"),
+        _ => panic!("Expected a duplicate key error"),
+    }
+}
+
+#[test]
+#[should_panic(
+    expected = "Duplicate key \"export\" in the component map of dyn map_bindings::Command"
+)]
+fn duplicate_key_from_submodule_panics() {
+    let test_module = Arc::new(TestModule::builder().build());
+    DuplicateKeyModule::builder(test_module).build();
+}
+
+#[test]
+fn keyed_and_multi_bound_components() {
+    let module = MixedModule::builder().build();
+
+    let commands: Vec<Arc<dyn Command>> = module.resolve_all();
+    let command_map: HashMap<&'static str, Arc<dyn Command>> = module.resolve_map();
+    assert_eq!(commands.len(), 1);
+    assert_eq!(commands[0].run(), "import");
+    assert_eq!(command_map.len(), 1);
+    assert_eq!(command_map["help"].run(), "help");
+}
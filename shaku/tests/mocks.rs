@@ -0,0 +1,139 @@
+//! Test overriding services with mocks generated by `#[shaku::interface(mock)]`
+#![cfg(feature = "mock")]
+
+use shaku::{module, Component, HasComponent, HasProvider, Interface, MockCall, Provider};
+use std::sync::Arc;
+
+#[shaku::interface(mock)]
+trait Repository: Interface {
+    fn find(&self, id: u32) -> Option<String>;
+    fn save(&self, id: u32, name: &str) -> Result<(), String>;
+    fn prefix<'a>(&self, name: &'a str) -> &'a str;
+}
+
+#[shaku::interface(mock)]
+trait Clock {
+    fn now(&self) -> u64;
+}
+
+#[shaku::interface]
+trait Service: Interface {
+    fn describe(&self, id: u32) -> String;
+}
+
+#[derive(Component)]
+#[shaku(interface = Repository)]
+struct RepositoryImpl;
+impl Repository for RepositoryImpl {
+    fn find(&self, _id: u32) -> Option<String> {
+        None
+    }
+
+    fn save(&self, _id: u32, _name: &str) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn prefix<'a>(&self, name: &'a str) -> &'a str {
+        name
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = Clock)]
+struct ClockImpl;
+impl Clock for ClockImpl {
+    fn now(&self) -> u64 {
+        0
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Service)]
+struct ServiceImpl {
+    #[shaku(inject)]
+    repository: Arc<dyn Repository>,
+}
+impl Service for ServiceImpl {
+    fn describe(&self, id: u32) -> String {
+        self.repository
+            .find(id)
+            .unwrap_or_else(|| "unknown".to_string())
+    }
+}
+
+module! {
+    TestModule {
+        components = [RepositoryImpl, ServiceImpl],
+        providers = [ClockImpl]
+    }
+}
+
+#[test]
+fn component_mock() {
+    let mock = MockRepository::new()
+        .expect_find(|id| {
+            if id == 1 {
+                Some("one".to_string())
+            } else {
+                None
+            }
+        })
+        .expect_save(|_, name| Err(format!("{} is read-only", name)));
+    let module = TestModule::builder()
+        .with_component_override::<dyn Repository>(mock.boxed())
+        .build();
+
+    let service: &dyn Service = module.resolve_ref();
+    let repository: &dyn Repository = module.resolve_ref();
+    assert_eq!(service.describe(1), "one");
+    assert_eq!(service.describe(2), "unknown");
+    assert_eq!(
+        repository.save(1, "one"),
+        Err("one is read-only".to_string())
+    );
+
+    assert_eq!(
+        mock.calls(),
+        vec![
+            MockCall {
+                method: "find",
+                arguments: vec!["1".to_string()],
+            },
+            MockCall {
+                method: "find",
+                arguments: vec!["2".to_string()],
+            },
+            MockCall {
+                method: "save",
+                arguments: vec!["1".to_string(), "\"one\"".to_string()],
+            },
+        ]
+    );
+    assert_eq!(mock.calls_to("save").len(), 1);
+}
+
+#[test]
+fn provider_mock() {
+    let mock = MockClock::new().expect_now(|| 42);
+    let module = TestModule::builder()
+        .with_provider_override::<dyn Clock>(mock.provider())
+        .build();
+
+    let clock: Box<dyn Clock> = module.provide().unwrap();
+    assert_eq!(clock.now(), 42);
+    assert_eq!(mock.calls_to("now").len(), 1);
+}
+
+/// Expectations can return the lifetimes of their arguments
+#[test]
+fn named_lifetimes() {
+    let mock = MockRepository::new().expect_prefix(|name| &name[..1]);
+
+    assert_eq!(mock.prefix("shaku"), "s");
+}
+
+#[test]
+#[should_panic(expected = "No expectation was set for `Repository::find`")]
+fn missing_expectation() {
+    MockRepository::new().find(1);
+}
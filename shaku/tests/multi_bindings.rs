@@ -0,0 +1,173 @@
+//! Test multi-bound components, which are injected as a collection
+
+use shaku::{module, Component, HasComponent, HasComponents, HasProvider, Interface, Provider};
+use std::sync::Arc;
+
+trait Listener: Interface {
+    fn name(&self) -> String;
+}
+trait EventBus: Interface {
+    fn listener_names(&self) -> Vec<String>;
+}
+trait ListenerCount {
+    fn count(&self) -> usize;
+}
+
+#[derive(Component)]
+#[shaku(interface = Listener)]
+struct AuditListener;
+impl Listener for AuditListener {
+    fn name(&self) -> String {
+        "audit".to_string()
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Listener)]
+struct MetricsListener {
+    #[shaku(default = "metrics".to_string())]
+    name: String,
+}
+impl Listener for MetricsListener {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Listener)]
+struct EmailListener;
+impl Listener for EmailListener {
+    fn name(&self) -> String {
+        "email".to_string()
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = EventBus)]
+struct EventBusImpl {
+    #[shaku(inject)]
+    listeners: Vec<Arc<dyn Listener>>,
+}
+impl EventBus for EventBusImpl {
+    fn listener_names(&self) -> Vec<String> {
+        self.listeners
+            .iter()
+            .map(|listener| listener.name())
+            .collect()
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = ListenerCount)]
+struct ListenerCountImpl {
+    #[shaku(inject)]
+    listeners: Vec<Arc<dyn Listener>>,
+}
+impl ListenerCount for ListenerCountImpl {
+    fn count(&self) -> usize {
+        self.listeners.len()
+    }
+}
+
+module! {
+    TestModule {
+        components = [
+            EventBusImpl,
+            #[multi(dyn Listener)] AuditListener,
+            #[multi(dyn Listener)] MetricsListener
+        ],
+        providers = [ListenerCountImpl]
+    }
+}
+
+module! {
+    EmailModule {
+        components = [#[multi(dyn Listener)] EmailListener],
+        providers = []
+    }
+}
+
+// The set is combined with the sets of the submodules
+module! {
+    ParentModule {
+        components = [EventBusImpl, #[multi(dyn Listener)] AuditListener],
+        providers = [],
+
+        use TestModule {
+            components = [#[multi] dyn Listener],
+            providers = []
+        },
+
+        use EmailModule {
+            components = [#[multi] dyn Listener],
+            providers = []
+        }
+    }
+}
+
+// A multi-bound component can also be the single component of its interface
+module! {
+    MixedModule {
+        components = [EmailListener, #[multi(dyn Listener)] AuditListener],
+        providers = []
+    }
+}
+
+#[test]
+fn inject_multi_bound_components() {
+    let module = TestModule::builder().build();
+
+    let event_bus: &dyn EventBus = module.resolve_ref();
+    assert_eq!(event_bus.listener_names(), vec!["audit", "metrics"]);
+
+    let listener_count: Box<dyn ListenerCount> = module.provide().unwrap();
+    assert_eq!(listener_count.count(), 2);
+}
+
+#[test]
+fn multi_bound_components_are_shared() {
+    let module = TestModule::builder().build();
+
+    let listeners1: Vec<Arc<dyn Listener>> = module.resolve_all();
+    let listeners2: Vec<Arc<dyn Listener>> = module.resolve_all();
+    assert_eq!(listeners1.len(), 2);
+    assert!(Arc::ptr_eq(&listeners1[0], &listeners2[0]));
+    assert!(Arc::ptr_eq(&listeners1[1], &listeners2[1]));
+}
+
+#[test]
+fn multi_component_parameters() {
+    let module = TestModule::builder()
+        .with_multi_component_parameters::<MetricsListener>(MetricsListenerParameters {
+            name: "prometheus".to_string(),
+        })
+        .build();
+
+    let event_bus: &dyn EventBus = module.resolve_ref();
+    assert_eq!(event_bus.listener_names(), vec!["audit", "prometheus"]);
+}
+
+#[test]
+fn multi_bound_components_from_submodules() {
+    let test_module = Arc::new(TestModule::builder().build());
+    let email_module = Arc::new(EmailModule::builder().build());
+    let module = ParentModule::builder(test_module, email_module).build();
+
+    let event_bus: &dyn EventBus = module.resolve_ref();
+    assert_eq!(
+        event_bus.listener_names(),
+        vec!["audit", "audit", "metrics", "email"]
+    );
+}
+
+#[test]
+fn multi_bound_and_single_component() {
+    let module = MixedModule::builder().build();
+
+    let listener: &dyn Listener = module.resolve_ref();
+    let listeners: Vec<Arc<dyn Listener>> = module.resolve_all();
+    assert_eq!(listener.name(), "email");
+    assert_eq!(listeners.len(), 1);
+    assert_eq!(listeners[0].name(), "audit");
+}
@@ -0,0 +1,180 @@
+//! Test named components, which allow multiple components of the same interface
+
+use shaku::{module, Component, HasComponent, HasNamedComponent, HasProvider, Interface, Provider};
+use std::sync::Arc;
+
+trait Database: Interface {
+    fn url(&self) -> String;
+}
+trait Repository: Interface {
+    fn urls(&self) -> (String, String);
+}
+trait ReportService {
+    fn replica_url(&self) -> String;
+}
+
+struct Replica;
+struct Analytics;
+
+#[derive(Component)]
+#[shaku(interface = Database)]
+struct DatabaseImpl {
+    #[shaku(default = "db://primary".to_string())]
+    url: String,
+}
+impl Database for DatabaseImpl {
+    fn url(&self) -> String {
+        self.url.clone()
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Database)]
+struct ReplicaDatabaseImpl {
+    #[shaku(default = "db://replica".to_string())]
+    url: String,
+}
+impl Database for ReplicaDatabaseImpl {
+    fn url(&self) -> String {
+        self.url.clone()
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Repository)]
+struct RepositoryImpl {
+    #[shaku(inject)]
+    primary: Arc<dyn Database>,
+    #[shaku(inject, named = Replica)]
+    replica: Arc<dyn Database>,
+}
+impl Repository for RepositoryImpl {
+    fn urls(&self) -> (String, String) {
+        (self.primary.url(), self.replica.url())
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = ReportService)]
+struct ReportServiceImpl {
+    #[shaku(inject, named = Replica)]
+    replica: Arc<dyn Database>,
+}
+impl ReportService for ReportServiceImpl {
+    fn replica_url(&self) -> String {
+        self.replica.url()
+    }
+}
+
+module! {
+    TestModule {
+        components = [
+            RepositoryImpl,
+            DatabaseImpl,
+            #[named(Replica)] ReplicaDatabaseImpl,
+            #[lazy] #[named(Analytics)] ReplicaDatabaseImpl
+        ],
+        providers = [ReportServiceImpl]
+    }
+}
+
+module! {
+    ParentModule {
+        components = [],
+        providers = [],
+
+        use TestModule {
+            components = [dyn Database, #[named(Replica)] dyn Database],
+            providers = []
+        }
+    }
+}
+
+#[test]
+fn named_and_unnamed_components_are_separate() {
+    let module = TestModule::builder().build();
+
+    let primary: &dyn Database = module.resolve_ref();
+    let replica: &dyn Database =
+        HasNamedComponent::<dyn Database, Replica>::resolve_named_ref(&module);
+    assert_eq!(primary.url(), "db://primary");
+    assert_eq!(replica.url(), "db://replica");
+}
+
+#[test]
+fn inject_named_component() {
+    let module = TestModule::builder().build();
+
+    let repository: &dyn Repository = module.resolve_ref();
+    assert_eq!(
+        repository.urls(),
+        ("db://primary".to_string(), "db://replica".to_string())
+    );
+
+    let report_service: Box<dyn ReportService> = module.provide().unwrap();
+    assert_eq!(report_service.replica_url(), "db://replica");
+}
+
+/// The same component type can be used under multiple tags, with different parameters
+#[test]
+fn named_component_parameters() {
+    let module = TestModule::builder()
+        .with_named_component_parameters::<ReplicaDatabaseImpl, Analytics>(
+            ReplicaDatabaseImplParameters {
+                url: "db://analytics".to_string(),
+            },
+        )
+        .build();
+
+    let replica: Arc<dyn Database> =
+        HasNamedComponent::<dyn Database, Replica>::resolve_named(&module);
+    let analytics: Arc<dyn Database> =
+        HasNamedComponent::<dyn Database, Analytics>::resolve_named(&module);
+    assert_eq!(replica.url(), "db://replica");
+    assert_eq!(analytics.url(), "db://analytics");
+}
+
+#[test]
+fn override_named_component() {
+    let module = TestModule::builder()
+        .with_named_component_override::<dyn Database, Replica>(Box::new(DatabaseImpl {
+            url: "db://override".to_string(),
+        }))
+        .build();
+
+    let primary: &dyn Database = module.resolve_ref();
+    let repository: &dyn Repository = module.resolve_ref();
+    assert_eq!(primary.url(), "db://primary");
+    assert_eq!(
+        repository.urls(),
+        ("db://primary".to_string(), "db://override".to_string())
+    );
+}
+
+#[test]
+fn override_named_component_fn() {
+    let module = TestModule::builder()
+        .with_named_component_override_fn::<dyn Database, Replica>(Box::new(|context| {
+            let primary: Arc<dyn Database> = TestModule::build_component(context);
+            Box::new(DatabaseImpl {
+                url: format!("{}/replica", primary.url()),
+            })
+        }))
+        .build();
+
+    let replica: &dyn Database =
+        HasNamedComponent::<dyn Database, Replica>::resolve_named_ref(&module);
+    assert_eq!(replica.url(), "db://primary/replica");
+}
+
+#[test]
+fn import_named_component_from_submodule() {
+    let submodule = Arc::new(TestModule::builder().build());
+    let module = ParentModule::builder(submodule).build();
+
+    let primary: &dyn Database = module.resolve_ref();
+    let replica: &dyn Database =
+        HasNamedComponent::<dyn Database, Replica>::resolve_named_ref(&module);
+    assert_eq!(primary.url(), "db://primary");
+    assert_eq!(replica.url(), "db://replica");
+}
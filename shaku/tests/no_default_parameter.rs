@@ -1,6 +1,9 @@
 //! Tests related to parameters which do not have a default value
 
-use shaku::{module, Component, Interface};
+use shaku::{
+    module, BuildError, Component, HasComponent, HasComponentMap, HasComponents, HasNamedComponent,
+    Interface,
+};
 
 trait MyComponent: Interface {}
 
@@ -15,6 +18,9 @@ struct MyComponentImpl {
 }
 impl MyComponent for MyComponentImpl {}
 
+struct FakeComponent;
+impl MyComponent for FakeComponent {}
+
 module! {
     TestModule {
         components = [MyComponentImpl],
@@ -22,6 +28,19 @@ module! {
     }
 }
 
+struct Tag;
+
+module! {
+    BindingsModule {
+        components = [
+            #[named(Tag)] MyComponentImpl,
+            #[multi(dyn MyComponent)] MyComponentImpl,
+            #[multi(dyn MyComponent)] #[key("a")] MyComponentImpl
+        ],
+        providers = []
+    }
+}
+
 /// Providing the parameter will allow module creation to succeed
 #[test]
 fn with_given_parameter() {
@@ -32,9 +51,81 @@ fn with_given_parameter() {
         .build();
 }
 
-/// Not providing the parameter will cause a panic
+/// Not providing the parameter to an unchecked builder will cause a panic
 #[test]
-#[should_panic(expected = "There is no default value for `MyComponentImpl::no_default`")]
+#[should_panic(
+    expected = "There is no default value for `no_default_parameter::MyComponentImpl::no_default`"
+)]
 fn without_given_parameter() {
-    TestModule::builder().build();
+    TestModule::builder().unchecked().build();
+}
+
+/// Not providing the parameter will cause `try_build` to return an error
+#[test]
+fn without_given_parameter_try_build() {
+    let result = TestModule::builder().try_build();
+
+    match result {
+        Err(BuildError::MissingParameter {
+            component,
+            parameter,
+        }) => {
+            assert_eq!(component, "no_default_parameter::MyComponentImpl");
+            assert_eq!(parameter, "no_default");
+        }
+        Err(e) => panic!("Unexpected error: {}", e),
+        Ok(_) => panic!("Expected the build to fail"),
+    }
+}
+
+/// Named, multi-bound, and keyed components need their parameters set
+/// separately
+#[test]
+fn bindings_with_given_parameters() {
+    let module = BindingsModule::builder()
+        .with_named_component_parameters::<MyComponentImpl, Tag>(MyComponentImplParameters {
+            no_default: NoDefault,
+        })
+        .with_multi_component_parameters::<MyComponentImpl>(MyComponentImplParameters {
+            no_default: NoDefault,
+        })
+        .with_keyed_component_parameters::<MyComponentImpl>(MyComponentImplParameters {
+            no_default: NoDefault,
+        })
+        .build();
+
+    assert_eq!(
+        HasComponents::<dyn MyComponent>::resolve_all(&module).len(),
+        1
+    );
+    assert_eq!(
+        HasComponentMap::<dyn MyComponent>::resolve_map(&module).len(),
+        1
+    );
+}
+
+/// Overridden components are not built, so their parameters don't need to be
+/// set
+#[test]
+fn overridden_without_given_parameter() {
+    let module = TestModule::builder()
+        .with_component_override::<dyn MyComponent>(Box::new(FakeComponent))
+        .build();
+    let _: &dyn MyComponent = module.resolve_ref();
+
+    let module = TestModule::builder()
+        .with_component_override_fn::<dyn MyComponent>(Box::new(|_| Box::new(FakeComponent)))
+        .build();
+    let _: &dyn MyComponent = module.resolve_ref();
+
+    let module = BindingsModule::builder()
+        .with_named_component_override::<dyn MyComponent, Tag>(Box::new(FakeComponent))
+        .with_multi_component_parameters::<MyComponentImpl>(MyComponentImplParameters {
+            no_default: NoDefault,
+        })
+        .with_keyed_component_parameters::<MyComponentImpl>(MyComponentImplParameters {
+            no_default: NoDefault,
+        })
+        .build();
+    let _ = HasNamedComponent::<dyn MyComponent, Tag>::resolve_named_ref(&module);
 }
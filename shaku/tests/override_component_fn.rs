@@ -73,7 +73,7 @@ module! {
 }
 
 #[test]
-#[should_panic = "Circular dependency detected while resolving dyn override_component_fn::MyInterface. Resolution chain: [override_component_fn::MyComponent]"]
+#[should_panic = "Circular dependency detected: override_component_fn::MyComponent -> override_component_fn::MyComponent"]
 fn detects_circular_dependency() {
     MyCircularModule::builder()
         .with_component_override_fn::<dyn MyInterface>(Box::new(|context| {
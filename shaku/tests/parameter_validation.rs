@@ -0,0 +1,224 @@
+//! Test validating component parameters with `#[shaku(validate = ...)]`
+
+use shaku::{module, BuildError, Component, HasComponent, Interface, ParameterViolation};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+trait Server: Interface {
+    fn port(&self) -> u16;
+}
+trait Pool: Interface {
+    fn size(&self) -> usize;
+}
+
+fn non_zero_port(port: &u16) -> Result<(), &'static str> {
+    if *port == 0 {
+        Err("must not be zero")
+    } else {
+        Ok(())
+    }
+}
+
+fn valid_host(host: &str) -> Result<(), String> {
+    if host.contains(' ') {
+        Err(format!("{:?} is not a valid host", host))
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Server)]
+struct ServerImpl {
+    #[shaku(default = "localhost".to_string(), validate = valid_host)]
+    #[allow(dead_code)]
+    host: String,
+    #[shaku(default = 8080, validate = non_zero_port)]
+    port: u16,
+}
+impl Server for ServerImpl {
+    fn port(&self) -> u16 {
+        self.port
+    }
+}
+
+fn valid_pool(params: &PoolImplParameters) -> Result<(), String> {
+    if params.min_size > params.max_size {
+        Err("min_size must not be greater than max_size".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Pool, validate = valid_pool)]
+struct PoolImpl {
+    #[shaku(default = 1)]
+    min_size: usize,
+    #[shaku(default = 4)]
+    max_size: usize,
+}
+impl Pool for PoolImpl {
+    fn size(&self) -> usize {
+        self.max_size - self.min_size
+    }
+}
+
+trait Worker: Interface {}
+
+static WORKER_BUILDS: AtomicUsize = AtomicUsize::new(0);
+static WORKER_STARTS: AtomicUsize = AtomicUsize::new(0);
+
+fn non_zero_threads(threads: &usize) -> Result<(), &'static str> {
+    if *threads == 0 {
+        Err("must not be zero")
+    } else {
+        Ok(())
+    }
+}
+
+/// A component which counts how many times it was built and started
+#[derive(Component)]
+#[shaku(interface = Worker, try_build = WorkerImpl::check, on_start = WorkerImpl::start)]
+struct WorkerImpl {
+    #[shaku(default = 1, validate = non_zero_threads)]
+    #[allow(dead_code)]
+    threads: usize,
+}
+impl Worker for WorkerImpl {}
+impl WorkerImpl {
+    fn check(self) -> Result<Self, String> {
+        WORKER_BUILDS.fetch_add(1, Ordering::SeqCst);
+        Ok(self)
+    }
+
+    fn start(&self) {
+        WORKER_STARTS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+module! {
+    TestModule {
+        components = [ServerImpl, PoolImpl],
+        providers = []
+    }
+}
+
+module! {
+    WorkerModule {
+        components = [WorkerImpl, ServerImpl],
+        providers = []
+    }
+}
+
+module! {
+    LazyModule {
+        components = [#[lazy] ServerImpl],
+        providers = []
+    }
+}
+
+#[test]
+fn valid_parameters() {
+    let module = TestModule::builder()
+        .with_component_parameters::<ServerImpl>(ServerImplParameters {
+            host: "example.com".to_string(),
+            port: 80,
+        })
+        .build();
+
+    let server: &dyn Server = module.resolve_ref();
+    let pool: &dyn Pool = module.resolve_ref();
+    assert_eq!(server.port(), 80);
+    assert_eq!(pool.size(), 3);
+}
+
+/// Every violation in the module is reported, not just the first one
+#[test]
+fn violations_are_collected() {
+    let result = TestModule::builder()
+        .with_component_parameters::<ServerImpl>(ServerImplParameters {
+            host: "example com".to_string(),
+            port: 0,
+        })
+        .with_component_parameters::<PoolImpl>(PoolImplParameters {
+            min_size: 8,
+            max_size: 4,
+        })
+        .try_build();
+
+    match result {
+        Err(BuildError::InvalidParameters { violations }) => assert_eq!(
+            violations,
+            vec![
+                ParameterViolation {
+                    component: "parameter_validation::ServerImpl",
+                    parameter: Some("host"),
+                    message: "\"example com\" is not a valid host".to_string(),
+                },
+                ParameterViolation {
+                    component: "parameter_validation::ServerImpl",
+                    parameter: Some("port"),
+                    message: "must not be zero".to_string(),
+                },
+                ParameterViolation {
+                    component: "parameter_validation::PoolImpl",
+                    parameter: None,
+                    message: "min_size must not be greater than max_size".to_string(),
+                },
+            ]
+        ),
+        Err(e) => panic!("Unexpected error: {}", e),
+        Ok(_) => panic!("Expected the build to fail"),
+    }
+}
+
+/// A component with invalid parameters is never built or started, and the
+/// rest of the module is still validated
+#[test]
+fn invalid_component_is_not_built() {
+    let result = WorkerModule::builder()
+        .with_component_parameters::<WorkerImpl>(WorkerImplParameters { threads: 0 })
+        .with_component_parameters::<ServerImpl>(ServerImplParameters {
+            host: "localhost".to_string(),
+            port: 0,
+        })
+        .try_build();
+
+    match result {
+        Err(BuildError::InvalidParameters { violations }) => {
+            let parameters: Vec<_> = violations.iter().map(|v| v.parameter).collect();
+            assert_eq!(parameters, vec![Some("threads"), Some("port")]);
+        }
+        Err(e) => panic!("Unexpected error: {}", e),
+        Ok(_) => panic!("Expected the build to fail"),
+    }
+    assert_eq!(WORKER_BUILDS.load(Ordering::SeqCst), 0);
+    assert_eq!(WORKER_STARTS.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+#[should_panic(expected = "Invalid component parameters:\n\
+                           - `parameter_validation::ServerImpl::port`: must not be zero")]
+fn build_panics() {
+    TestModule::builder()
+        .with_component_parameters::<ServerImpl>(ServerImplParameters {
+            host: "localhost".to_string(),
+            port: 0,
+        })
+        .build();
+}
+
+/// Lazy components are validated when they are built
+#[test]
+#[should_panic(expected = "Invalid component parameters:\n\
+                           - `parameter_validation::ServerImpl::port`: must not be zero")]
+fn lazy_component() {
+    let module = LazyModule::builder()
+        .with_component_parameters::<ServerImpl>(ServerImplParameters {
+            host: "localhost".to_string(),
+            port: 0,
+        })
+        .build();
+
+    let _server: &dyn Server = module.resolve_ref();
+}
@@ -0,0 +1,137 @@
+//! Test loading component parameters from configuration via serde
+#![cfg(feature = "serde")]
+
+use serde_json::json;
+use shaku::{module, Component, HasComponent, HasNamedComponent, Interface, ModuleBuilder};
+use std::sync::Arc;
+
+trait Database: Interface {
+    fn url(&self) -> String;
+    fn pool_size(&self) -> usize;
+}
+trait Mailer: Interface {
+    fn sender(&self) -> String;
+}
+
+struct Replica;
+
+#[derive(Component)]
+#[shaku(interface = Database)]
+struct DatabaseImpl {
+    url: String,
+    #[shaku(default = 4)]
+    pool_size: usize,
+}
+impl Database for DatabaseImpl {
+    fn url(&self) -> String {
+        self.url.clone()
+    }
+
+    fn pool_size(&self) -> usize {
+        self.pool_size
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Mailer)]
+struct MailerImpl {
+    #[shaku(default)]
+    sender: String,
+    #[shaku(inject)]
+    #[allow(dead_code)]
+    database: Arc<dyn Database>,
+}
+impl Mailer for MailerImpl {
+    fn sender(&self) -> String {
+        self.sender.clone()
+    }
+}
+
+module! {
+    TestModule {
+        components = [DatabaseImpl, #[named(Replica)] DatabaseImpl, MailerImpl],
+        providers = []
+    }
+}
+
+fn builder(config: serde_json::Value) -> ModuleBuilder<TestModule> {
+    TestModule::builder().with_parameters_from(config).unwrap()
+}
+
+#[test]
+fn parameters_are_loaded() {
+    let module = builder(json!({
+        "DatabaseImpl": { "url": "db://primary", "pool_size": 16 },
+        "DatabaseImpl(Replica)": { "url": "db://replica" },
+        "MailerImpl": { "sender": "noreply@example.com" }
+    }))
+    .build();
+
+    let database: &dyn Database = module.resolve_ref();
+    let replica: &dyn Database =
+        HasNamedComponent::<dyn Database, Replica>::resolve_named_ref(&module);
+    let mailer: &dyn Mailer = module.resolve_ref();
+    assert_eq!(database.url(), "db://primary");
+    assert_eq!(database.pool_size(), 16);
+    assert_eq!(replica.url(), "db://replica");
+    assert_eq!(mailer.sender(), "noreply@example.com");
+}
+
+/// Parameters which are left out fall back to their default
+#[test]
+fn missing_parameters_use_default() {
+    let module = builder(json!({
+        "DatabaseImpl": { "url": "db://primary" },
+        "DatabaseImpl(Replica)": { "url": "db://replica" }
+    }))
+    .build();
+
+    let replica: &dyn Database =
+        HasNamedComponent::<dyn Database, Replica>::resolve_named_ref(&module);
+    let mailer: &dyn Mailer = module.resolve_ref();
+    assert_eq!(replica.pool_size(), 4);
+    assert_eq!(mailer.sender(), "");
+}
+
+/// Parameters without a default must be in the configuration
+#[test]
+fn missing_required_parameter() {
+    let result = TestModule::builder().with_parameters_from(json!({
+        "DatabaseImpl": { "pool_size": 16 }
+    }));
+
+    let error = result
+        .err()
+        .expect("Expected the configuration to be rejected");
+    assert_eq!(error.to_string(), "missing field `url`");
+}
+
+#[test]
+fn unknown_component() {
+    let result = TestModule::builder().with_parameters_from(json!({
+        "CacheImpl": {}
+    }));
+
+    let error = result
+        .err()
+        .expect("Expected the configuration to be rejected");
+    assert!(error.to_string().starts_with("unknown field `CacheImpl`"));
+}
+
+/// Configuration can be combined with parameters set in code. The last
+/// parameters set for a component are used.
+#[test]
+fn combined_with_code() {
+    let module = builder(json!({
+        "DatabaseImpl": { "url": "db://primary" },
+        "DatabaseImpl(Replica)": { "url": "db://replica" }
+    }))
+    .with_component_parameters::<DatabaseImpl>(DatabaseImplParameters {
+        url: "db://override".to_string(),
+        pool_size: 1,
+    })
+    .build();
+
+    let database: &dyn Database = module.resolve_ref();
+    assert_eq!(database.url(), "db://override");
+}
@@ -0,0 +1,270 @@
+//! Test reloadable components, which can be replaced after the module is built
+
+use shaku::{module, Component, HasComponent, HasProvider, Interface, Module, Provider};
+use std::sync::{Arc, Mutex};
+
+trait Credentials: Interface {
+    fn token(&self) -> String;
+}
+trait Client: Interface {
+    fn token(&self) -> String;
+}
+trait Request {
+    fn token(&self) -> String;
+}
+
+#[derive(Component)]
+#[shaku(interface = Credentials)]
+struct CredentialsImpl {
+    #[shaku(default = "first".to_string())]
+    token: String,
+}
+impl Credentials for CredentialsImpl {
+    fn token(&self) -> String {
+        self.token.clone()
+    }
+}
+
+fn credentials(token: &str) -> Box<CredentialsImpl> {
+    Box::new(CredentialsImpl {
+        token: token.to_string(),
+    })
+}
+
+#[derive(Component)]
+#[shaku(interface = Client)]
+struct ClientImpl {
+    #[shaku(inject)]
+    credentials: Arc<dyn Credentials>,
+}
+impl Client for ClientImpl {
+    fn token(&self) -> String {
+        self.credentials.token()
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = Request)]
+struct RequestImpl {
+    #[shaku(inject)]
+    credentials: Arc<dyn Credentials>,
+}
+impl Request for RequestImpl {
+    fn token(&self) -> String {
+        self.credentials.token()
+    }
+}
+
+trait Session: Interface {
+    fn id(&self) -> u32;
+}
+
+/// A component with lifecycle hooks, which records them in `events`
+#[derive(Component)]
+#[shaku(interface = Session, on_start = SessionImpl::start, on_stop = SessionImpl::stop)]
+struct SessionImpl {
+    #[shaku(default = 1)]
+    id: u32,
+    #[shaku(default)]
+    events: Arc<Mutex<Vec<String>>>,
+}
+impl Session for SessionImpl {
+    fn id(&self) -> u32 {
+        self.id
+    }
+}
+impl SessionImpl {
+    fn start(&self) {
+        self.events
+            .lock()
+            .unwrap()
+            .push(format!("start {}", self.id));
+    }
+
+    fn stop(&self) {
+        self.events
+            .lock()
+            .unwrap()
+            .push(format!("stop {}", self.id));
+    }
+}
+
+/// Multiplies the id of the session it wraps
+struct ScaledSession(Arc<dyn Session>);
+impl Session for ScaledSession {
+    fn id(&self) -> u32 {
+        self.0.id() * 10
+    }
+}
+
+module! {
+    SessionModule {
+        components = [#[reloadable] SessionImpl],
+        providers = []
+    }
+}
+
+module! {
+    TestModule {
+        components = [#[reloadable] CredentialsImpl, ClientImpl],
+        providers = [RequestImpl]
+    }
+}
+
+module! {
+    LazyModule {
+        components = [#[reloadable] CredentialsImpl, #[lazy] ClientImpl],
+        providers = []
+    }
+}
+
+module! {
+    RootModule {
+        components = [],
+        providers = [],
+
+        use TestModule {
+            components = [#[reloadable] dyn Credentials],
+            providers = []
+        }
+    }
+}
+
+#[test]
+fn resolve_returns_latest_instance() {
+    let module = TestModule::builder().build();
+    let first: Arc<dyn Credentials> = module.resolve();
+
+    let old = module.replace::<dyn Credentials>(credentials("second"));
+
+    let credentials: Arc<dyn Credentials> = module.resolve();
+    assert_eq!(old.token(), "first");
+    assert_eq!(credentials.token(), "second");
+    // The old instance is still usable
+    assert_eq!(first.token(), "first");
+}
+
+/// Only the latest instance is kept, so it can't be borrowed
+#[test]
+#[should_panic(
+    expected = "reloadable_components::CredentialsImpl is reloadable, so it can't be \
+                           borrowed. Use HasComponent::resolve to resolve it."
+)]
+fn resolve_ref_panics() {
+    let module = TestModule::builder().build();
+    let _credentials: &dyn Credentials = module.resolve_ref();
+}
+
+#[test]
+fn replacement_is_started_and_stopped() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let module = SessionModule::builder()
+        .with_component_parameters::<SessionImpl>(SessionImplParameters {
+            id: 1,
+            events: Arc::clone(&events),
+        })
+        .build();
+
+    module.replace::<dyn Session>(Box::new(SessionImpl {
+        id: 2,
+        events: Arc::clone(&events),
+    }));
+    module.shutdown();
+
+    assert_eq!(
+        *events.lock().unwrap(),
+        vec!["start 1", "start 2", "stop 2", "stop 1"]
+    );
+}
+
+#[test]
+fn replacement_is_decorated() {
+    let module = SessionModule::builder()
+        .with_component_decorator::<dyn Session>(Box::new(|session, _| {
+            Box::new(ScaledSession(session))
+        }))
+        .build();
+
+    let old = module.replace::<dyn Session>(Box::new(SessionImpl {
+        id: 2,
+        events: Arc::default(),
+    }));
+
+    let session: Arc<dyn Session> = module.resolve();
+    assert_eq!(old.id(), 10);
+    assert_eq!(session.id(), 20);
+}
+
+#[test]
+fn injected_components_keep_old_instance() {
+    let module = TestModule::builder().build();
+    module.replace::<dyn Credentials>(credentials("second"));
+
+    let client: &dyn Client = module.resolve_ref();
+    assert_eq!(client.token(), "first");
+}
+
+#[test]
+fn providers_use_latest_instance() {
+    let module = TestModule::builder().build();
+    module.replace::<dyn Credentials>(credentials("second"));
+
+    let request: Box<dyn Request> = module.provide().unwrap();
+    assert_eq!(request.token(), "second");
+}
+
+#[test]
+fn lazy_components_use_latest_instance() {
+    let module = LazyModule::builder().build();
+    module.replace::<dyn Credentials>(credentials("second"));
+
+    let client: &dyn Client = module.resolve_ref();
+    assert_eq!(client.token(), "second");
+}
+
+#[test]
+fn replace_overridden_component() {
+    let module = TestModule::builder()
+        .with_component_override::<dyn Credentials>(credentials("override"))
+        .build();
+
+    let old = module.replace::<dyn Credentials>(credentials("second"));
+    assert_eq!(old.token(), "override");
+}
+
+#[test]
+fn replace_submodule_component() {
+    let submodule = Arc::new(TestModule::builder().build());
+    let module = RootModule::builder(Arc::clone(&submodule)).build();
+
+    module.replace::<dyn Credentials>(credentials("second"));
+
+    let credentials: Arc<dyn Credentials> = submodule.resolve();
+    assert_eq!(credentials.token(), "second");
+}
+
+#[test]
+#[cfg(feature = "thread_safe")]
+fn concurrent_replacements() {
+    use std::thread;
+
+    let module = Arc::new(TestModule::builder().build());
+
+    let threads: Vec<_> = (0..8)
+        .map(|i| {
+            let module = Arc::clone(&module);
+            thread::spawn(move || {
+                for j in 0..100 {
+                    module.replace::<dyn Credentials>(credentials(&format!("{}-{}", i, j)));
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    // The latest instance is the last replacement made by one of the threads
+    let credentials: Arc<dyn Credentials> = module.resolve();
+    assert!(credentials.token().ends_with("-99"));
+}
@@ -0,0 +1,136 @@
+//! Test resolving components by the `TypeId` of their interface with `ResolveAny`
+
+use shaku::{module, Component, DynamicModule, HasComponent, Interface, InterfaceInfo, ResolveAny};
+use std::any::TypeId;
+use std::sync::Arc;
+
+trait Database: Interface {
+    fn name(&self) -> String;
+}
+trait Cache: Interface {}
+trait Service: Interface {}
+trait Unused: Interface {}
+struct Primary;
+
+#[derive(Component)]
+#[shaku(interface = Database)]
+struct DatabaseImpl;
+impl Database for DatabaseImpl {
+    fn name(&self) -> String {
+        "database".to_string()
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Cache)]
+struct CacheImpl;
+impl Cache for CacheImpl {}
+
+#[derive(Component)]
+#[shaku(interface = Service)]
+struct ServiceImpl {
+    #[shaku(inject)]
+    #[allow(dead_code)]
+    database: Arc<dyn Database>,
+}
+impl Service for ServiceImpl {}
+
+module! {
+    DatabaseModule {
+        components = [DatabaseImpl, #[named(Primary)] CacheImpl],
+        providers = []
+    }
+}
+
+module! {
+    RootModule {
+        components = [#[lazy] ServiceImpl, CacheImpl],
+        providers = [],
+
+        use DatabaseModule {
+            components = [dyn Database],
+            providers = []
+        }
+    }
+}
+
+fn root_module() -> RootModule {
+    RootModule::builder(Arc::new(DatabaseModule::builder().build())).build()
+}
+
+#[test]
+fn interfaces() {
+    let module = root_module();
+
+    assert_eq!(
+        module.interfaces(),
+        vec![
+            InterfaceInfo::of::<dyn Service>(),
+            InterfaceInfo::of::<dyn Cache>(),
+            InterfaceInfo::of::<dyn Database>(),
+        ]
+    );
+    assert_eq!(
+        module.interfaces()[2].type_name,
+        "dyn resolve_any::Database"
+    );
+}
+
+/// Named components are not resolved by their interface alone
+#[test]
+fn named_components_excluded() {
+    let module = DatabaseModule::builder().build();
+
+    assert_eq!(
+        module.interfaces(),
+        vec![InterfaceInfo::of::<dyn Database>()]
+    );
+    assert!(module.resolve_any(TypeId::of::<dyn Cache>()).is_none());
+}
+
+#[test]
+fn resolve_any() {
+    let module = root_module();
+
+    let database = module.resolve_any(TypeId::of::<dyn Database>()).unwrap();
+    let database = database.downcast_ref::<Arc<dyn Database>>().unwrap();
+    assert_eq!(database.name(), "database");
+
+    // The lazy component is built when it is resolved
+    let service = module.resolve_any(TypeId::of::<dyn Service>()).unwrap();
+    let service = service.downcast_ref::<Arc<dyn Service>>().unwrap();
+    let resolved_service: Arc<dyn Service> = module.resolve();
+    assert!(Arc::ptr_eq(service, &resolved_service));
+
+    assert!(module.resolve_any(TypeId::of::<dyn Unused>()).is_none());
+}
+
+#[test]
+fn dynamic_module() {
+    let module = DynamicModule::builder()
+        .with_component::<CacheImpl>()
+        .with_component::<DatabaseImpl>()
+        .build();
+
+    assert_eq!(
+        module.interfaces(),
+        vec![
+            InterfaceInfo::of::<dyn Cache>(),
+            InterfaceInfo::of::<dyn Database>(),
+        ]
+    );
+
+    let database = module.resolve_any(TypeId::of::<dyn Database>()).unwrap();
+    let database = database.downcast_ref::<Arc<dyn Database>>().unwrap();
+    assert_eq!(database.name(), "database");
+    assert!(module.resolve_any(TypeId::of::<dyn Service>()).is_none());
+}
+
+/// Modules can be used as trait objects
+#[test]
+fn trait_object() {
+    let module: Box<dyn ResolveAny> = Box::new(root_module());
+
+    assert_eq!(module.interfaces().len(), 3);
+    assert!(module.resolve_any(TypeId::of::<dyn Cache>()).is_some());
+}
@@ -0,0 +1,181 @@
+//! Test scoped providers, which are shared within a scope
+
+use shaku::{module, Component, HasComponent, HasProvider, Interface, Module, Provider};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Weak};
+
+trait IdGenerator: Interface {
+    fn next_id(&self) -> usize;
+}
+trait Transaction: Interface {
+    fn id(&self) -> usize;
+}
+trait UserRepository {
+    fn transaction_id(&self) -> usize;
+}
+trait OrderRepository {
+    fn transaction_id(&self) -> usize;
+}
+trait CheckoutService {
+    fn transaction_ids(&self) -> (usize, usize);
+}
+
+#[derive(Component)]
+#[shaku(interface = IdGenerator)]
+struct IdGeneratorImpl {
+    #[shaku(default)]
+    next_id: AtomicUsize,
+}
+impl IdGenerator for IdGeneratorImpl {
+    fn next_id(&self) -> usize {
+        self.next_id.fetch_add(1, Ordering::SeqCst)
+    }
+}
+
+struct TransactionImpl {
+    id: usize,
+}
+impl Transaction for TransactionImpl {
+    fn id(&self) -> usize {
+        self.id
+    }
+}
+impl<M: Module + HasComponent<dyn IdGenerator>> Provider<M> for TransactionImpl {
+    type Interface = dyn Transaction;
+
+    fn provide(module: &M) -> Result<Box<dyn Transaction>, Box<dyn std::error::Error>> {
+        let id_generator: &dyn IdGenerator = module.resolve_ref();
+        Ok(Box::new(TransactionImpl {
+            id: id_generator.next_id(),
+        }))
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = UserRepository)]
+struct UserRepositoryImpl {
+    // Provided Arc properties get the scope's scoped service
+    #[shaku(provide)]
+    transaction: Arc<dyn Transaction>,
+}
+impl UserRepository for UserRepositoryImpl {
+    fn transaction_id(&self) -> usize {
+        self.transaction.id()
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = OrderRepository)]
+struct OrderRepositoryImpl {
+    #[shaku(provide)]
+    transaction: Arc<dyn Transaction>,
+}
+impl OrderRepository for OrderRepositoryImpl {
+    fn transaction_id(&self) -> usize {
+        self.transaction.id()
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = CheckoutService)]
+struct CheckoutServiceImpl {
+    #[shaku(provide)]
+    users: Box<dyn UserRepository>,
+    #[shaku(provide)]
+    orders: Box<dyn OrderRepository>,
+}
+impl CheckoutService for CheckoutServiceImpl {
+    fn transaction_ids(&self) -> (usize, usize) {
+        (self.users.transaction_id(), self.orders.transaction_id())
+    }
+}
+
+module! {
+    TestModule {
+        components = [IdGeneratorImpl],
+        providers = [
+            #[scoped] TransactionImpl,
+            UserRepositoryImpl,
+            OrderRepositoryImpl,
+            CheckoutServiceImpl
+        ]
+    }
+}
+
+#[test]
+fn scoped_service_is_shared_within_scope() {
+    let module = TestModule::builder().build();
+    let scope = module.create_scope();
+
+    let transaction1: Arc<dyn Transaction> = scope.provide().unwrap();
+    let transaction2: Arc<dyn Transaction> = scope.provide().unwrap();
+    assert!(Arc::ptr_eq(&transaction1, &transaction2));
+
+    let checkout: Box<dyn CheckoutService> =
+        HasProvider::<dyn CheckoutService>::provide_scoped(&scope).unwrap();
+    assert_eq!(checkout.transaction_ids(), (0, 0));
+}
+
+#[test]
+fn scopes_are_separate() {
+    let module = TestModule::builder().build();
+    let scope1 = module.create_scope();
+    let scope2 = module.create_scope();
+
+    let transaction1: Arc<dyn Transaction> = scope1.provide().unwrap();
+    let transaction2: Arc<dyn Transaction> = scope2.provide().unwrap();
+    assert_eq!(transaction1.id(), 0);
+    assert_eq!(transaction2.id(), 1);
+}
+
+/// Outside of a scope, each provided service gets its own scope
+#[test]
+fn provide_without_scope() {
+    let module = TestModule::builder().build();
+
+    let checkout1: Box<dyn CheckoutService> = module.provide().unwrap();
+    let checkout2: Box<dyn CheckoutService> = module.provide().unwrap();
+    assert_eq!(checkout1.transaction_ids(), (0, 0));
+    assert_eq!(checkout2.transaction_ids(), (1, 1));
+
+    let transaction: Box<dyn Transaction> = module.provide().unwrap();
+    assert_eq!(transaction.id(), 2);
+}
+
+#[test]
+fn scoped_services_are_dropped_with_scope() {
+    let module = TestModule::builder().build();
+    let scope = module.create_scope();
+
+    let transaction: Weak<dyn Transaction> =
+        Arc::downgrade(&scope.provide::<dyn Transaction>().unwrap());
+    assert!(transaction.upgrade().is_some());
+
+    drop(scope);
+    assert!(transaction.upgrade().is_none());
+}
+
+#[test]
+fn singletons_are_resolved_from_module() {
+    let module = TestModule::builder().build();
+    let scope = module.create_scope();
+
+    let id_generator1: Arc<dyn IdGenerator> = scope.module().resolve();
+    let id_generator2: Arc<dyn IdGenerator> = module.resolve();
+    assert!(Arc::ptr_eq(&id_generator1, &id_generator2));
+}
+
+#[test]
+fn override_scoped_provider() {
+    let module = TestModule::builder()
+        .with_provider_override::<dyn Transaction>(Box::new(|_| {
+            Ok(Box::new(TransactionImpl { id: 100 }))
+        }))
+        .build();
+    let scope = module.create_scope();
+
+    let transaction1: Arc<dyn Transaction> = scope.provide().unwrap();
+    let transaction2: Arc<dyn Transaction> = scope.provide().unwrap();
+    assert_eq!(transaction1.id(), 100);
+    assert!(Arc::ptr_eq(&transaction1, &transaction2));
+}
@@ -0,0 +1,120 @@
+//! Test detecting unused parameters and overrides with `ModuleBuilder::strict`
+
+use shaku::{
+    module, BuildError, Component, HasComponent, HasProvider, Interface, Provider, UnusedEntry,
+};
+
+trait Greeter: Interface {
+    fn greet(&self) -> String;
+}
+trait Cache: Interface {}
+trait Request {}
+
+#[derive(Component)]
+#[shaku(interface = Greeter)]
+struct GreeterImpl {
+    #[shaku(default = "hello".to_string())]
+    greeting: String,
+}
+impl Greeter for GreeterImpl {
+    fn greet(&self) -> String {
+        self.greeting.clone()
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Cache)]
+struct CacheImpl;
+impl Cache for CacheImpl {}
+
+#[derive(Provider)]
+#[shaku(interface = Request)]
+struct RequestImpl;
+impl Request for RequestImpl {}
+
+module! {
+    TestModule {
+        components = [GreeterImpl, #[lazy] CacheImpl],
+        providers = [RequestImpl]
+    }
+}
+
+struct FakeGreeter;
+impl Greeter for FakeGreeter {
+    fn greet(&self) -> String {
+        "fake".to_string()
+    }
+}
+
+/// Every entry is used, so the build succeeds
+#[test]
+fn used_entries() {
+    let module = TestModule::builder()
+        .with_component_parameters::<GreeterImpl>(GreeterImplParameters {
+            greeting: "hi".to_string(),
+        })
+        .with_provider_override::<dyn Request>(Box::new(|_| Ok(Box::new(RequestImpl))))
+        .strict()
+        .build();
+
+    let greeter: &dyn Greeter = module.resolve_ref();
+    assert_eq!(greeter.greet(), "hi");
+    assert!(module.provide().is_ok());
+}
+
+#[test]
+fn unused_entries() {
+    let result = TestModule::builder()
+        .with_component_parameters::<GreeterImpl>(GreeterImplParameters {
+            greeting: "hi".to_string(),
+        })
+        .with_component_override::<dyn Greeter>(Box::new(FakeGreeter))
+        .with_component_override_fn::<dyn Cache>(Box::new(|_| Box::new(CacheImpl)))
+        .strict()
+        .try_build();
+
+    match result {
+        Err(BuildError::UnusedEntries { entries }) => assert_eq!(
+            entries,
+            vec![
+                UnusedEntry::Parameters {
+                    component: "strict_builder::GreeterImpl",
+                    named: None,
+                },
+                UnusedEntry::ComponentOverride {
+                    interface: "dyn strict_builder::Cache",
+                    named: None,
+                },
+            ]
+        ),
+        Err(e) => panic!("Unexpected error: {}", e),
+        Ok(_) => panic!("Expected the build to fail"),
+    }
+}
+
+/// Unused entries are ignored unless the builder is strict
+#[test]
+fn not_strict() {
+    let module = TestModule::builder()
+        .with_component_parameters::<GreeterImpl>(GreeterImplParameters {
+            greeting: "hi".to_string(),
+        })
+        .with_component_override::<dyn Greeter>(Box::new(FakeGreeter))
+        .build();
+
+    let greeter: &dyn Greeter = module.resolve_ref();
+    assert_eq!(greeter.greet(), "fake");
+}
+
+#[test]
+#[should_panic(expected = "Unused module builder entries:\n\
+                           - parameters of `strict_builder::GreeterImpl`")]
+fn build_panics() {
+    TestModule::builder()
+        .with_component_parameters::<GreeterImpl>(GreeterImplParameters {
+            greeting: "hi".to_string(),
+        })
+        .with_component_override::<dyn Greeter>(Box::new(FakeGreeter))
+        .strict()
+        .build();
+}
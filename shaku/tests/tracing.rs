@@ -0,0 +1,189 @@
+//! Test the spans emitted by the `tracing` feature
+#![cfg(feature = "tracing")]
+
+use shaku::{module, Component, HasComponent, HasProvider, Interface, Module, Provider};
+use std::any::type_name;
+use std::error::Error;
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+trait Database: Interface {}
+trait Repository: Interface {}
+trait Mailer: Interface {}
+trait Connection {}
+
+#[derive(Component)]
+#[shaku(interface = Database)]
+struct DatabaseImpl;
+impl Database for DatabaseImpl {}
+
+#[derive(Component)]
+#[shaku(interface = Repository)]
+struct RepositoryImpl {
+    #[shaku(inject)]
+    #[allow(dead_code)]
+    database: Arc<dyn Database>,
+}
+impl Repository for RepositoryImpl {}
+
+#[derive(Component)]
+#[shaku(interface = Mailer)]
+struct MailerImpl;
+impl Mailer for MailerImpl {}
+
+struct ConnectionImpl;
+impl Connection for ConnectionImpl {}
+impl<M: Module> Provider<M> for ConnectionImpl {
+    type Interface = dyn Connection;
+
+    fn provide(_: &M) -> Result<Box<dyn Connection>, Box<dyn Error>> {
+        Err("connection refused".into())
+    }
+}
+
+module! {
+    TestModule {
+        components = [RepositoryImpl, DatabaseImpl, #[lazy] MailerImpl],
+        providers = [ConnectionImpl]
+    }
+}
+
+/// Records the spans which are entered and the error events, with the
+/// `component` or `provider` field of each span
+#[derive(Clone, Default)]
+struct Recorder {
+    spans: Arc<Mutex<Vec<String>>>,
+    log: Arc<Mutex<Vec<String>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+/// Finds the name of the traced service, and the error message
+#[derive(Default)]
+struct FieldVisitor {
+    service: Option<String>,
+    error: Option<String>,
+}
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if ["module", "component", "provider"].contains(&field.name()) {
+            self.service = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "error" {
+            self.error = Some(format!("{:?}", value));
+        }
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut visitor = FieldVisitor::default();
+        span.record(&mut visitor);
+        self.spans.lock().unwrap().push(format!(
+            "{} {}",
+            span.metadata().name(),
+            visitor.service.unwrap_or_default()
+        ));
+
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::SeqCst) + 1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        self.log
+            .lock()
+            .unwrap()
+            .push(format!("error {}", visitor.error.unwrap_or_default()));
+    }
+
+    fn enter(&self, span: &Id) {
+        let name = self.spans.lock().unwrap()[span.into_u64() as usize - 1].clone();
+        self.log.lock().unwrap().push(format!("enter {}", name));
+    }
+
+    fn exit(&self, span: &Id) {
+        let name = self.spans.lock().unwrap()[span.into_u64() as usize - 1].clone();
+        self.log.lock().unwrap().push(format!("exit {}", name));
+    }
+}
+
+#[test]
+fn module_build_spans() {
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || TestModule::builder().build());
+
+    let module = format!("shaku::build_module {}", type_name::<TestModule>());
+    let repository = format!("shaku::build_component {}", type_name::<RepositoryImpl>());
+    let database = format!("shaku::build_component {}", type_name::<DatabaseImpl>());
+    assert_eq!(
+        *recorder.log.lock().unwrap(),
+        vec![
+            format!("enter {}", module),
+            format!("enter {}", repository),
+            format!("enter {}", database),
+            format!("exit {}", database),
+            format!("exit {}", repository),
+            format!("exit {}", module),
+        ]
+    );
+}
+
+#[test]
+fn lazy_component_spans() {
+    let module = TestModule::builder().build();
+
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        let _: &dyn Mailer = module.resolve_ref();
+        let _: &dyn Mailer = module.resolve_ref();
+    });
+
+    let lazy = format!("shaku::lazy_component {}", type_name::<MailerImpl>());
+    let build = format!("shaku::build_component {}", type_name::<MailerImpl>());
+    assert_eq!(
+        *recorder.log.lock().unwrap(),
+        vec![
+            format!("enter {}", lazy),
+            format!("enter {}", build),
+            format!("exit {}", build),
+            format!("exit {}", lazy),
+        ]
+    );
+}
+
+#[test]
+fn provider_error_event() {
+    let module = TestModule::builder().build();
+
+    let recorder = Recorder::default();
+    let result = tracing::subscriber::with_default(recorder.clone(), || {
+        HasProvider::<dyn Connection>::provide(&module).map(|_| ())
+    });
+    assert!(result.is_err());
+
+    let provide = format!("shaku::provide {}", type_name::<ConnectionImpl>());
+    assert_eq!(
+        *recorder.log.lock().unwrap(),
+        vec![
+            format!("enter {}", provide),
+            "error connection refused".to_string(),
+            format!("exit {}", provide),
+        ]
+    );
+}
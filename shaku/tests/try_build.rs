@@ -0,0 +1,212 @@
+//! Test `ModuleBuilder::try_build`
+
+use shaku::{
+    module, BuildError, Component, HasComponent, Interface, Module, ModuleBuildContext,
+    ModuleBuilder, ModuleInstance,
+};
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::sync::Arc;
+
+trait Dependency: Interface {}
+trait Service: Interface {}
+
+#[derive(Debug)]
+struct InvalidPort(u16);
+
+impl Display for InvalidPort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid port: {}", self.0)
+    }
+}
+
+impl Error for InvalidPort {}
+
+/// A manually implemented component which fails to build for port 0
+struct DependencyImpl;
+impl Dependency for DependencyImpl {}
+
+impl<M: Module> Component<M> for DependencyImpl {
+    type Interface = dyn Dependency;
+    type Parameters = u16;
+
+    fn build(context: &mut ModuleBuildContext<M>, port: u16) -> Box<dyn Dependency> {
+        Self::try_build(context, port).unwrap()
+    }
+
+    fn try_build(
+        _: &mut ModuleBuildContext<M>,
+        port: u16,
+    ) -> Result<Box<dyn Dependency>, Box<dyn Error + Send + Sync>> {
+        if port == 0 {
+            return Err(Box::new(InvalidPort(port)));
+        }
+
+        Ok(Box::new(Self))
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Service)]
+struct ServiceImpl {
+    #[shaku(inject)]
+    #[allow(dead_code)]
+    dependency: Arc<dyn Dependency>,
+}
+impl Service for ServiceImpl {}
+
+module! {
+    TestModule {
+        components = [ServiceImpl, DependencyImpl],
+        providers = []
+    }
+}
+
+#[test]
+fn try_build_success() {
+    let module = TestModule::builder()
+        .with_component_parameters::<DependencyImpl>(8080)
+        .try_build();
+
+    assert!(module.is_ok());
+}
+
+/// The error of a dependency is returned, even though it was built as part of
+/// another component.
+#[test]
+fn try_build_component_error() {
+    let result = TestModule::builder()
+        .with_component_parameters::<DependencyImpl>(0)
+        .try_build();
+
+    match result {
+        Err(BuildError::ComponentBuild { component, source }) => {
+            assert_eq!(component, "try_build::DependencyImpl");
+            assert_eq!(source.to_string(), "Invalid port: 0");
+        }
+        Err(e) => panic!("Unexpected error: {}", e),
+        Ok(_) => panic!("Expected the build to fail"),
+    }
+}
+
+#[test]
+#[should_panic(expected = "Failed to build try_build::DependencyImpl: Invalid port: 0")]
+fn build_panics_on_component_error() {
+    TestModule::builder()
+        .with_component_parameters::<DependencyImpl>(0)
+        .build();
+}
+
+trait Component1Trait: Interface {}
+trait Component2Trait: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = Component1Trait)]
+struct Component1 {
+    #[shaku(inject)]
+    #[allow(dead_code)]
+    component2: Arc<dyn Component2Trait>,
+}
+impl Component1Trait for Component1 {}
+
+#[derive(Component)]
+#[shaku(interface = Component2Trait)]
+struct Component2 {
+    #[shaku(inject)]
+    #[allow(dead_code)]
+    component1: Arc<dyn Component1Trait>,
+}
+impl Component2Trait for Component2 {}
+
+/// A manually implemented module with a circular dependency (the module macro
+/// would catch this at compile time)
+struct CircularModule {
+    component1: Arc<dyn Component1Trait>,
+}
+impl Module for CircularModule {
+    type Submodules = ();
+
+    fn build(context: ModuleBuildContext<Self>) -> Self {
+        Self::try_build(context).unwrap()
+    }
+
+    fn try_build(mut context: ModuleBuildContext<Self>) -> Result<Self, BuildError> {
+        Ok(Self {
+            component1: Self::try_build_component(&mut context)?,
+        })
+    }
+}
+impl ModuleInstance for CircularModule {}
+impl HasComponent<dyn Component1Trait> for CircularModule {
+    fn build_component(context: &mut ModuleBuildContext<Self>) -> Arc<dyn Component1Trait> {
+        context.build_component::<Component1>()
+    }
+
+    fn try_build_component(
+        context: &mut ModuleBuildContext<Self>,
+    ) -> Result<Arc<dyn Component1Trait>, BuildError> {
+        context.try_build_component::<Component1>()
+    }
+
+    fn resolve(&self) -> Arc<dyn Component1Trait> {
+        Arc::clone(&self.component1)
+    }
+
+    fn resolve_ref(&self) -> &dyn Component1Trait {
+        Arc::as_ref(&self.component1)
+    }
+}
+impl HasComponent<dyn Component2Trait> for CircularModule {
+    fn build_component(context: &mut ModuleBuildContext<Self>) -> Arc<dyn Component2Trait> {
+        context.build_component::<Component2>()
+    }
+
+    fn try_build_component(
+        context: &mut ModuleBuildContext<Self>,
+    ) -> Result<Arc<dyn Component2Trait>, BuildError> {
+        context.try_build_component::<Component2>()
+    }
+
+    fn resolve(&self) -> Arc<dyn Component2Trait> {
+        unimplemented!()
+    }
+
+    fn resolve_ref(&self) -> &dyn Component2Trait {
+        unimplemented!()
+    }
+}
+
+#[test]
+fn try_build_circular_dependency() {
+    let result = ModuleBuilder::<CircularModule>::with_submodules(()).try_build();
+
+    match result {
+        Err(BuildError::CircularDependency(error)) => {
+            let components: Vec<&str> = error.chain().iter().map(|step| step.component).collect();
+            let interfaces: Vec<&str> = error.chain().iter().map(|step| step.interface).collect();
+            assert_eq!(error.interface(), "dyn try_build::Component1Trait");
+            assert_eq!(
+                components,
+                vec![
+                    "try_build::Component1",
+                    "try_build::Component2",
+                    "try_build::Component1"
+                ]
+            );
+            assert_eq!(
+                interfaces,
+                vec![
+                    "dyn try_build::Component1Trait",
+                    "dyn try_build::Component2Trait",
+                    "dyn try_build::Component1Trait"
+                ]
+            );
+            assert_eq!(
+                error.to_string(),
+                "Circular dependency detected: try_build::Component1 -> try_build::Component2 -> try_build::Component1"
+            );
+        }
+        Err(e) => panic!("Unexpected error: {}", e),
+        Ok(_) => panic!("Expected the build to fail"),
+    }
+}
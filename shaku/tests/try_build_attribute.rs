@@ -0,0 +1,133 @@
+//! Test the `#[shaku(try_build = ...)]` attribute
+
+use shaku::{module, BuildError, Component, HasComponent, Interface};
+use std::fs::File;
+use std::io;
+use std::sync::Arc;
+
+trait Config: Interface {
+    fn port(&self) -> u16;
+}
+trait Server: Interface {
+    fn port(&self) -> u16;
+}
+
+#[derive(Component)]
+#[shaku(interface = Config, try_build = ConfigImpl::validate)]
+struct ConfigImpl {
+    port: u16,
+}
+impl Config for ConfigImpl {
+    fn port(&self) -> u16 {
+        self.port
+    }
+}
+
+impl ConfigImpl {
+    fn validate(self) -> Result<Self, String> {
+        if self.port == 0 {
+            return Err("The port must not be zero".to_string());
+        }
+
+        Ok(self)
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = Server)]
+struct ServerImpl {
+    #[shaku(inject)]
+    config: Arc<dyn Config>,
+}
+impl Server for ServerImpl {
+    fn port(&self) -> u16 {
+        self.config.port()
+    }
+}
+
+/// The function can return any error type
+#[derive(Component)]
+#[shaku(interface = Config, try_build = open_config)]
+struct FileConfigImpl {
+    #[shaku(default)]
+    path: String,
+}
+impl Config for FileConfigImpl {
+    fn port(&self) -> u16 {
+        0
+    }
+}
+
+fn open_config(config: FileConfigImpl) -> Result<FileConfigImpl, io::Error> {
+    File::open(&config.path)?;
+    Ok(config)
+}
+
+module! {
+    TestModule {
+        components = [ConfigImpl, ServerImpl],
+        providers = []
+    }
+}
+
+module! {
+    FileTestModule {
+        components = [FileConfigImpl],
+        providers = []
+    }
+}
+
+#[test]
+fn try_build_success() {
+    let module = TestModule::builder()
+        .with_component_parameters::<ConfigImpl>(ConfigImplParameters { port: 8080 })
+        .try_build()
+        .unwrap();
+
+    let server: &dyn Server = module.resolve_ref();
+    assert_eq!(server.port(), 8080);
+}
+
+#[test]
+fn try_build_error_is_propagated() {
+    let result = TestModule::builder()
+        .with_component_parameters::<ConfigImpl>(ConfigImplParameters { port: 0 })
+        .try_build();
+
+    match result {
+        Err(BuildError::ComponentBuild { component, source }) => {
+            assert_eq!(component, "try_build_attribute::ConfigImpl");
+            assert_eq!(source.to_string(), "The port must not be zero");
+        }
+        Err(e) => panic!("Unexpected error: {}", e),
+        Ok(_) => panic!("Expected the build to fail"),
+    }
+}
+
+#[test]
+#[should_panic(
+    expected = "Failed to build try_build_attribute::ConfigImpl: The port must not be zero"
+)]
+fn build_panics_on_error() {
+    TestModule::builder()
+        .with_component_parameters::<ConfigImpl>(ConfigImplParameters { port: 0 })
+        .build();
+}
+
+#[test]
+fn try_build_io_error() {
+    let result = FileTestModule::builder()
+        .with_component_parameters::<FileConfigImpl>(FileConfigImplParameters {
+            path: "this/file/does/not/exist".to_string(),
+        })
+        .try_build();
+
+    match result {
+        Err(BuildError::ComponentBuild { source, .. }) => {
+            let error = source.downcast::<io::Error>().unwrap();
+            assert_eq!(error.kind(), io::ErrorKind::NotFound);
+        }
+        Err(e) => panic!("Unexpected error: {}", e),
+        Ok(_) => panic!("Expected the build to fail"),
+    }
+}
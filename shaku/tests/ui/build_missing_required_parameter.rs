@@ -0,0 +1,24 @@
+//! Building a module without setting a required parameter will fail to compile
+
+use shaku::{module, Component, Interface};
+
+trait ServiceTrait: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = ServiceTrait)]
+struct ServiceImpl {
+    #[allow(dead_code)]
+    url: String,
+}
+impl ServiceTrait for ServiceImpl {}
+
+module! {
+    TestModule {
+        components = [ServiceImpl],
+        providers = []
+    }
+}
+
+fn main() {
+    let _module = TestModule::builder().build();
+}
@@ -0,0 +1,20 @@
+//! Importing an interface from a DynamicModule which was not declared via
+//! dynamic_interfaces will fail to compile
+
+use shaku::{module, Interface};
+
+trait ServiceTrait: Interface {}
+
+module! {
+    TestModule {
+        components = [],
+        providers = [],
+
+        use shaku::DynamicModule {
+            components = [dyn ServiceTrait],
+            providers = []
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,29 @@
+//! A provided Arc property requires the provider to be scoped
+
+use shaku::{module, Interface, Provider};
+use std::sync::Arc;
+
+trait DependencyTrait: Interface {}
+trait ProviderTrait {}
+
+#[derive(Provider)]
+#[shaku(interface = DependencyTrait)]
+struct DependencyImpl;
+impl DependencyTrait for DependencyImpl {}
+
+#[derive(Provider)]
+#[shaku(interface = ProviderTrait)]
+struct ProviderImpl {
+    #[shaku(provide)]
+    dependency: Arc<dyn DependencyTrait>,
+}
+impl ProviderTrait for ProviderImpl {}
+
+module! {
+    TestModule {
+        components = [],
+        providers = [DependencyImpl, ProviderImpl]
+    }
+}
+
+fn main() {}
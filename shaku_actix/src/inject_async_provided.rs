@@ -0,0 +1,100 @@
+use crate::get_module_from_state;
+use actix_web::dev::Payload;
+use actix_web::error::ErrorInternalServerError;
+use actix_web::{Error, FromRequest, HttpRequest};
+use futures_util::future::{self, FutureExt, LocalBoxFuture};
+use shaku::{HasAsyncProvider, ModuleInterface};
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// Used to create a service from a shaku `Module` using an async provider.
+/// The module should be stored in Actix's app data, wrapped in an `Arc`.
+/// Use this struct as an extractor.
+///
+/// # Example
+/// ```rust
+/// use actix_web::{App, HttpServer, web};
+/// use shaku::{module, AsyncProvider};
+/// use shaku_actix::InjectAsyncProvided;
+/// use std::sync::Arc;
+///
+/// trait HelloWorld: Send + Sync {
+///     fn greet(&self) -> String;
+/// }
+///
+/// #[derive(AsyncProvider)]
+/// #[shaku(interface = HelloWorld)]
+/// struct HelloWorldImpl;
+///
+/// impl HelloWorld for HelloWorldImpl {
+///     fn greet(&self) -> String {
+///         "Hello, world!".to_owned()
+///     }
+/// }
+///
+/// module! {
+///     HelloModule {
+///         components = [],
+///         providers = [#[async] HelloWorldImpl]
+///     }
+/// }
+///
+/// async fn hello(hello_world: InjectAsyncProvided<HelloModule, dyn HelloWorld>) -> String {
+///     hello_world.greet()
+/// }
+///
+/// #[actix_web::main]
+/// async fn main() -> std::io::Result<()> {
+///     let module = Arc::new(HelloModule::builder().build());
+///
+/// # if false { // We don't actually want to launch the server in an example.
+///     HttpServer::new(move || {
+///         App::new()
+///             .app_data(module.clone())
+///             .route("/", web::get().to(hello))
+///     })
+///     .bind("127.0.0.1:8080")?
+///     .run()
+///     .await
+/// # } else { Ok(()) }
+/// }
+/// ```
+pub struct InjectAsyncProvided<M: ModuleInterface + HasAsyncProvider<I> + ?Sized, I: ?Sized>(
+    Box<I>,
+    PhantomData<M>,
+);
+
+impl<M: ModuleInterface + HasAsyncProvider<I> + ?Sized, I: ?Sized + 'static> FromRequest
+    for InjectAsyncProvided<M, I>
+{
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let module: Arc<M> = match get_module_from_state::<M>(req) {
+            Ok(module) => Arc::clone(module),
+            Err(e) => return future::err(e).boxed_local(),
+        };
+
+        async move {
+            let service = module
+                .provide_async()
+                .await
+                .map_err(ErrorInternalServerError)?;
+
+            Ok(InjectAsyncProvided(service, PhantomData))
+        }
+        .boxed_local()
+    }
+}
+
+impl<M: ModuleInterface + HasAsyncProvider<I> + ?Sized, I: ?Sized> Deref
+    for InjectAsyncProvided<M, I>
+{
+    type Target = I;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.deref()
+    }
+}
@@ -1,13 +1,16 @@
 //! This crate provides integration between the `shaku` and `actix-web` crates.
 //!
-//! See [`Inject`] and [`InjectProvided`] for details.
+//! See [`Inject`], [`InjectProvided`] and [`InjectAsyncProvided`] for details.
 //!
 //! [`Inject`]: struct.Inject.html
 //! [`InjectProvided`]: struct.InjectProvided.html
+//! [`InjectAsyncProvided`]: struct.InjectAsyncProvided.html
 
+mod inject_async_provided;
 mod inject_component;
 mod inject_provided;
 
+pub use inject_async_provided::InjectAsyncProvided;
 pub use inject_component::Inject;
 pub use inject_provided::InjectProvided;
 
@@ -16,9 +19,10 @@ use actix_web::{Error, HttpRequest};
 use shaku::ModuleInterface;
 use std::sync::Arc;
 
-fn get_module_from_state<M: ModuleInterface + ?Sized>(request: &HttpRequest) -> Result<&M, Error> {
+fn get_module_from_state<M: ModuleInterface + ?Sized>(
+    request: &HttpRequest,
+) -> Result<&Arc<M>, Error> {
     request
         .app_data::<Arc<M>>()
-        .map(Arc::as_ref)
         .ok_or_else(|| ErrorInternalServerError("Failed to retrieve module from state"))
 }
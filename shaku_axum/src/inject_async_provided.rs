@@ -0,0 +1,112 @@
+use axum::async_trait;
+use axum::extract::{FromRef, FromRequestParts};
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use shaku::{HasAsyncProvider, ModuleInterface};
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// Used to create a service from a shaku `Module` using an async provider.
+/// The module should be stored in Axum state, wrapped in an `Arc` (`Arc<MyModule>`).
+/// This `Arc<MyModule>` must implement `FromRef<S>` where `S` is the Axum state type.
+///
+/// Use this struct as an extractor.
+///
+/// # Example
+/// ```rust
+/// use axum::{routing::get, Router};
+/// use axum::extract::FromRef;
+/// use shaku::{module, AsyncProvider};
+/// use shaku_axum::InjectAsyncProvided;
+/// use std::sync::Arc;
+/// use tokio::net::TcpListener;
+///
+/// trait HelloWorld: Send + Sync {
+///     fn greet(&self) -> String;
+/// }
+///
+/// #[derive(AsyncProvider)]
+/// #[shaku(interface = HelloWorld)]
+/// struct HelloWorldImpl;
+///
+/// impl HelloWorld for HelloWorldImpl {
+///     fn greet(&self) -> String {
+///         "Hello, world!".to_owned()
+///     }
+/// }
+///
+/// module! {
+///     HelloModule {
+///         components = [],
+///         providers = [#[async] HelloWorldImpl]
+///     }
+/// }
+///
+/// #[derive(Clone)]
+/// struct AppState {
+///     module: Arc<HelloModule>,
+/// }
+///
+/// impl FromRef<AppState> for Arc<HelloModule> {
+///     fn from_ref(app_state: &AppState) -> Arc<HelloModule> {
+///         app_state.module.clone()
+///     }
+/// }
+///
+/// async fn hello(hello_world: InjectAsyncProvided<HelloModule, dyn HelloWorld>) -> String {
+///     hello_world.greet()
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let module = Arc::new(HelloModule::builder().build());
+///     let state = AppState { module };
+///
+///     let app = Router::new()
+///         .route("/", get(hello))
+///         .with_state(state);
+///
+///     # if false {
+///     let listener = TcpListener::bind("127.0.0.1:8080").await.unwrap();
+///     axum::serve(listener, app.into_make_service())
+///         .await
+///         .unwrap();
+///     }
+/// }
+/// ```
+pub struct InjectAsyncProvided<M: ModuleInterface + HasAsyncProvider<I> + ?Sized, I: ?Sized>(
+    Box<I>,
+    PhantomData<M>,
+);
+
+#[async_trait]
+impl<S, M, I> FromRequestParts<S> for InjectAsyncProvided<M, I>
+where
+    S: Send + Sync,
+    M: ModuleInterface + HasAsyncProvider<I> + ?Sized,
+    I: ?Sized,
+    Arc<M>: FromRef<S>,
+{
+    type Rejection = (StatusCode, String);
+
+    async fn from_request_parts(_req: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let module = Arc::<M>::from_ref(state);
+        let service = module
+            .provide_async()
+            .await
+            .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+        Ok(Self(service, PhantomData))
+    }
+}
+
+impl<M: ModuleInterface + HasAsyncProvider<I> + ?Sized, I: ?Sized> Deref
+    for InjectAsyncProvided<M, I>
+{
+    type Target = I;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.deref()
+    }
+}
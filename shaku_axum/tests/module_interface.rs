@@ -1,11 +1,15 @@
-//! Module interfaces can be used with `Inject` and `InjectProvided`.
+//! Module interfaces can be used with `Inject`, `InjectProvided` and `InjectAsyncProvided`.
 //! The module itself would be stored in state as `Arc<dyn MyModule>`.
 
-use shaku::{module, Component, HasComponent, HasProvider, Interface, Provider};
-use shaku_axum::{Inject, InjectProvided};
+use shaku::{
+    module, AsyncProvider, Component, HasAsyncProvider, HasComponent, HasProvider, Interface,
+    Provider,
+};
+use shaku_axum::{Inject, InjectAsyncProvided, InjectProvided};
 
 trait MyComponent: Interface {}
 trait MyProvider {}
+trait MyAsyncProvider: Send {}
 
 #[derive(Component)]
 #[shaku(interface = MyComponent)]
@@ -17,12 +21,20 @@ impl MyComponent for MyComponentImpl {}
 struct MyProviderImpl;
 impl MyProvider for MyProviderImpl {}
 
-trait MyModule: HasComponent<dyn MyComponent> + HasProvider<dyn MyProvider> {}
+#[derive(AsyncProvider)]
+#[shaku(interface = MyAsyncProvider)]
+struct MyAsyncProviderImpl;
+impl MyAsyncProvider for MyAsyncProviderImpl {}
+
+trait MyModule:
+    HasComponent<dyn MyComponent> + HasProvider<dyn MyProvider> + HasAsyncProvider<dyn MyAsyncProvider>
+{
+}
 
 module! {
     MyModuleImpl: MyModule {
         components = [MyComponentImpl],
-        providers = [MyProviderImpl]
+        providers = [MyProviderImpl, #[async] MyAsyncProviderImpl]
     }
 }
 
@@ -30,6 +42,7 @@ module! {
 async fn index(
     _component: Inject<dyn MyModule, dyn MyComponent>,
     _provider: InjectProvided<dyn MyModule, dyn MyProvider>,
+    _async_provider: InjectAsyncProvided<dyn MyModule, dyn MyAsyncProvider>,
 ) {
 }
 
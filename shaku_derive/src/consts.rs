@@ -3,4 +3,11 @@ pub const INTERFACE_ATTR_NAME: &str = "interface";
 pub const INJECT_ATTR_NAME: &str = "inject";
 pub const PROVIDE_ATTR_NAME: &str = "provide";
 pub const DEFAULT_ATTR_NAME: &str = "default";
+pub const NAMED_ATTR_NAME: &str = "named";
+pub const ENV_ATTR_NAME: &str = "env";
+pub const TRY_BUILD_ATTR_NAME: &str = "try_build";
+pub const VALIDATE_ATTR_NAME: &str = "validate";
+pub const MOCK_ATTR_NAME: &str = "mock";
+pub const ON_START_ATTR_NAME: &str = "on_start";
+pub const ON_STOP_ATTR_NAME: &str = "on_stop";
 pub const DEBUG_ENV_VAR: &str = "SHAKU_CODEGEN_DEBUG";
@@ -22,6 +22,24 @@ pub fn component(input: TokenStream) -> TokenStream {
         .into()
 }
 
+#[proc_macro_derive(AsyncComponent, attributes(shaku))]
+pub fn async_component(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+    macros::async_component::expand_derive_async_component(&input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(AsyncProvider, attributes(shaku))]
+pub fn async_provider(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+    macros::async_provider::expand_derive_async_provider(&input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
 #[proc_macro_derive(Provider, attributes(shaku))]
 pub fn provider(input: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(input as syn::DeriveInput);
@@ -81,7 +99,10 @@ pub fn provider(input: TokenStream) -> TokenStream {
 /// "overflow evaluating the requirement `TestModule: HasComponent<(dyn Component1Trait + 'static)>`".
 ///
 /// It is still possible to compile with a circular dependency if the module is manually implemented
-/// in a certain way. In that case, there will be a panic during module creation with more details.
+/// in a certain way. In that case, there will be a panic during module creation with more details,
+/// or a `BuildError::CircularDependency` if the module is created via
+/// [`ModuleBuilder::try_build`]. The error lists the components in the cycle, for example
+/// `Circular dependency detected: A -> B -> A`.
 ///
 /// ## Lazy Components
 /// Components can be lazily created by annotating them with `#[lazy]` in the module declaration.
@@ -107,6 +128,70 @@ pub fn provider(input: TokenStream) -> TokenStream {
 /// # fn main() {}
 /// ```
 ///
+/// ## Named Components
+/// Multiple components of the same interface can be added by annotating them with
+/// `#[named(Tag)]`, for example `components = [DatabaseImpl, #[named(Replica)] ReplicaDatabase]`.
+/// The module will implement `HasNamedComponent<dyn Database, Replica>` for them instead of
+/// `HasComponent`. Named components from submodules are annotated the same way. Named components
+/// can be lazy, but not async.
+///
+/// ## Multi-bound Components
+/// Components which contribute to a set are annotated with the set's interface, for example
+/// `components = [#[multi(dyn Listener)] AuditListener, #[multi(dyn Listener)] EmailListener]`.
+/// The module will implement `HasComponents<dyn Listener>` once for the whole set, instead of
+/// `HasComponent` for each component. Submodule sets are imported with `#[multi] dyn Listener`,
+/// and are appended to the module's own set. Multi-bound components cannot be lazy, async, or
+/// named.
+///
+/// ## Keyed Components
+/// Multi-bound components which also have a key are added to a map instead of a set, for example
+/// `components = [#[multi(dyn Command)] #[key("export")] ExportCommand]`. The module will
+/// implement `HasComponentMap<dyn Command>` once for the whole map. Keys must be unique within the
+/// module. Submodule maps are imported with `#[key] dyn Command`, and a key which is also used by a
+/// submodule fails the module build with `BuildError::DuplicateKey`.
+///
+/// ## Reloadable Components
+/// Components annotated with `#[reloadable]`, for example `components = [#[reloadable]
+/// CredentialsImpl]`, can be replaced after the module is built via `Module::replace`. The module
+/// will also implement `HasReloadableComponent` for them. `resolve` and `resolve_ref` return the
+/// latest instance, but components which were injected with the old instance keep it. Reloadable
+/// components cannot be lazy, async, named, or multi-bound. Reloadable submodule components are
+/// annotated the same way, and replacing them replaces them in the submodule.
+///
+/// ## Configuration
+/// When the `serde` feature of shaku is enabled, the module will also implement
+/// `ConfigurableModule` if the parameters of all its components implement `Deserialize`, so the
+/// parameters can be set via `ModuleBuilder::with_parameters_from`. Components are named by the
+/// last segment of their type, followed by the tag of named components (ex.
+/// `"DatabaseImpl(Replica)"`).
+///
+/// ## Lifecycle Hooks
+/// The generated `Module::shutdown` runs the `#[shaku(on_stop = ...)]` hooks of the module's
+/// components in reverse build order, then shuts down the submodules which components are imported
+/// from. A lazy component's hooks are only registered once it is built.
+///
+/// ## Async Components
+/// Components which implement `AsyncComponent` (requires the `async` feature of shaku) are
+/// annotated with `#[async]`, for example `components = [#[async] DatabaseImpl]`. The module must
+/// then be built with `ModuleBuilder::build_async`. Lazy async components (`#[lazy] #[async]`) are
+/// built the first time `HasComponent::resolve_async` is called.
+///
+/// Providers which implement `AsyncProvider` are also annotated with `#[async]`, for example
+/// `providers = [#[async] TransactionProvider]`. The module will implement `HasAsyncProvider` for
+/// them instead of `HasProvider`. Async providers from submodules are annotated the same way.
+///
+/// ## Scoped Providers
+/// Providers annotated with `#[scoped]`, for example `providers = [#[scoped] TransactionImpl]`,
+/// are cached by the `Scope` returned from `Module::create_scope`. The module will also implement
+/// `HasScopedProvider` for them. Scoped providers cannot be async, and submodule providers cannot
+/// be scoped.
+///
+/// ## Dependency Graph
+/// The generated `Module::dependency_graph` lists the module's components and providers (with
+/// their interfaces and whether they are lazy) and the dependencies declared by their
+/// `dependencies` functions, which the derive macros generate. The graphs of submodules which
+/// services are imported from are included, with the submodule as the owning module.
+///
 /// # Examples
 /// ```
 /// use shaku::{module, Component, Interface, HasComponent};
@@ -144,6 +229,7 @@ pub fn provider(input: TokenStream) -> TokenStream {
 /// ```
 ///
 /// [`Module`]: trait.Module.html
+/// [`ModuleBuilder::try_build`]: struct.ModuleBuilder.html#method.try_build
 /// [`ModuleInterface`]: trait.ModuleInterface.html
 /// [submodules getting started guide]: guide/submodules/index.html
 #[proc_macro]
@@ -154,3 +240,108 @@ pub fn module(input: TokenStream) -> TokenStream {
         .unwrap_or_else(|e| e.to_compile_error())
         .into()
 }
+
+/// Generate a proxy for an interface trait, which runs an [`Interceptor`] around each method call.
+///
+/// For a trait `MyService`, a `MyServiceProxy<I: Interceptor>` struct is generated with the same
+/// visibility as the trait. It wraps an `Arc<dyn MyService>` and implements `MyService` by
+/// delegating every method to it. The interceptor is called with the method name and the
+/// arguments before the call, and with the elapsed time after it. Methods which return a `Result`
+/// call [`Interceptor::error`] instead of [`Interceptor::after`] when they return an error.
+///
+/// Proxies are typically installed via [`ModuleBuilder::with_component_decorator`].
+///
+/// ## Requirements
+/// - The arguments of every method must implement `Debug`, and so must the error type of methods
+///   which return a `Result`.
+/// - Every method must take `&self`. Methods with a `where Self: Sized` bound are skipped if they
+///   have a default implementation.
+/// - Generic traits, generic methods, async methods, and associated types and consts are not
+///   supported.
+/// - Supertraits (other than [`Interface`]) are not implemented for the proxy.
+///
+/// ## Mocks
+/// With the `mock` feature of shaku enabled, `#[shaku::interface(mock)]` also generates a
+/// `MockMyService` struct for tests. Set the behavior of each method with `expect_<method>`, which
+/// takes a closure with the method's arguments. Calls are recorded with their arguments' `Debug`
+/// representations, and can be checked via `calls` and `calls_to`. Clones of a mock share its
+/// expectations and calls, so `boxed` (for [`ModuleBuilder::with_component_override`]) and
+/// `provider` (for [`ModuleBuilder::with_provider_override`]) hand out clones while the test
+/// keeps the original:
+///
+/// ```ignore
+/// let mock = MockGreeter::new().expect_greet(|name| format!("Hi, {}", name));
+/// let module = MyModule::builder()
+///     .with_component_override::<dyn Greeter>(mock.boxed())
+///     .build();
+///
+/// // ...
+/// assert_eq!(mock.calls_to("greet").len(), 1);
+/// ```
+///
+/// Calling a method without an expectation panics. Mocked methods can't return references with
+/// elided lifetimes, since the expectation can't borrow from the mock.
+///
+/// ```rust
+/// use shaku::{module, Component, HasComponent, Interceptor, Interface, MethodCall};
+/// use std::fmt::Debug;
+///
+/// #[shaku::interface]
+/// trait Greeter: Interface {
+///     fn greet(&self, name: &str) -> String;
+/// }
+///
+/// #[derive(Component)]
+/// #[shaku(interface = Greeter)]
+/// struct GreeterImpl;
+/// impl Greeter for GreeterImpl {
+///     fn greet(&self, name: &str) -> String {
+///         format!("Hello, {}!", name)
+///     }
+/// }
+///
+/// struct Logging;
+/// impl Interceptor for Logging {
+///     fn before(&self, call: &MethodCall, arguments: &[&dyn Debug]) {
+///         println!("Calling {} with {:?}", call.method, arguments);
+///     }
+/// }
+///
+/// module! {
+///     MyModule {
+///         components = [GreeterImpl],
+///         providers = []
+///     }
+/// }
+///
+/// # fn main() {
+/// let module = MyModule::builder()
+///     .with_component_decorator::<dyn Greeter>(Box::new(|inner, _| {
+///         Box::new(GreeterProxy::new(inner, Logging))
+///     }))
+///     .build();
+///
+/// let greeter: &dyn Greeter = module.resolve_ref();
+/// assert_eq!(greeter.greet("world"), "Hello, world!");
+/// # }
+/// ```
+///
+/// [`Interceptor`]: trait.Interceptor.html
+/// [`Interceptor::error`]: trait.Interceptor.html#method.error
+/// [`Interceptor::after`]: trait.Interceptor.html#method.after
+/// [`Interface`]: trait.Interface.html
+/// [`ModuleBuilder::with_component_decorator`]: struct.ModuleBuilder.html#method.with_component_decorator
+/// [`ModuleBuilder::with_component_override`]: struct.ModuleBuilder.html#method.with_component_override
+/// [`ModuleBuilder::with_provider_override`]: struct.ModuleBuilder.html#method.with_provider_override
+#[proc_macro_attribute]
+pub fn interface(args: TokenStream, input: TokenStream) -> TokenStream {
+    let item = syn::parse_macro_input!(input as syn::ItemTrait);
+
+    // Keep the trait on errors, so its uses don't cause more errors
+    macros::interface::expand_interface_attribute(args.into(), item.clone())
+        .unwrap_or_else(|e| {
+            let error = e.to_compile_error();
+            quote! { #item #error }
+        })
+        .into()
+}
@@ -1,6 +1,9 @@
 //! Implementations of the proc macros
 
+pub mod async_component;
+pub mod async_provider;
 mod common_output;
 pub mod component;
+pub mod interface;
 pub mod module;
 pub mod provider;
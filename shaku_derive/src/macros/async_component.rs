@@ -0,0 +1,161 @@
+//! Implementation of the `#[derive(AsyncComponent)]` procedural macro
+
+use crate::debug::get_debug_level;
+use crate::macros::common_output::{
+    create_default_parameters_fn, create_dependencies_fn, create_dependency,
+    create_lifecycle_hooks, create_parameters_struct, create_required_parameters,
+    create_required_parameters_impl, create_validate_parameters_fn,
+};
+use crate::structures::service::{Property, PropertyType, ServiceData};
+use proc_macro2::TokenStream;
+use syn::DeriveInput;
+
+pub fn expand_derive_async_component(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let service = ServiceData::from_derive_input(input)?;
+
+    let debug_level = get_debug_level();
+    if debug_level > 1 {
+        println!(
+            "Service data parsed from AsyncComponent input: {:#?}",
+            service
+        );
+    }
+
+    let resolve_properties: Vec<TokenStream> = service
+        .properties
+        .iter()
+        .map(create_resolve_property)
+        .collect();
+
+    let required_parameters = create_required_parameters(&service);
+
+    let dependencies: Vec<TokenStream> = service
+        .properties
+        .iter()
+        .filter_map(create_dependency)
+        .collect();
+
+    let dependencies_fn = create_dependencies_fn(&service);
+    let default_parameters_fn = create_default_parameters_fn(&service);
+    let validate_parameters_fn = create_validate_parameters_fn(&service);
+    let parameters_struct = create_parameters_struct(&service);
+    let required_parameters_impl = create_required_parameters_impl(&service);
+
+    // The try_build function is async for async components
+    let try_build = service.metadata.try_build.as_ref().map(|try_build| {
+        quote! {
+            let component = #try_build(component).await?;
+        }
+    });
+
+    // Only override build_shared_async if there are lifecycle hooks
+    let build_shared_async = create_lifecycle_hooks(&service.metadata).map(|lifecycle_hooks| {
+        quote! {
+            fn build_shared_async(
+                context: &mut ::shaku::ModuleBuildContext<M>,
+                params: Self::Parameters,
+            ) -> ::shaku::BoxFuture<'_, ::std::result::Result<
+                ::std::sync::Arc<Self::Interface>,
+                Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync>
+            >> {
+                Box::pin(async move {
+                    let component = Self {
+                        #(#resolve_properties),*
+                    };
+                    #try_build
+                    let component = ::std::sync::Arc::new(component);
+                    let lifecycle = context.lifecycle();
+                    #lifecycle_hooks
+
+                    ::std::result::Result::Ok::<
+                        ::std::sync::Arc<Self::Interface>,
+                        Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync>
+                    >(component)
+                })
+            }
+        }
+    });
+
+    // AsyncComponent implementation
+    let component_name = service.metadata.identifier;
+    let parameters_name = format_ident!("{}Parameters", component_name);
+    let interface = service.metadata.interface;
+    let (_, generic_tys, generic_where) = service.metadata.generics.split_for_impl();
+    let generic_impls_no_parens = &service.metadata.generics.params;
+    let output = quote! {
+        impl<
+            M: ::shaku::Module #(+ #dependencies)*,
+            #generic_impls_no_parens
+        > ::shaku::AsyncComponent<M> for #component_name #generic_tys #generic_where {
+            type Interface = dyn #interface;
+            type Parameters = #parameters_name #generic_tys;
+
+            const REQUIRED_PARAMETERS: &'static [&'static str] = &[#(#required_parameters),*];
+
+            #default_parameters_fn
+
+            #validate_parameters_fn
+
+            fn build_async(
+                context: &mut ::shaku::ModuleBuildContext<M>,
+                params: Self::Parameters,
+            ) -> ::shaku::BoxFuture<'_, ::std::result::Result<
+                Box<Self::Interface>,
+                Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync>
+            >> {
+                Box::pin(async move {
+                    let component = Self {
+                        #(#resolve_properties),*
+                    };
+                    #try_build
+
+                    ::std::result::Result::Ok::<
+                        Box<Self::Interface>,
+                        Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync>
+                    >(Box::new(component))
+                })
+            }
+
+            #build_shared_async
+
+            #dependencies_fn
+        }
+
+        #parameters_struct
+        #required_parameters_impl
+    };
+
+    if debug_level > 0 {
+        println!("{}", output);
+    }
+
+    Ok(output)
+}
+
+fn create_resolve_property(property: &Property) -> TokenStream {
+    let property_name = &property.property_name;
+    let property_ty = &property.ty;
+
+    // Named, multi-bound, and keyed components are never async, so they are built synchronously
+    if let PropertyType::Components = property.property_type {
+        quote! {
+            #property_name: M::try_build_components(context)?
+        }
+    } else if let PropertyType::ComponentMap = property.property_type {
+        quote! {
+            #property_name: M::try_build_component_map(context)?
+        }
+    } else if let Some(tag) = &property.named {
+        quote! {
+            #property_name: <M as ::shaku::HasNamedComponent<#property_ty, #tag>>::try_build_named_component(context)?
+        }
+    } else if property.is_service() {
+        quote! {
+            #property_name: M::build_component_async(context).await?
+        }
+    } else {
+        quote! {
+            #property_name: params.#property_name
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! Implementation of the `#[derive(AsyncProvider)]` procedural macro
+
+use crate::debug::get_debug_level;
+use crate::macros::common_output::{
+    check_provider_metadata, create_component_dependency, create_dependencies_fn,
+    create_resolve_component,
+};
+use crate::structures::service::{Property, PropertyType, ServiceData};
+use proc_macro2::TokenStream;
+use syn::{DeriveInput, Error};
+
+pub fn expand_derive_async_provider(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let service = ServiceData::from_derive_input(input)?;
+
+    let debug_level = get_debug_level();
+    if debug_level > 1 {
+        println!(
+            "Service data parsed from AsyncProvider input: {:#?}",
+            service
+        );
+    }
+
+    check_provider_metadata(&service.metadata)?;
+
+    let resolve_properties: Vec<TokenStream> = service
+        .properties
+        .iter()
+        .map(create_property_assignment)
+        .collect::<Result<_, _>>()?;
+
+    let dependencies: Vec<TokenStream> = service
+        .properties
+        .iter()
+        .filter_map(create_dependency)
+        .collect();
+
+    let dependencies_fn = create_dependencies_fn(&service);
+
+    // AsyncProvider implementation
+    let provider_name = service.metadata.identifier;
+    let interface = service.metadata.interface;
+    let (_, generic_tys, generic_where) = service.metadata.generics.split_for_impl();
+    let generic_impls_no_parens = &service.metadata.generics.params;
+    let output = quote! {
+        impl<
+            M: ::shaku::Module #(+ #dependencies)*,
+            #generic_impls_no_parens
+        > ::shaku::AsyncProvider<M> for #provider_name #generic_tys #generic_where {
+            type Interface = dyn #interface;
+
+            fn provide_async(module: &M) -> ::shaku::BoxFuture<'_, ::std::result::Result<
+                Box<Self::Interface>,
+                Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync>
+            >> {
+                Box::pin(async move {
+                    ::std::result::Result::Ok::<
+                        Box<Self::Interface>,
+                        Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync>
+                    >(Box::new(Self {
+                        #(#resolve_properties),*
+                    }))
+                })
+            }
+
+            #dependencies_fn
+        }
+    };
+
+    if debug_level > 0 {
+        println!("{}", output);
+    }
+
+    Ok(output)
+}
+
+/// Async providers depend on other async providers
+fn create_dependency(property: &Property) -> Option<TokenStream> {
+    let property_ty = &property.ty;
+
+    match property.property_type {
+        PropertyType::Parameter => None,
+        PropertyType::Component | PropertyType::Components | PropertyType::ComponentMap => {
+            Some(create_component_dependency(property))
+        }
+        PropertyType::Provided => Some(quote! {
+            ::shaku::HasAsyncProvider<#property_ty>
+        }),
+        // Rejected in create_property_assignment
+        PropertyType::Scoped => None,
+    }
+}
+
+fn create_property_assignment(property: &Property) -> syn::Result<TokenStream> {
+    let property_name = &property.property_name;
+
+    match property.property_type {
+        PropertyType::Component | PropertyType::Components | PropertyType::ComponentMap => {
+            let resolve_component = create_resolve_component(property);
+            Ok(quote! {
+                #property_name: #resolve_component
+            })
+        }
+        PropertyType::Provided => Ok(quote! {
+            #property_name: module.provide_async().await?
+        }),
+        PropertyType::Scoped => Err(Error::new(
+            property.property_name.span(),
+            "Scoped services cannot be provided to async providers",
+        )),
+        PropertyType::Parameter => Err(Error::new(
+            property.property_name.span(),
+            "Parameters are not allowed in Providers",
+        )),
+    }
+}
@@ -1,18 +1,499 @@
 //! Functions which create common tokenstream outputs
 
-use crate::structures::service::{Property, PropertyType};
+use crate::structures::service::{MetaData, Property, PropertyDefault, PropertyType, ServiceData};
 use proc_macro2::TokenStream;
+use syn::spanned::Spanned;
+use syn::{Error, Ident, Visibility};
+
+/// Make sure a provider does not use component-only attributes
+pub fn check_provider_metadata(metadata: &MetaData) -> syn::Result<()> {
+    if let Some(try_build) = &metadata.try_build {
+        return Err(Error::new(
+            try_build.span(),
+            "Providers cannot use try_build, since they are already fallible",
+        ));
+    }
+
+    if let Some(validate) = &metadata.validate {
+        return Err(Error::new(
+            validate.span(),
+            "Providers cannot validate parameters, since they don't have any",
+        ));
+    }
+
+    if let Some(hook) = metadata.on_start.as_ref().or(metadata.on_stop.as_ref()) {
+        return Err(Error::new(
+            hook.span(),
+            "Providers cannot have lifecycle hooks, since they are not owned by the module",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Create the lifecycle hook calls of a component, if it has any. The
+/// generated code expects the built component to be in an `Arc` named
+/// `component`, and the module's `Lifecycle` to be named `lifecycle`.
+pub fn create_lifecycle_hooks(metadata: &MetaData) -> Option<TokenStream> {
+    if metadata.on_start.is_none() && metadata.on_stop.is_none() {
+        return None;
+    }
+
+    let on_start = metadata.on_start.as_ref().map(|on_start| {
+        quote! {
+            #on_start(&*component);
+        }
+    });
+
+    let on_stop = metadata.on_stop.as_ref().map(|on_stop| {
+        quote! {
+            let stop_component = ::std::sync::Arc::clone(&component);
+            lifecycle.add_stop_hook(Box::new(move || #on_stop(&*stop_component)));
+        }
+    });
+
+    Some(quote! {
+        #on_start
+        #on_stop
+    })
+}
 
 pub fn create_dependency(property: &Property) -> Option<TokenStream> {
     let property_ty = &property.ty;
 
     match property.property_type {
         PropertyType::Parameter => None,
-        PropertyType::Component => Some(quote! {
-            ::shaku::HasComponent<#property_ty>
-        }),
+        PropertyType::Component | PropertyType::Components | PropertyType::ComponentMap => {
+            Some(create_component_dependency(property))
+        }
         PropertyType::Provided => Some(quote! {
             ::shaku::HasProvider<#property_ty>
         }),
+        PropertyType::Scoped => Some(quote! {
+            ::shaku::HasScopedProvider<#property_ty>
+        }),
+    }
+}
+
+/// Create the `HasComponent` (or `HasNamedComponent`, `HasComponents`,
+/// `HasComponentMap`) bound of an injected component
+pub fn create_component_dependency(property: &Property) -> TokenStream {
+    let property_ty = &property.ty;
+
+    match property.property_type {
+        PropertyType::Components => {
+            return quote! {
+                ::shaku::HasComponents<#property_ty>
+            }
+        }
+        PropertyType::ComponentMap => {
+            return quote! {
+                ::shaku::HasComponentMap<#property_ty>
+            }
+        }
+        _ => {}
+    }
+
+    match &property.named {
+        Some(tag) => quote! {
+            ::shaku::HasNamedComponent<#property_ty, #tag>
+        },
+        None => quote! {
+            ::shaku::HasComponent<#property_ty>
+        },
+    }
+}
+
+/// Create the `dependencies` function of a service, which lists its injected
+/// and provided properties for the module's dependency graph
+pub fn create_dependencies_fn(service: &ServiceData) -> TokenStream {
+    let dependencies: Vec<TokenStream> = service
+        .properties
+        .iter()
+        .filter_map(|property| {
+            let property_ty = &property.ty;
+
+            match property.property_type {
+                PropertyType::Parameter => None,
+                PropertyType::Component | PropertyType::Components | PropertyType::ComponentMap => {
+                    match &property.named {
+                        Some(tag) => Some(quote! {
+                            ::shaku::Dependency::inject_named::<#property_ty, #tag>()
+                        }),
+                        None => Some(quote! { ::shaku::Dependency::inject::<#property_ty>() }),
+                    }
+                }
+                PropertyType::Provided | PropertyType::Scoped => {
+                    Some(quote! { ::shaku::Dependency::provide::<#property_ty>() })
+                }
+            }
+        })
+        .collect();
+
+    quote! {
+        fn dependencies() -> ::std::vec::Vec<::shaku::Dependency> {
+            vec![#(#dependencies),*]
+        }
+    }
+}
+
+/// Create the expression which resolves an injected component from a built
+/// module named `module`. Used by providers.
+pub fn create_resolve_component(property: &Property) -> TokenStream {
+    let property_ty = &property.ty;
+
+    match property.property_type {
+        PropertyType::Components => {
+            return quote! {
+                module.resolve_all()
+            }
+        }
+        PropertyType::ComponentMap => {
+            return quote! {
+                module.resolve_map()
+            }
+        }
+        _ => {}
+    }
+
+    match &property.named {
+        Some(tag) => quote! {
+            ::shaku::HasNamedComponent::<#property_ty, #tag>::resolve_named(module)
+        },
+        None => quote! {
+            module.resolve()
+        },
+    }
+}
+
+/// Create the names of the parameters which do not have a default value
+pub fn create_required_parameters(service: &ServiceData) -> Vec<String> {
+    service
+        .properties
+        .iter()
+        .filter(|property| property.is_required_parameter())
+        .map(|property| property.property_name.to_string())
+        .collect()
+}
+
+/// Create the `validate_parameters` function of a component, if it has
+/// `#[shaku(validate = ...)]` parameters or parameters struct. Each function
+/// returns a `Result`, and its error is reported as the violation message.
+pub fn create_validate_parameters_fn(service: &ServiceData) -> Option<TokenStream> {
+    let property_checks: Vec<TokenStream> = service
+        .properties
+        .iter()
+        .filter_map(|property| {
+            let validate = property.validate.as_ref()?;
+            let property_name = &property.property_name;
+            let parameter = property_name.to_string();
+
+            Some(quote! {
+                if let Err(error) = #validate(&params.#property_name) {
+                    violations.push(::shaku::ParameterViolation {
+                        component: ::std::any::type_name::<Self>(),
+                        parameter: Some(#parameter),
+                        message: ::std::string::ToString::to_string(&error),
+                    });
+                }
+            })
+        })
+        .collect();
+    let struct_check = service.metadata.validate.as_ref().map(|validate| {
+        quote! {
+            if let Err(error) = #validate(params) {
+                violations.push(::shaku::ParameterViolation {
+                    component: ::std::any::type_name::<Self>(),
+                    parameter: None,
+                    message: ::std::string::ToString::to_string(&error),
+                });
+            }
+        }
+    });
+
+    if property_checks.is_empty() && struct_check.is_none() {
+        return None;
+    }
+
+    Some(quote! {
+        fn validate_parameters(params: &Self::Parameters) -> Vec<::shaku::ParameterViolation> {
+            let mut violations = Vec::new();
+            #(#property_checks)*
+            #struct_check
+            violations
+        }
+    })
+}
+
+/// Implement `RequiredParameters` for the component, if any of its
+/// parameters are required, so the module can't be built without setting them
+pub fn create_required_parameters_impl(service: &ServiceData) -> Option<TokenStream> {
+    if !service
+        .properties
+        .iter()
+        .any(|property| property.is_required_parameter())
+    {
+        return None;
+    }
+
+    let component_name = &service.metadata.identifier;
+    let (impl_generics, ty_generics, where_clause) = service.metadata.generics.split_for_impl();
+
+    Some(quote! {
+        impl #impl_generics ::shaku::__private::RequiredParameters for #component_name #ty_generics #where_clause {}
+    })
+}
+
+/// Create the `default_parameters` function of a component, if it has
+/// parameters which are read from environment variables
+pub fn create_default_parameters_fn(service: &ServiceData) -> Option<TokenStream> {
+    if !service
+        .properties
+        .iter()
+        .any(|property| property.env.is_some())
+    {
+        return None;
+    }
+
+    let parameters_name = format_ident!("{}Parameters", service.metadata.identifier);
+    let parameters: Vec<TokenStream> = service
+        .properties
+        .iter()
+        .filter(|property| !property.is_service())
+        .map(|property| {
+            let property_name = &property.property_name;
+            let parameter = property_name.to_string();
+            let default = match &property.default {
+                PropertyDefault::Provided(default_expr) => quote! { #default_expr },
+                PropertyDefault::NotProvided => quote! { Default::default() },
+                PropertyDefault::NoDefault => match &property.env {
+                    Some(variable) => quote! {
+                        return Err(::shaku::BuildError::MissingEnvVar {
+                            component: ::std::any::type_name::<Self>(),
+                            parameter: #parameter,
+                            variable: #variable,
+                        })
+                    },
+                    None => quote! {
+                        return Err(::shaku::BuildError::MissingParameter {
+                            component: ::std::any::type_name::<Self>(),
+                            parameter: #parameter,
+                        })
+                    },
+                },
+            };
+
+            match &property.env {
+                Some(variable) => quote! {
+                    #property_name: match ::shaku::env_parameter::<Self, _>(#variable, #parameter)? {
+                        Some(value) => value,
+                        None => #default,
+                    }
+                },
+                None => quote! {
+                    #property_name: #default
+                },
+            }
+        })
+        .collect();
+
+    Some(quote! {
+        fn default_parameters() -> ::std::result::Result<Self::Parameters, ::shaku::BuildError> {
+            Ok(#parameters_name {
+                #(#parameters),*
+            })
+        }
+    })
+}
+
+/// Create the `*Parameters` struct of a component, and its `Default` impl
+pub fn create_parameters_struct(service: &ServiceData) -> TokenStream {
+    let visibility = &service.metadata.visibility;
+    let parameters_properties: Vec<TokenStream> = service
+        .properties
+        .iter()
+        .filter_map(|property| create_parameters_property(property, service, visibility))
+        .collect();
+
+    let parameters_defaults: Vec<TokenStream> = service
+        .properties
+        .iter()
+        .filter_map(|property| create_parameters_default(property, &service.metadata.identifier))
+        .collect();
+
+    let component_name = &service.metadata.identifier;
+    let parameters_name = format_ident!("{}Parameters", component_name);
+    let parameters_doc = format!(" Parameters for {}", component_name);
+    let (generic_impls, generic_tys, generic_where) = service.metadata.generics.split_for_impl();
+    let parameters_serde = create_parameters_serde(service);
+    let parameters_serde_default_fns = create_parameters_serde_default_fns(service);
+
+    quote! {
+        #[doc = #parameters_doc]
+        #parameters_serde
+        #visibility struct #parameters_name #generic_impls #generic_where {
+            #(#parameters_properties),*
+        }
+
+        impl #generic_impls ::std::default::Default for #parameters_name #generic_tys #generic_where {
+            #[allow(unreachable_code)]
+            fn default() -> Self {
+                Self {
+                    #(#parameters_defaults),*
+                }
+            }
+        }
+
+        #parameters_serde_default_fns
+    }
+}
+
+fn create_parameters_property(
+    property: &Property,
+    service: &ServiceData,
+    vis: &Visibility,
+) -> Option<TokenStream> {
+    if property.is_service() {
+        return None;
+    }
+
+    let property_name = &property.property_name;
+    let property_type = &property.ty;
+    let doc_comment = &property.doc_comment;
+    let serde_default = create_parameters_serde_default(property, service);
+
+    Some(quote! {
+        #(#doc_comment)*
+        #serde_default
+        #vis #property_name: #property_type
+    })
+}
+
+fn create_parameters_default(property: &Property, component_ident: &Ident) -> Option<TokenStream> {
+    if property.is_service() {
+        return None;
+    }
+
+    let property_name = &property.property_name;
+
+    match &property.default {
+        PropertyDefault::Provided(default_expr) => Some(quote! {
+            #property_name: #default_expr
+        }),
+        PropertyDefault::NotProvided => Some(quote! {
+            #property_name: Default::default()
+        }),
+        PropertyDefault::NoDefault => {
+            let unreachable_msg = format!(
+                "There is no default value for `{}::{}`",
+                component_ident, property_name
+            );
+
+            Some(quote! {
+                #property_name: unreachable!(#unreachable_msg)
+            })
+        }
+    }
+}
+
+/// Create the `Deserialize` derive of the `*Parameters` struct. The derive is
+/// bounded on every parameter being deserializable, so components with other
+/// parameters still compile (but can't be configured from serde).
+#[cfg(feature = "serde")]
+fn create_parameters_serde(service: &ServiceData) -> TokenStream {
+    let bounds: Vec<String> = service
+        .properties
+        .iter()
+        .filter(|property| !property.is_service())
+        .map(|property| {
+            let property_type = &property.ty;
+            format!(
+                "{}: ::shaku::serde::Deserialize<'de>",
+                quote! { #property_type }
+            )
+        })
+        .collect();
+    let bounds = bounds.join(", ");
+
+    quote! {
+        #[derive(::shaku::serde::Deserialize)]
+        #[serde(crate = "::shaku::serde", bound(deserialize = #bounds))]
+    }
+}
+
+#[cfg(not(feature = "serde"))]
+fn create_parameters_serde(_service: &ServiceData) -> TokenStream {
+    TokenStream::new()
+}
+
+/// Create the functions which provide the `#[shaku(default = ...)]` values
+/// of the parameters when they are missing from the deserialized
+/// configuration
+#[cfg(feature = "serde")]
+fn create_parameters_serde_default_fns(service: &ServiceData) -> TokenStream {
+    let default_fns: Vec<TokenStream> = service
+        .properties
+        .iter()
+        .filter(|property| !property.is_service())
+        .filter_map(|property| match &property.default {
+            PropertyDefault::Provided(default_expr) => {
+                let property_type = &property.ty;
+                let default_fn = serde_default_fn(property);
+
+                Some(quote! {
+                    fn #default_fn() -> #property_type {
+                        #default_expr
+                    }
+                })
+            }
+            PropertyDefault::NotProvided | PropertyDefault::NoDefault => None,
+        })
+        .collect();
+
+    if default_fns.is_empty() {
+        return TokenStream::new();
     }
+
+    let parameters_name = format_ident!("{}Parameters", service.metadata.identifier);
+    let (generic_impls, generic_tys, generic_where) = service.metadata.generics.split_for_impl();
+
+    quote! {
+        impl #generic_impls #parameters_name #generic_tys #generic_where {
+            #(#default_fns)*
+        }
+    }
+}
+
+#[cfg(not(feature = "serde"))]
+fn create_parameters_serde_default_fns(_service: &ServiceData) -> TokenStream {
+    TokenStream::new()
+}
+
+/// Create the `#[serde(default)]` attribute of a parameter, if it has a
+/// default
+#[cfg(feature = "serde")]
+fn create_parameters_serde_default(property: &Property, service: &ServiceData) -> TokenStream {
+    match &property.default {
+        PropertyDefault::Provided(_) => {
+            let parameters_name = format_ident!("{}Parameters", service.metadata.identifier);
+            let (_, generic_tys, _) = service.metadata.generics.split_for_impl();
+            let turbofish = generic_tys.as_turbofish();
+            let default_fn = serde_default_fn(property);
+            let default_path = quote! { #parameters_name #turbofish :: #default_fn }.to_string();
+
+            quote! { #[serde(default = #default_path)] }
+        }
+        PropertyDefault::NotProvided => quote! { #[serde(default)] },
+        PropertyDefault::NoDefault => TokenStream::new(),
+    }
+}
+
+#[cfg(not(feature = "serde"))]
+fn create_parameters_serde_default(_property: &Property, _service: &ServiceData) -> TokenStream {
+    TokenStream::new()
+}
+
+#[cfg(feature = "serde")]
+fn serde_default_fn(property: &Property) -> Ident {
+    format_ident!("__shaku_default_{}", property.property_name)
 }
@@ -1,10 +1,14 @@
 //! Implementation of the `#[derive(Component)]` procedural macro
 
 use crate::debug::get_debug_level;
-use crate::macros::common_output::create_dependency;
-use crate::structures::service::{Property, PropertyDefault, ServiceData};
+use crate::macros::common_output::{
+    create_default_parameters_fn, create_dependencies_fn, create_dependency,
+    create_lifecycle_hooks, create_parameters_struct, create_required_parameters,
+    create_required_parameters_impl, create_validate_parameters_fn,
+};
+use crate::structures::service::{Property, PropertyType, ServiceData};
 use proc_macro2::TokenStream;
-use syn::{DeriveInput, Ident, Visibility};
+use syn::DeriveInput;
 
 pub fn expand_derive_component(input: &DeriveInput) -> syn::Result<TokenStream> {
     let service = ServiceData::from_derive_input(input)?;
@@ -20,31 +24,87 @@ pub fn expand_derive_component(input: &DeriveInput) -> syn::Result<TokenStream>
         .map(create_resolve_property)
         .collect();
 
-    let dependencies: Vec<TokenStream> = service
+    let try_resolve_properties: Vec<TokenStream> = service
         .properties
         .iter()
-        .filter_map(create_dependency)
+        .map(create_try_resolve_property)
         .collect();
 
-    let visibility = &service.metadata.visibility;
-    let parameters_properties: Vec<TokenStream> = service
-        .properties
-        .iter()
-        .filter_map(|property| create_parameters_property(property, visibility))
-        .collect();
+    let required_parameters = create_required_parameters(&service);
 
-    let parameters_defaults: Vec<TokenStream> = service
+    let dependencies: Vec<TokenStream> = service
         .properties
         .iter()
-        .filter_map(|property| create_parameters_default(property, &service.metadata.identifier))
+        .filter_map(create_dependency)
         .collect();
 
+    let dependencies_fn = create_dependencies_fn(&service);
+    let default_parameters_fn = create_default_parameters_fn(&service);
+    let validate_parameters_fn = create_validate_parameters_fn(&service);
+    let parameters_struct = create_parameters_struct(&service);
+    let required_parameters_impl = create_required_parameters_impl(&service);
+
     // Component implementation
+    let (build_body, try_build_component) = match &service.metadata.try_build {
+        Some(try_build) => (
+            quote! {
+                let component = #try_build(Self {
+                    #(#resolve_properties),*
+                }).unwrap_or_else(|error| panic!("{}", ::shaku::BuildError::ComponentBuild {
+                    component: ::std::any::type_name::<Self>(),
+                    source: error.into(),
+                }));
+
+                Box::new(component)
+            },
+            quote! {
+                let component = #try_build(Self {
+                    #(#try_resolve_properties),*
+                })?;
+            },
+        ),
+        None => (
+            quote! {
+                Box::new(Self {
+                    #(#resolve_properties),*
+                })
+            },
+            quote! {
+                let component = Self {
+                    #(#try_resolve_properties),*
+                };
+            },
+        ),
+    };
+
+    // Only override start and try_build_shared if there are lifecycle hooks
+    let try_build_shared = create_lifecycle_hooks(&service.metadata).map(|lifecycle_hooks| {
+        quote! {
+            fn start(component: &::std::sync::Arc<Self>, lifecycle: &::shaku::Lifecycle) {
+                let component = ::std::sync::Arc::clone(component);
+                #lifecycle_hooks
+            }
+
+            fn try_build_shared(
+                context: &mut ::shaku::ModuleBuildContext<M>,
+                params: Self::Parameters,
+            ) -> ::std::result::Result<
+                ::std::sync::Arc<Self::Interface>,
+                Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync>
+            > {
+                #try_build_component
+                let component = ::std::sync::Arc::new(component);
+                <Self as ::shaku::Component<M>>::start(&component, context.lifecycle());
+
+                Ok(component)
+            }
+        }
+    });
+
     let component_name = service.metadata.identifier;
     let parameters_name = format_ident!("{}Parameters", component_name);
-    let parameters_doc = format!(" Parameters for {}", component_name);
     let interface = service.metadata.interface;
-    let (generic_impls, generic_tys, generic_where) = service.metadata.generics.split_for_impl();
+    let (_, generic_tys, generic_where) = service.metadata.generics.split_for_impl();
     let generic_impls_no_parens = &service.metadata.generics.params;
     let output = quote! {
         impl<
@@ -54,26 +114,35 @@ pub fn expand_derive_component(input: &DeriveInput) -> syn::Result<TokenStream>
             type Interface = dyn #interface;
             type Parameters = #parameters_name #generic_tys;
 
+            const REQUIRED_PARAMETERS: &'static [&'static str] = &[#(#required_parameters),*];
+
+            #default_parameters_fn
+
+            #validate_parameters_fn
+
             fn build(context: &mut ::shaku::ModuleBuildContext<M>, params: Self::Parameters) -> Box<Self::Interface> {
-                Box::new(Self {
-                    #(#resolve_properties),*
-                })
+                #build_body
             }
-        }
 
-        #[doc = #parameters_doc]
-        #visibility struct #parameters_name #generic_impls #generic_where {
-            #(#parameters_properties),*
-        }
+            fn try_build(
+                context: &mut ::shaku::ModuleBuildContext<M>,
+                params: Self::Parameters,
+            ) -> ::std::result::Result<
+                Box<Self::Interface>,
+                Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync>
+            > {
+                #try_build_component
 
-        impl #generic_impls ::std::default::Default for #parameters_name #generic_tys #generic_where {
-            #[allow(unreachable_code)]
-            fn default() -> Self {
-                Self {
-                    #(#parameters_defaults),*
-                }
+                Ok(Box::new(component))
             }
+
+            #try_build_shared
+
+            #dependencies_fn
         }
+
+        #parameters_struct
+        #required_parameters_impl
     };
 
     if debug_level > 0 {
@@ -85,8 +154,21 @@ pub fn expand_derive_component(input: &DeriveInput) -> syn::Result<TokenStream>
 
 fn create_resolve_property(property: &Property) -> TokenStream {
     let property_name = &property.property_name;
+    let property_ty = &property.ty;
 
-    if property.is_service() {
+    if let PropertyType::Components = property.property_type {
+        quote! {
+            #property_name: M::build_components(context)
+        }
+    } else if let PropertyType::ComponentMap = property.property_type {
+        quote! {
+            #property_name: M::build_component_map(context)
+        }
+    } else if let Some(tag) = &property.named {
+        quote! {
+            #property_name: <M as ::shaku::HasNamedComponent<#property_ty, #tag>>::build_named_component(context)
+        }
+    } else if property.is_service() {
         quote! {
             #property_name: M::build_component(context)
         }
@@ -97,44 +179,29 @@ fn create_resolve_property(property: &Property) -> TokenStream {
     }
 }
 
-fn create_parameters_property(property: &Property, vis: &Visibility) -> Option<TokenStream> {
-    if property.is_service() {
-        return None;
-    }
-
-    let property_name = &property.property_name;
-    let property_type = &property.ty;
-    let doc_comment = &property.doc_comment;
-
-    Some(quote! {
-        #(#doc_comment)*
-        #vis #property_name: #property_type
-    })
-}
-
-fn create_parameters_default(property: &Property, component_ident: &Ident) -> Option<TokenStream> {
-    if property.is_service() {
-        return None;
-    }
-
+fn create_try_resolve_property(property: &Property) -> TokenStream {
     let property_name = &property.property_name;
+    let property_ty = &property.ty;
 
-    match &property.default {
-        PropertyDefault::Provided(default_expr) => Some(quote! {
-            #property_name: #default_expr
-        }),
-        PropertyDefault::NotProvided => Some(quote! {
-            #property_name: Default::default()
-        }),
-        PropertyDefault::NoDefault => {
-            let unreachable_msg = format!(
-                "There is no default value for `{}::{}`",
-                component_ident, property_name
-            );
-
-            Some(quote! {
-                #property_name: unreachable!(#unreachable_msg)
-            })
+    if let PropertyType::Components = property.property_type {
+        quote! {
+            #property_name: M::try_build_components(context)?
+        }
+    } else if let PropertyType::ComponentMap = property.property_type {
+        quote! {
+            #property_name: M::try_build_component_map(context)?
+        }
+    } else if let Some(tag) = &property.named {
+        quote! {
+            #property_name: <M as ::shaku::HasNamedComponent<#property_ty, #tag>>::try_build_named_component(context)?
+        }
+    } else if property.is_service() {
+        quote! {
+            #property_name: M::try_build_component(context)?
+        }
+    } else {
+        quote! {
+            #property_name: params.#property_name
         }
     }
 }
@@ -0,0 +1,450 @@
+//! Implementation of the `#[shaku::interface]` attribute macro
+
+use crate::consts;
+use crate::debug::get_debug_level;
+use proc_macro2::TokenStream;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{
+    Error, FnArg, Ident, ItemTrait, Pat, PatIdent, ReturnType, Signature, Token, TraitItem,
+    TraitItemMethod, Type, WherePredicate,
+};
+
+/// A trait method which is implemented by the generated types
+struct InterfaceMethod {
+    /// The method's signature, with each argument renamed to `__di_arg{i}`
+    signature: Signature,
+    arguments: Vec<Ident>,
+    returns_result: bool,
+}
+
+pub fn expand_interface_attribute(args: TokenStream, item: ItemTrait) -> syn::Result<TokenStream> {
+    let mut generate_mock = false;
+    for option in Punctuated::<Ident, Token![,]>::parse_terminated.parse2(args)? {
+        if option == consts::MOCK_ATTR_NAME && !generate_mock {
+            generate_mock = true;
+        } else {
+            return Err(Error::new(option.span(), "Unknown or duplicate option"));
+        }
+    }
+
+    if !item.generics.params.is_empty() || item.generics.where_clause.is_some() {
+        return Err(Error::new(
+            item.generics.span(),
+            "Generic interfaces are not supported",
+        ));
+    }
+
+    let methods: Vec<InterfaceMethod> = item
+        .items
+        .iter()
+        .filter_map(|trait_item| match trait_item {
+            TraitItem::Method(method) => parse_method(method).transpose(),
+            TraitItem::Type(ty) => Some(Err(Error::new(
+                ty.ident.span(),
+                "Associated types are not supported",
+            ))),
+            TraitItem::Const(constant) => Some(Err(Error::new(
+                constant.ident.span(),
+                "Associated consts are not supported",
+            ))),
+            _ => None,
+        })
+        .collect::<syn::Result<_>>()?;
+
+    let proxy = create_proxy(&item, &methods);
+    let mock = if generate_mock {
+        create_mock(&item, &methods)?
+    } else {
+        TokenStream::new()
+    };
+
+    let output = quote! {
+        #item
+
+        #proxy
+
+        #mock
+    };
+
+    if get_debug_level() > 0 {
+        println!("{}", output);
+    }
+
+    Ok(output)
+}
+
+/// Check that the method can be implemented by the generated types. Methods
+/// which can't be called on a trait object and have a default implementation
+/// are skipped.
+fn parse_method(method: &TraitItemMethod) -> syn::Result<Option<InterfaceMethod>> {
+    let signature = &method.sig;
+
+    if requires_sized(signature) {
+        return if method.default.is_some() {
+            Ok(None)
+        } else {
+            Err(Error::new(
+                signature.ident.span(),
+                "Methods which require `Self: Sized` must have a default implementation",
+            ))
+        };
+    }
+
+    match signature.inputs.first() {
+        Some(FnArg::Receiver(receiver))
+            if receiver.reference.is_some() && receiver.mutability.is_none() => {}
+        receiver => {
+            return Err(Error::new(
+                receiver
+                    .map(Spanned::span)
+                    .unwrap_or_else(|| signature.ident.span()),
+                "Only methods which take `&self` are supported",
+            ))
+        }
+    }
+    if signature.asyncness.is_some() {
+        return Err(Error::new(
+            signature.asyncness.span(),
+            "Async methods are not supported",
+        ));
+    }
+    if signature.generics.type_params().next().is_some() || signature.variadic.is_some() {
+        return Err(Error::new(
+            signature.span(),
+            "Generic methods are not supported",
+        ));
+    }
+
+    // Give each argument a name, so it can be passed on
+    let mut renamed_signature = signature.clone();
+    let mut arguments = Vec::new();
+    for (i, input) in renamed_signature.inputs.iter_mut().skip(1).enumerate() {
+        if let FnArg::Typed(argument) = input {
+            let argument_name = format_ident!("__di_arg{}", i);
+            *argument.pat = Pat::Ident(PatIdent {
+                attrs: Vec::new(),
+                by_ref: None,
+                mutability: None,
+                ident: argument_name.clone(),
+                subpat: None,
+            });
+            arguments.push(argument_name);
+        }
+    }
+
+    Ok(Some(InterfaceMethod {
+        signature: renamed_signature,
+        arguments,
+        returns_result: returns_result(signature),
+    }))
+}
+
+fn create_proxy(item: &ItemTrait, methods: &[InterfaceMethod]) -> TokenStream {
+    let visibility = &item.vis;
+    let trait_name = &item.ident;
+    let proxy_name = format_ident!("{}Proxy", trait_name);
+    let proxy_doc = format!(
+        "Implements `{}` by delegating to an inner service, running a \
+         `shaku::Interceptor` around each method call.",
+        trait_name
+    );
+    let proxy_methods = methods
+        .iter()
+        .map(|method| create_proxy_method(trait_name, method));
+
+    quote! {
+        #[doc = #proxy_doc]
+        #visibility struct #proxy_name<__DiInterceptor: ::shaku::Interceptor> {
+            inner: ::std::sync::Arc<dyn #trait_name>,
+            interceptor: __DiInterceptor,
+        }
+
+        impl<__DiInterceptor: ::shaku::Interceptor> #proxy_name<__DiInterceptor> {
+            /// Wrap the service, intercepting its method calls
+            #[allow(dead_code)]
+            #visibility fn new(
+                inner: ::std::sync::Arc<dyn #trait_name>,
+                interceptor: __DiInterceptor,
+            ) -> Self {
+                Self { inner, interceptor }
+            }
+
+            /// The wrapped service
+            #[allow(dead_code)]
+            #visibility fn inner(&self) -> &::std::sync::Arc<dyn #trait_name> {
+                &self.inner
+            }
+        }
+
+        impl<__DiInterceptor: ::shaku::Interceptor> #trait_name for #proxy_name<__DiInterceptor> {
+            #(#proxy_methods)*
+        }
+    }
+}
+
+/// Create the delegating implementation of a method
+fn create_proxy_method(trait_name: &Ident, method: &InterfaceMethod) -> TokenStream {
+    let signature = &method.signature;
+    let arguments = &method.arguments;
+    let method_name = &signature.ident;
+    let call = if signature.unsafety.is_some() {
+        quote! { unsafe { #trait_name::#method_name(&*self.inner, #(#arguments),*) } }
+    } else {
+        quote! { #trait_name::#method_name(&*self.inner, #(#arguments),*) }
+    };
+    let report = if method.returns_result {
+        quote! {
+            match &__di_result {
+                ::std::result::Result::Ok(_) => {
+                    self.interceptor.after(&__di_call, __di_start.elapsed())
+                }
+                ::std::result::Result::Err(__di_error) => {
+                    self.interceptor.error(&__di_call, __di_error, __di_start.elapsed())
+                }
+            }
+        }
+    } else {
+        quote! { self.interceptor.after(&__di_call, __di_start.elapsed()); }
+    };
+
+    quote! {
+        #signature {
+            let __di_call = ::shaku::MethodCall {
+                interface: stringify!(#trait_name),
+                method: stringify!(#method_name),
+            };
+            self.interceptor.before(
+                &__di_call,
+                &[#(&#arguments as &dyn ::std::fmt::Debug),*],
+            );
+            let __di_start = ::std::time::Instant::now();
+            let __di_result = #call;
+            #report
+            __di_result
+        }
+    }
+}
+
+#[cfg(feature = "mock")]
+fn create_mock(item: &ItemTrait, methods: &[InterfaceMethod]) -> syn::Result<TokenStream> {
+    let visibility = &item.vis;
+    let trait_name = &item.ident;
+    let mock_name = format_ident!("Mock{}", trait_name);
+    let mock_doc = format!(
+        "A mock implementation of `{}`, which records its calls and runs the \
+         function set via `expect_*` for each method. Calling a method without \
+         an expectation panics. Clones share the same expectations and calls.",
+        trait_name
+    );
+
+    let mut fields = Vec::new();
+    let mut expect_fns = Vec::new();
+    let mut mock_methods = Vec::new();
+    for method in methods {
+        let signature = &method.signature;
+        check_mock_return_type(&signature.output)?;
+
+        // The function type of the method, without `self`
+        let lifetimes: Vec<_> = signature
+            .generics
+            .lifetimes()
+            .map(|lifetime| &lifetime.lifetime)
+            .collect();
+        let for_lifetimes = if lifetimes.is_empty() {
+            TokenStream::new()
+        } else {
+            quote! { for<#(#lifetimes),*> }
+        };
+        let argument_types = signature.inputs.iter().filter_map(|input| match input {
+            FnArg::Typed(argument) => Some(&argument.ty),
+            FnArg::Receiver(_) => None,
+        });
+        let output = &signature.output;
+        let function_ty = quote! {
+            #for_lifetimes Fn(#(#argument_types),*) #output + Send + Sync
+        };
+
+        let method_name = &signature.ident;
+        let method_name_str = method_name.to_string();
+        let full_name = format!("{}::{}", trait_name, method_name);
+        let expect_name = format_ident!("expect_{}", method_name);
+        let expect_doc = format!(
+            "Set the function which is run when `{}` is called",
+            full_name
+        );
+        let arguments = &method.arguments;
+
+        fields.push(quote! {
+            #method_name: ::shaku::MockExpectation<dyn #function_ty>
+        });
+        expect_fns.push(quote! {
+            #[doc = #expect_doc]
+            #visibility fn #expect_name<__DiFunction>(self, function: __DiFunction) -> Self
+            where
+                __DiFunction: #function_ty + 'static,
+            {
+                self.#method_name.set(::std::sync::Arc::new(function));
+                self
+            }
+        });
+        mock_methods.push(quote! {
+            #signature {
+                self.__di_calls.record(
+                    #method_name_str,
+                    &[#(&#arguments as &dyn ::std::fmt::Debug),*],
+                );
+                (self.#method_name.get(#full_name))(#(#arguments),*)
+            }
+        });
+    }
+
+    Ok(quote! {
+        #[doc = #mock_doc]
+        #[derive(Clone, Default)]
+        #visibility struct #mock_name {
+            __di_calls: ::shaku::MockRecorder,
+            #(#fields),*
+        }
+
+        #[allow(dead_code)]
+        impl #mock_name {
+            /// Create a mock without any expectations
+            #visibility fn new() -> Self {
+                Self::default()
+            }
+
+            #(#expect_fns)*
+
+            /// Box a clone of the mock, such as for
+            /// `ModuleBuilder::with_component_override`
+            #visibility fn boxed(&self) -> ::std::boxed::Box<dyn #trait_name> {
+                ::std::boxed::Box::new(self.clone())
+            }
+
+            /// A provider function which provides clones of the mock, such as
+            /// for `ModuleBuilder::with_provider_override`
+            #visibility fn provider<__DiModule: 'static>(
+                &self,
+            ) -> ::shaku::ProviderFn<__DiModule, dyn #trait_name> {
+                let mock = self.clone();
+                ::std::boxed::Box::new(move |_| {
+                    ::std::result::Result::Ok(::std::boxed::Box::new(mock.clone()))
+                })
+            }
+
+            /// Every call made to the mock, in order
+            #visibility fn calls(&self) -> ::std::vec::Vec<::shaku::MockCall> {
+                self.__di_calls.calls()
+            }
+
+            /// The calls made to one method of the mock, in order
+            #visibility fn calls_to(&self, method: &str) -> ::std::vec::Vec<::shaku::MockCall> {
+                self.__di_calls.calls_to(method)
+            }
+        }
+
+        impl #trait_name for #mock_name {
+            #(#mock_methods)*
+        }
+    })
+}
+
+#[cfg(not(feature = "mock"))]
+fn create_mock(item: &ItemTrait, _methods: &[InterfaceMethod]) -> syn::Result<TokenStream> {
+    Err(Error::new(
+        item.ident.span(),
+        "Generating mocks requires the `mock` feature of shaku",
+    ))
+}
+
+/// The expectations of a mock can't return references to the mock, so check
+/// that the return type has no elided lifetimes
+#[cfg(feature = "mock")]
+fn check_mock_return_type(output: &ReturnType) -> syn::Result<()> {
+    use proc_macro2::TokenTree;
+
+    fn check_tokens(tokens: TokenStream) -> syn::Result<()> {
+        let mut tokens = tokens.into_iter().peekable();
+        while let Some(token) = tokens.next() {
+            let is_elided = match &token {
+                TokenTree::Punct(punct) if punct.as_char() == '&' => match tokens.peek() {
+                    Some(TokenTree::Punct(next)) => next.as_char() != '\'',
+                    _ => true,
+                },
+                TokenTree::Punct(punct) if punct.as_char() == '\'' => match tokens.peek() {
+                    Some(TokenTree::Ident(next)) => next == "_",
+                    _ => false,
+                },
+                TokenTree::Group(group) => {
+                    check_tokens(group.stream())?;
+                    false
+                }
+                _ => false,
+            };
+
+            if is_elided {
+                return Err(Error::new(
+                    token.span(),
+                    "Mocked methods can't return references with elided lifetimes",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    match output {
+        ReturnType::Type(_, ty) => check_tokens(quote! { #ty }),
+        ReturnType::Default => Ok(()),
+    }
+}
+
+/// Check if the method has a `where Self: Sized` bound
+fn requires_sized(signature: &Signature) -> bool {
+    let where_clause = match &signature.generics.where_clause {
+        Some(where_clause) => where_clause,
+        None => return false,
+    };
+
+    where_clause
+        .predicates
+        .iter()
+        .any(|predicate| match predicate {
+            WherePredicate::Type(predicate) => {
+                let is_self = match &predicate.bounded_ty {
+                    Type::Path(path) => path.qself.is_none() && path.path.is_ident("Self"),
+                    _ => false,
+                };
+                let is_sized = predicate.bounds.iter().any(|bound| match bound {
+                    syn::TypeParamBound::Trait(bound) => bound
+                        .path
+                        .segments
+                        .last()
+                        .map(|segment| segment.ident == "Sized")
+                        .unwrap_or(false),
+                    _ => false,
+                });
+
+                is_self && is_sized
+            }
+            _ => false,
+        })
+}
+
+/// Check if the method returns a `Result` (or an alias named `Result`)
+fn returns_result(signature: &Signature) -> bool {
+    match &signature.output {
+        ReturnType::Type(_, ty) => match &**ty {
+            Type::Path(path) => path
+                .path
+                .segments
+                .last()
+                .map(|segment| segment.ident == "Result")
+                .unwrap_or(false),
+            _ => false,
+        },
+        ReturnType::Default => false,
+    }
+}
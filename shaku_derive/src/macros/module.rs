@@ -1,11 +1,11 @@
 //! Implementation of the `module` procedural macro
 
 use crate::debug::get_debug_level;
-use crate::structures::module::{ComponentItem, ModuleData, Submodule};
+use crate::structures::module::{ComponentItem, ModuleData, ProviderItem, Submodule};
 use proc_macro2::{Ident, Span, TokenStream};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::Type;
+use syn::{LitStr, Type};
 
 pub fn expand_module_macro(module: ModuleData) -> syn::Result<TokenStream> {
     let debug_level = get_debug_level();
@@ -13,19 +13,129 @@ pub fn expand_module_macro(module: ModuleData) -> syn::Result<TokenStream> {
         println!("Module data parsed from input: {:#?}", module);
     }
 
-    // Only capture the build context if there is a lazy component
+    // Named components are resolved synchronously, so they cannot be async
+    if let Some(component) = module
+        .services
+        .components
+        .items
+        .iter()
+        .find(|component| component.named().is_some() && component.is_async())
+    {
+        return Err(syn::Error::new(
+            component.ty.span(),
+            "Named components cannot be async",
+        ));
+    }
+
+    // Multi-bound components are built eagerly, as part of their set
+    for component in &module.services.components.items {
+        if !component.is_multi() {
+            continue;
+        }
+
+        if component.multi_interface().is_none() {
+            return Err(syn::Error::new(
+                component.ty.span(),
+                "Multi-bound components must specify their interface, for example #[multi(dyn Trait)]",
+            ));
+        }
+
+        if component.is_lazy() || component.is_async() || component.named().is_some() {
+            return Err(syn::Error::new(
+                component.ty.span(),
+                "Multi-bound components cannot be lazy, async, or named",
+            ));
+        }
+    }
+
+    // Reloadable components are replaced as a whole after the module is
+    // built, so they must be built with the module
+    if let Some(component) = module.services.components.items.iter().find(|component| {
+        component.is_reloadable()
+            && (component.is_lazy()
+                || component.is_async()
+                || component.named().is_some()
+                || component.is_multi())
+    }) {
+        return Err(syn::Error::new(
+            component.ty.span(),
+            "Reloadable components cannot be lazy, async, named, or multi-bound",
+        ));
+    }
+
+    // Keyed components are multi-bound components which are added to a map
+    // instead of a set
+    let mut keys: Vec<(&Type, String)> = Vec::new();
+    for component in &module.services.components.items {
+        if !component.is_keyed() {
+            continue;
+        }
+
+        let key = match component.key() {
+            Some(key) => key,
+            None => {
+                return Err(syn::Error::new(
+                    component.ty.span(),
+                    "Keyed components must specify their key, for example #[key(\"name\")]",
+                ))
+            }
+        };
+
+        let interface = match component.multi_interface() {
+            Some(interface) => interface,
+            None => {
+                return Err(syn::Error::new(
+                    component.ty.span(),
+                    "Keyed components must be multi-bound, for example #[multi(dyn Trait)] #[key(\"name\")]",
+                ))
+            }
+        };
+
+        let entry = (interface, key.value());
+        if keys.contains(&entry) {
+            return Err(syn::Error::new(key.span(), "Duplicate key"));
+        }
+        keys.push(entry);
+    }
+
+    // Scoped services are cached synchronously, so they cannot be async
+    if let Some(provider) = module
+        .services
+        .providers
+        .items
+        .iter()
+        .find(|provider| provider.is_scoped() && provider.is_async())
+    {
+        return Err(syn::Error::new(
+            provider.ty.span(),
+            "Scoped providers cannot be async",
+        ));
+    }
+
+    // Only capture the build context if there is a lazy component, or a
+    // reloadable component (whose replacements are decorated)
     let capture_build_context = module
         .services
         .components
         .items
         .iter()
-        .any(ComponentItem::is_lazy);
+        .any(|component| component.is_lazy() || component.is_reloadable());
+
+    // Lazy async components need to hold the build context across awaits
+    let async_build_context = module
+        .services
+        .components
+        .items
+        .iter()
+        .any(|component| component.is_lazy() && component.is_async());
 
     // Build token streams
-    let module_struct = module_struct(&module, capture_build_context);
+    let module_struct = module_struct(&module, capture_build_context, async_build_context);
     let module_trait_impl = module_trait(&module);
     let module_builder = module_builder(&module);
-    let module_impl = module_impl(&module, capture_build_context);
+    let module_impl = module_impl(&module, capture_build_context, async_build_context);
+    let configurable_module_impl = configurable_module_impl(&module);
+    let parameter_state_impls = parameter_state_impls(&module);
 
     let has_component_impls: Vec<TokenStream> = module
         .services
@@ -33,7 +143,17 @@ pub fn expand_module_macro(module: ModuleData) -> syn::Result<TokenStream> {
         .items
         .iter()
         .enumerate()
-        .map(|(i, ty)| has_component_impl(i, ty, &module))
+        .map(|(i, ty)| has_component_impl(i, ty, &module, async_build_context))
+        .collect();
+
+    let has_components_impls: Vec<TokenStream> = multi_interfaces(&module)
+        .into_iter()
+        .map(|interface| has_components_impl(interface, &module))
+        .collect();
+
+    let has_component_map_impls: Vec<TokenStream> = map_interfaces(&module)
+        .into_iter()
+        .map(|interface| has_component_map_impl(interface, &module))
         .collect();
 
     let has_provider_impls: Vec<TokenStream> = module
@@ -42,7 +162,7 @@ pub fn expand_module_macro(module: ModuleData) -> syn::Result<TokenStream> {
         .items
         .iter()
         .enumerate()
-        .map(|(i, provider)| has_provider_impl(i, &provider.ty, &module))
+        .map(|(i, provider)| has_provider_impl(i, provider, &module))
         .collect();
 
     let has_subcomponent_impls: Vec<TokenStream> = module
@@ -55,7 +175,7 @@ pub fn expand_module_macro(module: ModuleData) -> syn::Result<TokenStream> {
                 .components
                 .items
                 .iter()
-                .map(|component| has_subcomponent_impl(i, submodule, &component.ty, &module))
+                .map(|component| has_subcomponent_impl(i, submodule, component, &module))
                 .collect::<Vec<_>>()
         })
         .collect();
@@ -70,21 +190,30 @@ pub fn expand_module_macro(module: ModuleData) -> syn::Result<TokenStream> {
                 .providers
                 .items
                 .iter()
-                .map(|provider| has_subprovider_impl(i, submodule, &provider.ty, &module))
+                .map(|provider| has_subprovider_impl(i, submodule, provider, &module))
                 .collect::<Vec<_>>()
         })
         .collect();
 
+    let module_instance_impl = module_instance_impl(&module);
+    let resolve_any_impl = resolve_any_impl(&module);
+
     // Combine token streams for the final macro output
     let output = quote! {
         #module_struct
         #module_trait_impl
         #module_builder
         #module_impl
+        #module_instance_impl
+        #configurable_module_impl
+        #parameter_state_impls
         #(#has_component_impls)*
+        #(#has_components_impls)*
+        #(#has_component_map_impls)*
         #(#has_provider_impls)*
         #(#has_subcomponent_impls)*
         #(#has_subprovider_impls)*
+        #resolve_any_impl
     };
 
     if debug_level > 0 {
@@ -95,7 +224,11 @@ pub fn expand_module_macro(module: ModuleData) -> syn::Result<TokenStream> {
 }
 
 /// Create the module struct
-fn module_struct(module: &ModuleData, capture_build_context: bool) -> TokenStream {
+fn module_struct(
+    module: &ModuleData,
+    capture_build_context: bool,
+    async_build_context: bool,
+) -> TokenStream {
     let component_properties: Vec<TokenStream> = module
         .services
         .components
@@ -111,7 +244,7 @@ fn module_struct(module: &ModuleData, capture_build_context: bool) -> TokenStrea
         .items
         .iter()
         .enumerate()
-        .map(|(i, provider)| provider_property(i, &provider.ty))
+        .map(|(i, provider)| provider_property(i, provider))
         .collect();
 
     let submodule_properties: Vec<TokenStream> = module
@@ -126,7 +259,9 @@ fn module_struct(module: &ModuleData, capture_build_context: bool) -> TokenStrea
     let module_generics = &module.metadata.generics;
     let where_clause = &module.metadata.generics.where_clause;
 
-    let build_context_property = if capture_build_context {
+    let build_context_property = if async_build_context {
+        quote! { build_context: ::shaku::AsyncMutex<::shaku::ModuleBuildContext<Self>>, }
+    } else if capture_build_context {
         quote! { build_context: ::std::sync::Mutex<::shaku::ModuleBuildContext<Self>>, }
     } else {
         TokenStream::new()
@@ -137,6 +272,8 @@ fn module_struct(module: &ModuleData, capture_build_context: bool) -> TokenStrea
             #(#component_properties,)*
             #(#provider_properties,)*
             #(#submodule_properties,)*
+            __di_lifecycle: ::std::sync::Arc<::shaku::Lifecycle>,
+            __di_overrides: ::std::vec::Vec<::shaku::ServiceOverride>,
             #build_context_property
         }
     }
@@ -153,8 +290,31 @@ fn module_trait(module: &ModuleData) -> Option<TokenStream> {
     })
 }
 
+/// Create a ModuleInstance impl, which gives the modules this module is a
+/// submodule of access to its lifecycle and dependency graph
+fn module_instance_impl(module: &ModuleData) -> TokenStream {
+    let module_name = &module.metadata.identifier;
+    let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::shaku::ModuleInstance for #module_name #ty_generics #where_clause {
+            fn lifecycle(&self) -> ::std::option::Option<::std::sync::Arc<::shaku::Lifecycle>> {
+                Some(::std::sync::Arc::clone(&self.__di_lifecycle))
+            }
+
+            fn module_graph(&self) -> ::std::option::Option<::shaku::DependencyGraph> {
+                Some(::shaku::Module::dependency_graph(self))
+            }
+        }
+    }
+}
+
 /// Create a Module impl
-fn module_impl(module: &ModuleData, capture_build_context: bool) -> TokenStream {
+fn module_impl(
+    module: &ModuleData,
+    capture_build_context: bool,
+    async_build_context: bool,
+) -> TokenStream {
     let module_name = &module.metadata.identifier;
     let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
 
@@ -164,7 +324,16 @@ fn module_impl(module: &ModuleData, capture_build_context: bool) -> TokenStream
         .items
         .iter()
         .enumerate()
-        .map(|(i, component)| component_build(i, component))
+        .map(|(i, component)| component_build(i, component, false))
+        .collect();
+
+    let component_try_builders: Vec<TokenStream> = module
+        .services
+        .components
+        .items
+        .iter()
+        .enumerate()
+        .map(|(i, component)| component_build(i, component, true))
         .collect();
 
     let provider_builders: Vec<TokenStream> = module
@@ -173,17 +342,29 @@ fn module_impl(module: &ModuleData, capture_build_context: bool) -> TokenStream
         .items
         .iter()
         .enumerate()
-        .map(|(i, provider)| provider_build(i, &provider.ty))
+        .map(|(i, provider)| provider_build(i, provider))
         .collect();
 
+    let validate_parameters = validate_parameters(module);
     let submodules_init = submodules_init(&module.submodules);
     let submodule_names = submodule_names(&module.submodules);
     let submodule_types: Vec<&Type> = module.submodules.iter().map(|sub| &sub.ty).collect();
-    let build_context_init = if capture_build_context {
+    let build_context_init = if async_build_context {
+        quote! { build_context: ::shaku::AsyncMutex::new(context), }
+    } else if capture_build_context {
         quote! { build_context: ::std::sync::Mutex::new(context), }
     } else {
         TokenStream::new()
     };
+    let try_build_async = module_try_build_async(module);
+    let dependency_graph = module_dependency_graph(module);
+
+    // Maps which are combined with submodule maps are built up front, so
+    // duplicate keys are reported even if the map is never injected
+    let checked_maps: Vec<&Type> = map_interfaces(module)
+        .into_iter()
+        .filter(|interface| !map_submodule_names(interface, module).is_empty())
+        .collect();
 
     quote! {
         impl #impl_generics ::shaku::Module for #module_name #ty_generics #where_clause {
@@ -191,19 +372,89 @@ fn module_impl(module: &ModuleData, capture_build_context: bool) -> TokenStream
             type Submodules = (#(::std::sync::Arc<#submodule_types>),*);
 
             fn build(mut context: ::shaku::ModuleBuildContext<Self>) -> Self {
+                #validate_parameters
+                context.check_parameter_violations().unwrap_or_else(|error| panic!("{}", error));
                 #submodules_init
+                #(
+                <Self as ::shaku::HasComponentMap<#checked_maps>>::build_component_map(&mut context);
+                )*
 
                 Self {
                     #(#component_builders,)*
                     #(#provider_builders,)*
                     #(#submodule_names,)*
+                    __di_lifecycle: ::std::sync::Arc::clone(context.lifecycle()),
+                    __di_overrides: context.overrides().to_vec(),
                     #build_context_init
                 }
             }
+
+            fn try_build(
+                mut context: ::shaku::ModuleBuildContext<Self>
+            ) -> ::std::result::Result<Self, ::shaku::BuildError> {
+                #validate_parameters
+                context.check_parameter_violations()?;
+                #submodules_init
+                #(
+                <Self as ::shaku::HasComponentMap<#checked_maps>>::try_build_component_map(&mut context)?;
+                )*
+
+                Ok(Self {
+                    #(#component_try_builders,)*
+                    #(#provider_builders,)*
+                    #(#submodule_names,)*
+                    __di_lifecycle: ::std::sync::Arc::clone(context.lifecycle()),
+                    __di_overrides: context.overrides().to_vec(),
+                    #build_context_init
+                })
+            }
+
+            #try_build_async
+
+            fn shutdown(&self) {
+                self.__di_lifecycle.shutdown()
+            }
+
+            #dependency_graph
         }
     }
 }
 
+/// Create a `try_build_async` override which builds the (non-lazy) async
+/// components before building the rest of the module. Returns `None` if there
+/// are no async components.
+fn module_try_build_async(module: &ModuleData) -> Option<TokenStream> {
+    let components = &module.services.components.items;
+    if !components.iter().any(ComponentItem::is_async) {
+        return None;
+    }
+
+    let interfaces: Vec<TokenStream> = components
+        .iter()
+        .filter(|component| component.is_async() && !component.is_lazy())
+        .map(interface_from_component)
+        .collect();
+    let validate_parameters = validate_parameters(module);
+
+    Some(quote! {
+        fn try_build_async(
+            mut context: ::shaku::ModuleBuildContext<Self>
+        ) -> ::shaku::BoxFuture<'static, ::std::result::Result<Self, ::shaku::BuildError>> {
+            Box::pin(async move {
+                // The sync components are validated again by try_build, but
+                // the async components are skipped since they are resolved
+                #validate_parameters
+                context.check_parameter_violations()?;
+                #(
+                <Self as ::shaku::HasComponent<#interfaces>>::build_component_async(&mut context).await?;
+                )*
+
+                <Self as ::shaku::Module>::try_build(context)
+            })
+        }
+    })
+}
+
 /// Create the `builder` function on the generated module type
 fn module_builder(module: &ModuleData) -> TokenStream {
     let module_name = &module.metadata.identifier;
@@ -211,28 +462,265 @@ fn module_builder(module: &ModuleData) -> TokenStream {
     let submodule_names = submodule_names(&module.submodules);
     let submodule_types: Vec<&Type> = module.submodules.iter().map(|s| &s.ty).collect();
     let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+    let component_types = module
+        .services
+        .components
+        .items
+        .iter()
+        .map(|component| &component.ty);
 
     quote! {
         impl #impl_generics #module_name #ty_generics #where_clause {
             #[allow(bare_trait_objects)]
             #visibility fn builder(
                 #(#submodule_names: ::std::sync::Arc<#submodule_types>),*
-            ) -> ::shaku::ModuleBuilder<Self> {
+            ) -> ::shaku::ModuleBuilder<Self, (#(::shaku::__private::ParametersUnset<#component_types>,)*)> {
                 ::shaku::ModuleBuilder::with_submodules((#(#submodule_names),*))
+                    .with_parameter_state()
+            }
+        }
+    }
+}
+
+/// Create the impls which track the parameters set on the module builder at
+/// compile time. The state has a slot for each component, which is set when
+/// the component's parameters are set or the component is overridden.
+/// Components which are bound more than once under the same tag share their
+/// parameters, so they are set together. Modules without components have
+/// nothing to track.
+fn parameter_state_impls(module: &ModuleData) -> TokenStream {
+    let components = &module.services.components.items;
+    if components.is_empty() {
+        return TokenStream::new();
+    }
+
+    let module_name = &module.metadata.identifier;
+    let (_, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+    let states: Vec<Ident> = (0..components.len())
+        .map(|i| format_ident!("__DiState{}", i))
+        .collect();
+    let mut generics = module.metadata.generics.clone();
+    generics.params.extend(
+        states
+            .iter()
+            .map(|state| -> syn::GenericParam { syn::parse_quote!(#state) }),
+    );
+    let (impl_generics, _, _) = generics.split_for_impl();
+
+    let keys: Vec<(&Type, TokenStream)> = components
+        .iter()
+        .map(|component| {
+            let component_ty = &component.ty;
+            let tag = if component.is_keyed() {
+                quote! { ::shaku::Keyed<#component_ty> }
+            } else if component.is_multi() {
+                quote! { ::shaku::Multi<#component_ty> }
+            } else if let Some(tag) = component.named() {
+                quote! { #tag }
+            } else {
+                quote! { ::shaku::Untagged }
+            };
+
+            (component_ty, tag)
+        })
+        .collect();
+    let key_names: Vec<String> = keys
+        .iter()
+        .map(|(component_ty, tag)| quote!(#component_ty, #tag).to_string())
+        .collect();
+
+    let set_parameters_impls = keys
+        .iter()
+        .enumerate()
+        .filter_map(|(i, (component_ty, tag))| {
+            // Only the first component with a key gets the impl
+            if key_names[..i].contains(&key_names[i]) {
+                return None;
+            }
+
+            let output_states = states.iter().enumerate().map(|(j, state)| {
+                if key_names[j] == key_names[i] {
+                    quote! { ::shaku::__private::ParametersSet }
+                } else {
+                    quote! { #state }
+                }
+            });
+
+            Some(quote! {
+                impl #impl_generics ::shaku::__private::SetParameters<#component_ty, #tag, (#(#states,)*)>
+                    for #module_name #ty_generics #where_clause
+                {
+                    type Output = (#(#output_states,)*);
+                }
+            })
+        });
+
+    // Overrides are keyed by interface, so they are recorded in the state,
+    // and mapped to the component which implements the interface when the
+    // module is built. The component is found via a where clause on the
+    // interface, so the component's own bounds are only checked when the
+    // module is built. Overriding a subcomponent doesn't change the state.
+    let mut override_generics = generics.clone();
+    override_generics
+        .params
+        .push(syn::parse_quote!(__DiInterface: ?Sized));
+    override_generics.params.push(syn::parse_quote!(__DiTag));
+    let (override_impl_generics, _, _) = override_generics.split_for_impl();
+
+    let own_overrides = components
+        .iter()
+        .enumerate()
+        .filter(|(_, component)| !component.is_multi() && !component.is_keyed())
+        .map(|(i, component)| {
+            let component_ty = &component.ty;
+            let tag = component_tag(component);
+            let output_states = states.iter().enumerate().map(|(j, state)| {
+                if j == i {
+                    quote! { ::shaku::__private::ParametersSet }
+                } else {
+                    quote! { #state }
+                }
+            });
+
+            let mut apply_generics = generics.clone();
+            apply_generics
+                .params
+                .push(syn::parse_quote!(__DiInterface: ?Sized));
+            let interface_trait = if component.is_async() {
+                quote! { ::shaku::__private::AsyncComponentInterface }
+            } else {
+                quote! { ::shaku::__private::ComponentInterface }
+            };
+            apply_generics.make_where_clause().predicates.push(syn::parse_quote!(
+                __DiInterface: #interface_trait<#component_ty, Self>
+            ));
+            let (apply_impl_generics, _, apply_where_clause) = apply_generics.split_for_impl();
+
+            quote! {
+                impl #apply_impl_generics ::shaku::__private::ApplyOverride<__DiInterface, #tag, (#(#states,)*), #component_ty>
+                    for #module_name #ty_generics #apply_where_clause
+                {
+                    type Output = (#(#output_states,)*);
+                }
+            }
+        });
+    let subcomponent_overrides = module
+        .submodules
+        .iter()
+        .flat_map(|submodule| submodule.services.components.items.iter())
+        .filter(|component| !component.is_multi() && !component.is_keyed())
+        .map(|component| {
+            let interface = &component.ty;
+            let tag = component_tag(component);
+
+            quote! {
+                #[allow(bare_trait_objects)]
+                impl #impl_generics ::shaku::__private::ApplyOverride<#interface, #tag, (#(#states,)*), ()>
+                    for #module_name #ty_generics #where_clause
+                {
+                    type Output = (#(#states,)*);
+                }
             }
+        });
+    let apply_override_impls = own_overrides.chain(subcomponent_overrides);
+
+    // The proof of each state is inferred when the module is built
+    let proofs: Vec<Ident> = (0..components.len())
+        .map(|i| format_ident!("__DiProof{}", i))
+        .collect();
+    let mut ready_generics = module.metadata.generics.clone();
+    ready_generics.params.extend(states.iter().zip(&proofs).map(
+        |(state, proof)| -> syn::GenericParam {
+            syn::parse_quote!(#state: ::shaku::__private::ParametersReady<#proof>)
+        },
+    ));
+    ready_generics.params.extend(
+        proofs
+            .iter()
+            .map(|proof| -> syn::GenericParam { syn::parse_quote!(#proof) }),
+    );
+    let (ready_impl_generics, _, _) = ready_generics.split_for_impl();
+
+    quote! {
+        #(#set_parameters_impls)*
+
+        impl #override_impl_generics ::shaku::__private::OverrideComponent<__DiInterface, __DiTag, (#(#states,)*)>
+            for #module_name #ty_generics #where_clause
+        {
+            type Output = ::shaku::__private::ComponentOverridden<__DiInterface, __DiTag, (#(#states,)*)>;
+        }
+
+        #(#apply_override_impls)*
+
+        impl #ready_impl_generics ::shaku::__private::RequiredParametersSet<(#(#states,)*), (#(#proofs,)*)>
+            for #module_name #ty_generics #where_clause
+        {
         }
     }
 }
 
-/// Create a property initializer for the component during module build
-fn component_build(index: usize, component: &ComponentItem) -> TokenStream {
+/// Create a property initializer for the component during module build. If
+/// `fallible` is set, build errors are propagated with `?`.
+fn component_build(index: usize, component: &ComponentItem, fallible: bool) -> TokenStream {
     let property = generate_name(index, "component", component.ty.span());
-    let interface = interface_from_component(&component.ty);
+    let interface = interface_from_component(component);
 
-    if component.is_lazy() {
+    if component.is_lazy() && component.is_async() {
+        quote! {
+            #property: ::shaku::AsyncOnceCell::new()
+        }
+    } else if component.is_lazy() {
         quote! {
             #property: ::shaku::OnceCell::new()
         }
+    } else if component.is_keyed() {
+        let component_ty = &component.ty;
+
+        if fallible {
+            quote! {
+                #property: context.try_build_keyed_component::<#component_ty>()?
+            }
+        } else {
+            quote! {
+                #property: context.build_keyed_component::<#component_ty>()
+            }
+        }
+    } else if component.is_multi() {
+        let component_ty = &component.ty;
+
+        if fallible {
+            quote! {
+                #property: context.try_build_multi_component::<#component_ty>()?
+            }
+        } else {
+            quote! {
+                #property: context.build_multi_component::<#component_ty>()
+            }
+        }
+    } else if let Some(tag) = component.named() {
+        if fallible {
+            quote! {
+                #property: <Self as ::shaku::HasNamedComponent<#interface, #tag>>::try_build_named_component(&mut context)?
+            }
+        } else {
+            quote! {
+                #property: <Self as ::shaku::HasNamedComponent<#interface, #tag>>::build_named_component(&mut context)
+            }
+        }
+    } else if component.is_reloadable() {
+        let build_component = if fallible {
+            quote! { <Self as ::shaku::HasComponent<#interface>>::try_build_component(&mut context)? }
+        } else {
+            quote! { <Self as ::shaku::HasComponent<#interface>>::build_component(&mut context) }
+        };
+
+        quote! {
+            #property: ::shaku::ReloadableCell::new(#build_component)
+        }
+    } else if fallible {
+        quote! {
+            #property: <Self as ::shaku::HasComponent<#interface>>::try_build_component(&mut context)?
+        }
     } else {
         quote! {
             #property: <Self as ::shaku::HasComponent<#interface>>::build_component(&mut context)
@@ -240,116 +728,933 @@ fn component_build(index: usize, component: &ComponentItem) -> TokenStream {
     }
 }
 
-/// Create a property initializer for the provider during module build
-fn provider_build(index: usize, provider_ty: &Type) -> TokenStream {
+/// Validate the parameters of the module's components before any component
+/// is built, so components with invalid parameters are never built. Lazy
+/// components are validated when they are built.
+fn validate_parameters(module: &ModuleData) -> TokenStream {
+    let validations = module
+        .services
+        .components
+        .items
+        .iter()
+        .filter(|component| !component.is_lazy())
+        .map(|component| {
+            let component_ty = &component.ty;
+
+            if component.is_async() {
+                quote! { context.validate_async_component::<#component_ty>(); }
+            } else if component.is_keyed() {
+                quote! { context.validate_keyed_component::<#component_ty>(); }
+            } else if component.is_multi() {
+                quote! { context.validate_multi_component::<#component_ty>(); }
+            } else if let Some(tag) = component.named() {
+                quote! { context.validate_named_component::<#component_ty, #tag>(); }
+            } else {
+                quote! { context.validate_component::<#component_ty>(); }
+            }
+        });
+
+    quote! { #(#validations)* }
+}
+
+/// Create the property initializers for the provider during module build
+fn provider_build(index: usize, provider: &ProviderItem) -> TokenStream {
+    let provider_ty = &provider.ty;
     let property = generate_name(index, "provider", provider_ty.span());
+    let scoped_property = generate_name(index, "scoped_provider", provider_ty.span());
 
-    quote! {
-        #property: context.provider_fn::<#provider_ty>()
+    if provider.is_async() {
+        quote! {
+            #property: context.async_provider_fn::<#provider_ty>()
+        }
+    } else {
+        quote! {
+            #property: context.provider_fn::<#provider_ty>(),
+            #scoped_property: context.scoped_provider_fn::<#provider_ty>()
+        }
     }
 }
 
-/// Create a list of statements to initialize the submodule variables during module build
+/// Create a list of statements to initialize the submodule variables during module build.
+/// The lifecycles of submodules which components are imported from are registered, so they are
+/// shut down with this module.
 fn submodules_init(submodules: &Punctuated<Submodule, syn::Token![,]>) -> TokenStream {
     if submodules.is_empty() {
         return TokenStream::new();
     }
 
     let names = submodule_names(submodules);
+    let register_lifecycles: Vec<TokenStream> = submodules
+        .iter()
+        .zip(&names)
+        .filter(|(submodule, _)| imports_services(submodule))
+        .map(|(_, name)| {
+            quote! {
+                let lifecycle = ::shaku::ModuleInstance::lifecycle(::std::sync::Arc::as_ref(&#name));
+                if let Some(lifecycle) = lifecycle {
+                    context.lifecycle().add_submodule(lifecycle);
+                }
+            }
+        })
+        .collect();
 
     quote! {
         let (#(#names),*) = context.submodules();
         #(
         let #names = ::std::sync::Arc::clone(#names);
         )*
+        #(#register_lifecycles)*
     }
 }
 
-/// Create the property which holds a component instance
-fn component_property(index: usize, component: &ComponentItem) -> TokenStream {
-    let property = generate_name(index, "component", component.ty.span());
-    let interface = interface_from_component(&component.ty);
-
-    if component.is_lazy() {
-        quote! {
-            #property: ::shaku::OnceCell<::std::sync::Arc<#interface>>
-        }
-    } else {
-        quote! {
-            #property: ::std::sync::Arc<#interface>
-        }
-    }
-}
-
-/// Create the property which holds a provider function
-fn provider_property(index: usize, provider_ty: &Type) -> TokenStream {
-    let property = generate_name(index, "provider", provider_ty.span());
-    let interface = interface_from_provider(provider_ty);
-
-    quote! {
-        #property: ::std::sync::Arc<::shaku::ProviderFn<Self, #interface>>
-    }
+/// Check if any services are imported from the submodule. Submodules which
+/// nothing is imported from may not implement ModuleInstance, since they may
+/// be trait objects.
+fn imports_services(submodule: &Submodule) -> bool {
+    !submodule.services.components.items.is_empty()
+        || !submodule.services.providers.items.is_empty()
 }
 
-/// Create the property which holds a submodule instance
-fn submodule_property(index: usize, submodule: &Submodule) -> TokenStream {
-    let property = generate_name(index, "submodule", submodule.ty.span());
-    let submodule_ty = &submodule.ty;
+/// Create the `dependency_graph` function, which adds this module's services
+/// to the graphs of the submodules which services are imported from. The
+/// services which were overridden when the module was built are marked.
+fn module_dependency_graph(module: &ModuleData) -> TokenStream {
+    let submodule_graphs: Vec<TokenStream> = module
+        .submodules
+        .iter()
+        .enumerate()
+        .filter_map(|(i, submodule)| {
+            let name = generate_name(i, "submodule", submodule.ty.span());
 
-    quote! {
-        #[allow(bare_trait_objects)]
-        #property: ::std::sync::Arc<#submodule_ty>
-    }
-}
+            if !imports_services(submodule) {
+                return None;
+            }
 
-/// Create a HasComponent impl
-fn has_component_impl(index: usize, component: &ComponentItem, module: &ModuleData) -> TokenStream {
-    let component_ty = &component.ty;
-    let property = generate_name(index, "component", component_ty.span());
-    let interface = interface_from_component(component_ty);
-    let module_name = &module.metadata.identifier;
-    let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+            Some(quote! {
+                let submodule_graph = ::shaku::ModuleInstance::module_graph(::std::sync::Arc::as_ref(&self.#name));
+                if let Some(submodule_graph) = submodule_graph {
+                    graph.merge(submodule_graph);
+                }
+            })
+        })
+        .collect();
 
-    let get_ref_code = if component.is_lazy() {
-        quote! {
-            let component = self.#property.get_or_init(|| {
-                let mut context = self.build_context.lock().unwrap();
-                <Self as ::shaku::HasComponent<#interface>>::build_component(&mut *context)
-            });
-        }
-    } else {
-        quote! { let component = &self.#property; }
-    };
+    let component_nodes: Vec<TokenStream> = module
+        .services
+        .components
+        .items
+        .iter()
+        .map(|component| {
+            let component_ty = &component.ty;
+            let interface = interface_from_component(component);
+            let lazy = component.is_lazy();
+            let named = match component.named() {
+                Some(tag) => quote! { Some(::std::any::type_name::<#tag>()) },
+                None => quote! { None },
+            };
+            let dependencies = if component.is_async() {
+                quote! { <#component_ty as ::shaku::AsyncComponent<Self>>::dependencies() }
+            } else {
+                quote! { <#component_ty as ::shaku::Component<Self>>::dependencies() }
+            };
 
-    quote! {
-        impl #impl_generics ::shaku::HasComponent<#interface> for #module_name #ty_generics #where_clause {
-            fn build_component(
-                context: &mut ::shaku::ModuleBuildContext<Self>
-            ) -> ::std::sync::Arc<#interface> {
-                context.build_component::<#component_ty>()
+            quote! {
+                graph.add_service(
+                    ::shaku::GraphNode {
+                        service: ::std::any::type_name::<#component_ty>(),
+                        interface: ::std::any::type_name::<#interface>(),
+                        named: #named,
+                        kind: ::shaku::ServiceKind::Component,
+                        lazy: #lazy,
+                        overridden: false,
+                        module: ::std::any::type_name::<Self>(),
+                    },
+                    #dependencies,
+                );
             }
+        })
+        .collect();
 
-            fn resolve(&self) -> ::std::sync::Arc<#interface> {
-                #get_ref_code
-                ::std::sync::Arc::clone(component)
-            }
+    let provider_nodes: Vec<TokenStream> = module
+        .services
+        .providers
+        .items
+        .iter()
+        .map(|provider| {
+            let provider_ty = &provider.ty;
+            let interface = interface_from_provider(provider);
+            let dependencies = if provider.is_async() {
+                quote! { <#provider_ty as ::shaku::AsyncProvider<Self>>::dependencies() }
+            } else {
+                quote! { <#provider_ty as ::shaku::Provider<Self>>::dependencies() }
+            };
 
-            fn resolve_ref(&self) -> &#interface {
-                #get_ref_code
-                ::std::sync::Arc::as_ref(component)
+            quote! {
+                graph.add_service(
+                    ::shaku::GraphNode {
+                        service: ::std::any::type_name::<#provider_ty>(),
+                        interface: ::std::any::type_name::<#interface>(),
+                        named: None,
+                        kind: ::shaku::ServiceKind::Provider,
+                        lazy: false,
+                        overridden: false,
+                        module: ::std::any::type_name::<Self>(),
+                    },
+                    #dependencies,
+                );
             }
+        })
+        .collect();
+
+    quote! {
+        fn dependency_graph(&self) -> ::shaku::DependencyGraph {
+            let mut graph = ::shaku::DependencyGraph::new();
+            #(#submodule_graphs)*
+            #(#component_nodes)*
+            #(#provider_nodes)*
+            graph.mark_overrides(::std::any::type_name::<Self>(), &self.__di_overrides);
+            graph
         }
     }
 }
 
-/// Create a HasProvider impl
-fn has_provider_impl(index: usize, provider_ty: &Type, module: &ModuleData) -> TokenStream {
-    let property = generate_name(index, "provider", provider_ty.span());
-    let interface = interface_from_provider(provider_ty);
+/// Create the `ConfigurableModule` impl, which deserializes the parameters of
+/// each component by name. The impl is bounded on the parameters being
+/// deserializable, so modules with other components still compile. Modules
+/// without components have nothing to configure, so they don't get the impl.
+#[cfg(feature = "serde")]
+fn configurable_module_impl(module: &ModuleData) -> TokenStream {
+    let components = &module.services.components.items;
+    if components.is_empty() {
+        return TokenStream::new();
+    }
+
     let module_name = &module.metadata.identifier;
-    let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+    let (_, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+    let mut generics = module.metadata.generics.clone();
+    generics.params.insert(0, syn::parse_quote!('__di_de));
+    let (impl_generics, _, _) = generics.split_for_impl();
+    let module_ty = quote! { #module_name #ty_generics };
 
-    quote! {
+    let names: Vec<String> = components.iter().map(component_config_name).collect();
+    let parameters_bounds: Vec<TokenStream> = components
+        .iter()
+        .map(|component| {
+            let component_ty = &component.ty;
+
+            if component.is_async() {
+                quote! {
+                    <#component_ty as ::shaku::AsyncComponent<#module_ty>>::Parameters:
+                        ::shaku::serde::Deserialize<'__di_de>
+                }
+            } else {
+                quote! {
+                    <#component_ty as ::shaku::Component<#module_ty>>::Parameters:
+                        ::shaku::serde::Deserialize<'__di_de>
+                }
+            }
+        })
+        .collect();
+    let where_predicates: Vec<&syn::WherePredicate> = where_clause
+        .map(|where_clause| where_clause.predicates.iter().collect())
+        .unwrap_or_default();
+
+    let set_parameters: Vec<TokenStream> = components
+        .iter()
+        .map(|component| {
+            let component_ty = &component.ty;
+
+            if component.is_async() {
+                quote! { builder.with_async_component_parameters::<#component_ty>(parameters) }
+            } else if component.is_keyed() {
+                quote! { builder.with_keyed_component_parameters::<#component_ty>(parameters) }
+            } else if component.is_multi() {
+                quote! { builder.with_multi_component_parameters::<#component_ty>(parameters) }
+            } else if let Some(tag) = component.named() {
+                quote! { builder.with_named_component_parameters::<#component_ty, #tag>(parameters) }
+            } else {
+                quote! { builder.with_component_parameters::<#component_ty>(parameters) }
+            }
+        })
+        .collect();
+
+    quote! {
+        impl #impl_generics ::shaku::ConfigurableModule<'__di_de> for #module_ty
+        where
+            #(#where_predicates,)*
+            #(#parameters_bounds,)*
+        {
+            #[allow(unreachable_patterns)]
+            fn with_component_parameters_from<__DiMap: ::shaku::serde::de::MapAccess<'__di_de>>(
+                builder: ::shaku::ModuleBuilder<Self>,
+                component: &str,
+                map: &mut __DiMap,
+            ) -> ::std::result::Result<::shaku::ModuleBuilder<Self>, __DiMap::Error> {
+                match component {
+                    #(#names => {
+                        let parameters = map.next_value()?;
+                        Ok(#set_parameters)
+                    })*
+                    _ => Err(::shaku::serde::de::Error::unknown_field(
+                        component,
+                        &[#(#names),*],
+                    )),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "serde"))]
+fn configurable_module_impl(_module: &ModuleData) -> TokenStream {
+    TokenStream::new()
+}
+
+/// Get the name of a component in the configuration passed to
+/// `ModuleBuilder::with_parameters_from`. This is the last segment of the
+/// component's path, followed by the tag of named components.
+#[cfg(feature = "serde")]
+fn component_config_name(component: &ComponentItem) -> String {
+    let name = last_segment_name(&component.ty);
+
+    match component.named() {
+        Some(tag) => format!("{}({})", name, last_segment_name(tag)),
+        None => name,
+    }
+}
+
+#[cfg(feature = "serde")]
+fn last_segment_name(ty: &Type) -> String {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => match path.path.segments.last() {
+            Some(segment) => segment.ident.to_string(),
+            None => quote!(#ty).to_string(),
+        },
+        _ => quote!(#ty).to_string(),
+    }
+}
+
+/// Create the property which holds a component instance
+fn component_property(index: usize, component: &ComponentItem) -> TokenStream {
+    let property = generate_name(index, "component", component.ty.span());
+    let interface = interface_from_component(component);
+
+    if component.is_lazy() && component.is_async() {
+        quote! {
+            #property: ::shaku::AsyncOnceCell<::std::sync::Arc<#interface>>
+        }
+    } else if component.is_lazy() {
+        quote! {
+            #property: ::shaku::OnceCell<::std::sync::Arc<#interface>>
+        }
+    } else if component.is_reloadable() {
+        quote! {
+            #property: ::shaku::ReloadableCell<#interface>
+        }
+    } else {
+        quote! {
+            #property: ::std::sync::Arc<#interface>
+        }
+    }
+}
+
+/// Create the properties which hold a provider's functions. Sync providers
+/// have a separate function to provide the service within a scope.
+fn provider_property(index: usize, provider: &ProviderItem) -> TokenStream {
+    let property = generate_name(index, "provider", provider.ty.span());
+    let scoped_property = generate_name(index, "scoped_provider", provider.ty.span());
+    let interface = interface_from_provider(provider);
+
+    if provider.is_async() {
+        quote! {
+            #property: ::std::sync::Arc<::shaku::AsyncProviderFn<Self, #interface>>
+        }
+    } else {
+        quote! {
+            #property: ::std::sync::Arc<::shaku::ProviderFn<Self, #interface>>,
+            #scoped_property: ::std::sync::Arc<::shaku::ScopedProviderFn<Self, #interface>>
+        }
+    }
+}
+
+/// Create the property which holds a submodule instance
+fn submodule_property(index: usize, submodule: &Submodule) -> TokenStream {
+    let property = generate_name(index, "submodule", submodule.ty.span());
+    let submodule_ty = &submodule.ty;
+
+    quote! {
+        #[allow(bare_trait_objects)]
+        #property: ::std::sync::Arc<#submodule_ty>
+    }
+}
+
+/// Create a HasComponent impl
+fn has_component_impl(
+    index: usize,
+    component: &ComponentItem,
+    module: &ModuleData,
+    async_build_context: bool,
+) -> TokenStream {
+    // Multi-bound components are resolved via their set
+    if component.is_multi() {
+        return TokenStream::new();
+    }
+
+    if component.is_async() {
+        return has_async_component_impl(index, component, module);
+    }
+
+    if let Some(tag) = component.named() {
+        return has_named_component_impl(index, component, tag, module, async_build_context);
+    }
+
+    let component_ty = &component.ty;
+    let property = generate_name(index, "component", component_ty.span());
+    let interface = interface_from_component(component);
+    let module_name = &module.metadata.identifier;
+    let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+
+    let lock_code = if async_build_context {
+        quote! { self.build_context.lock_blocking() }
+    } else {
+        quote! { self.build_context.lock().unwrap() }
+    };
+
+    let get_ref_code = if component.is_lazy() {
+        quote! {
+            let component = self.#property.get_or_init(|| {
+                ::shaku::trace_lazy_component::<#component_ty, #interface, _>(|| {
+                    let mut context = #lock_code;
+                    <Self as ::shaku::HasComponent<#interface>>::build_component(&mut *context)
+                })
+            });
+        }
+    } else {
+        quote! { let component = &self.#property; }
+    };
+
+    // Only the latest instance of a reloadable component is kept, so it
+    // can't be borrowed
+    let (resolve_code, resolve_ref_code) = if component.is_reloadable() {
+        (
+            quote! { self.#property.get() },
+            quote! {
+                panic!(
+                    "{} is reloadable, so it can't be borrowed. Use HasComponent::resolve to resolve it.",
+                    ::std::any::type_name::<#component_ty>()
+                )
+            },
+        )
+    } else {
+        (
+            quote! {
+                #get_ref_code
+                ::std::sync::Arc::clone(component)
+            },
+            quote! {
+                #get_ref_code
+                ::std::sync::Arc::as_ref(component)
+            },
+        )
+    };
+
+    let has_reloadable_component_impl = if component.is_reloadable() {
+        // The replacement is started and decorated like a built component.
+        // The build context stays locked until the replacement is stored, so
+        // concurrent replacements are stored in the same order everywhere.
+        quote! {
+            impl #impl_generics ::shaku::HasReloadableComponent<#interface> for #module_name #ty_generics #where_clause {
+                type Component = #component_ty;
+
+                fn replace_component(
+                    &self,
+                    component: ::std::boxed::Box<#component_ty>
+                ) -> ::std::sync::Arc<#interface> {
+                    let component: ::std::sync::Arc<#component_ty> = ::std::sync::Arc::from(component);
+                    <#component_ty as ::shaku::Component<Self>>::start(&component, &self.__di_lifecycle);
+                    let mut context = #lock_code;
+                    let component = context.replace_resolved_component::<#interface>(component);
+                    self.#property.replace(component)
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    quote! {
+        impl #impl_generics ::shaku::HasComponent<#interface> for #module_name #ty_generics #where_clause {
+            fn build_component(
+                context: &mut ::shaku::ModuleBuildContext<Self>
+            ) -> ::std::sync::Arc<#interface> {
+                context.build_component::<#component_ty>()
+            }
+
+            fn try_build_component(
+                context: &mut ::shaku::ModuleBuildContext<Self>
+            ) -> ::std::result::Result<::std::sync::Arc<#interface>, ::shaku::BuildError> {
+                context.try_build_component::<#component_ty>()
+            }
+
+            fn resolve(&self) -> ::std::sync::Arc<#interface> {
+                #resolve_code
+            }
+
+            fn resolve_ref(&self) -> &#interface {
+                #resolve_ref_code
+            }
+        }
+
+        #has_reloadable_component_impl
+    }
+}
+
+/// Create a HasNamedComponent impl
+fn has_named_component_impl(
+    index: usize,
+    component: &ComponentItem,
+    tag: &Type,
+    module: &ModuleData,
+    async_build_context: bool,
+) -> TokenStream {
+    let component_ty = &component.ty;
+    let property = generate_name(index, "component", component_ty.span());
+    let interface = interface_from_component(component);
+    let module_name = &module.metadata.identifier;
+    let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+
+    let lock_code = if async_build_context {
+        quote! { self.build_context.lock_blocking() }
+    } else {
+        quote! { self.build_context.lock().unwrap() }
+    };
+
+    let get_ref_code = if component.is_lazy() {
+        quote! {
+            let component = self.#property.get_or_init(|| {
+                ::shaku::trace_lazy_component::<#component_ty, #interface, _>(|| {
+                    let mut context = #lock_code;
+                    <Self as ::shaku::HasNamedComponent<#interface, #tag>>::build_named_component(&mut *context)
+                })
+            });
+        }
+    } else {
+        quote! { let component = &self.#property; }
+    };
+
+    quote! {
+        impl #impl_generics ::shaku::HasNamedComponent<#interface, #tag> for #module_name #ty_generics #where_clause {
+            fn build_named_component(
+                context: &mut ::shaku::ModuleBuildContext<Self>
+            ) -> ::std::sync::Arc<#interface> {
+                context.build_named_component::<#component_ty, #tag>()
+            }
+
+            fn try_build_named_component(
+                context: &mut ::shaku::ModuleBuildContext<Self>
+            ) -> ::std::result::Result<::std::sync::Arc<#interface>, ::shaku::BuildError> {
+                context.try_build_named_component::<#component_ty, #tag>()
+            }
+
+            fn resolve_named(&self) -> ::std::sync::Arc<#interface> {
+                #get_ref_code
+                ::std::sync::Arc::clone(component)
+            }
+
+            fn resolve_named_ref(&self) -> &#interface {
+                #get_ref_code
+                ::std::sync::Arc::as_ref(component)
+            }
+        }
+    }
+}
+
+/// Get the interfaces of the sets which the module or its submodules
+/// contribute multi-bound components to, in order of first appearance
+fn multi_interfaces(module: &ModuleData) -> Vec<&Type> {
+    let local_interfaces = module
+        .services
+        .components
+        .items
+        .iter()
+        .filter_map(ComponentItem::set_interface);
+    let submodule_interfaces = module
+        .submodules
+        .iter()
+        .flat_map(|submodule| submodule.services.components.items.iter())
+        .filter(|component| component.is_multi())
+        .map(|component| &component.ty);
+
+    let mut interfaces = Vec::new();
+    for interface in local_interfaces.chain(submodule_interfaces) {
+        if !interfaces.contains(&interface) {
+            interfaces.push(interface);
+        }
+    }
+
+    interfaces
+}
+
+/// Create a HasComponents impl, which contains the module's own multi-bound
+/// components of the interface followed by the submodules' ones
+fn has_components_impl(interface: &Type, module: &ModuleData) -> TokenStream {
+    let module_name = &module.metadata.identifier;
+    let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+
+    let local_components: Vec<(Ident, &Type)> = module
+        .services
+        .components
+        .items
+        .iter()
+        .enumerate()
+        .filter(|(_, component)| component.set_interface() == Some(interface))
+        .map(|(i, component)| {
+            let property = generate_name(i, "component", component.ty.span());
+            (property, &component.ty)
+        })
+        .collect();
+    let properties: Vec<&Ident> = local_components.iter().map(|(p, _)| p).collect();
+    let component_tys: Vec<&Type> = local_components.iter().map(|(_, ty)| *ty).collect();
+
+    let submodule_names = submodule_names(&module.submodules);
+    let contributing_submodules: Vec<&Ident> = module
+        .submodules
+        .iter()
+        .zip(&submodule_names)
+        .filter(|(submodule, _)| {
+            submodule
+                .services
+                .components
+                .items
+                .iter()
+                .any(|component| component.is_multi() && component.ty == *interface)
+        })
+        .map(|(_, name)| name)
+        .collect();
+
+    let get_submodules = if contributing_submodules.is_empty() {
+        TokenStream::new()
+    } else {
+        quote! { let (#(#submodule_names),*) = context.submodules(); }
+    };
+
+    quote! {
+        #[allow(bare_trait_objects)]
+        impl #impl_generics ::shaku::HasComponents<#interface> for #module_name #ty_generics #where_clause {
+            fn build_components(
+                context: &mut ::shaku::ModuleBuildContext<Self>
+            ) -> ::std::vec::Vec<::std::sync::Arc<#interface>> {
+                #[allow(unused_mut)]
+                let mut components: ::std::vec::Vec<::std::sync::Arc<#interface>> = vec![
+                    #(context.build_multi_component::<#component_tys>()),*
+                ];
+                #get_submodules
+                #(
+                components.extend(::shaku::HasComponents::<#interface>::resolve_all(
+                    ::std::sync::Arc::as_ref(#contributing_submodules)
+                ));
+                )*
+
+                components
+            }
+
+            fn try_build_components(
+                context: &mut ::shaku::ModuleBuildContext<Self>
+            ) -> ::std::result::Result<::std::vec::Vec<::std::sync::Arc<#interface>>, ::shaku::BuildError> {
+                #[allow(unused_mut)]
+                let mut components: ::std::vec::Vec<::std::sync::Arc<#interface>> = vec![
+                    #(context.try_build_multi_component::<#component_tys>()?),*
+                ];
+                #get_submodules
+                #(
+                components.extend(::shaku::HasComponents::<#interface>::resolve_all(
+                    ::std::sync::Arc::as_ref(#contributing_submodules)
+                ));
+                )*
+
+                Ok(components)
+            }
+
+            fn resolve_all(&self) -> ::std::vec::Vec<::std::sync::Arc<#interface>> {
+                #[allow(unused_mut)]
+                let mut components: ::std::vec::Vec<::std::sync::Arc<#interface>> = vec![
+                    #(::std::sync::Arc::clone(&self.#properties)),*
+                ];
+                #(
+                components.extend(::shaku::HasComponents::<#interface>::resolve_all(
+                    ::std::sync::Arc::as_ref(&self.#contributing_submodules)
+                ));
+                )*
+
+                components
+            }
+        }
+    }
+}
+
+/// Get the distinct interfaces of the module's maps, including the maps
+/// imported from submodules
+fn map_interfaces(module: &ModuleData) -> Vec<&Type> {
+    let local_interfaces = module
+        .services
+        .components
+        .items
+        .iter()
+        .filter_map(ComponentItem::map_interface);
+    let submodule_interfaces = module
+        .submodules
+        .iter()
+        .flat_map(|submodule| submodule.services.components.items.iter())
+        .filter(|component| component.is_keyed())
+        .map(|component| &component.ty);
+
+    let mut interfaces = Vec::new();
+    for interface in local_interfaces.chain(submodule_interfaces) {
+        if !interfaces.contains(&interface) {
+            interfaces.push(interface);
+        }
+    }
+
+    interfaces
+}
+
+/// Get the names of the submodules which a map of the interface is imported
+/// from
+fn map_submodule_names(interface: &Type, module: &ModuleData) -> Vec<Ident> {
+    module
+        .submodules
+        .iter()
+        .zip(submodule_names(&module.submodules))
+        .filter(|(submodule, _)| {
+            submodule
+                .services
+                .components
+                .items
+                .iter()
+                .any(|component| component.is_keyed() && component.ty == *interface)
+        })
+        .map(|(_, name)| name)
+        .collect()
+}
+
+/// Create a HasComponentMap impl, which contains the module's own keyed
+/// components of the interface and the submodules' ones. Duplicate keys
+/// between the maps are reported when the map is built.
+fn has_component_map_impl(interface: &Type, module: &ModuleData) -> TokenStream {
+    let module_name = &module.metadata.identifier;
+    let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+
+    let local_components: Vec<(Ident, &Type, &LitStr)> = module
+        .services
+        .components
+        .items
+        .iter()
+        .enumerate()
+        .filter(|(_, component)| component.map_interface() == Some(interface))
+        .map(|(i, component)| {
+            let property = generate_name(i, "component", component.ty.span());
+            // Keyed components always have a key, see expand_module_macro
+            (property, &component.ty, component.key().unwrap())
+        })
+        .collect();
+    let properties: Vec<&Ident> = local_components.iter().map(|(p, _, _)| p).collect();
+    let component_tys: Vec<&Type> = local_components.iter().map(|(_, ty, _)| *ty).collect();
+    let keys: Vec<&LitStr> = local_components.iter().map(|(_, _, key)| *key).collect();
+
+    let submodule_names = submodule_names(&module.submodules);
+    let contributing_submodules = map_submodule_names(interface, module);
+
+    let get_submodules = if contributing_submodules.is_empty() {
+        TokenStream::new()
+    } else {
+        quote! { let (#(#submodule_names),*) = context.submodules(); }
+    };
+
+    quote! {
+        #[allow(bare_trait_objects)]
+        impl #impl_generics ::shaku::HasComponentMap<#interface> for #module_name #ty_generics #where_clause {
+            fn build_component_map(
+                context: &mut ::shaku::ModuleBuildContext<Self>
+            ) -> ::std::collections::HashMap<&'static str, ::std::sync::Arc<#interface>> {
+                <Self as ::shaku::HasComponentMap<#interface>>::try_build_component_map(context)
+                    .unwrap_or_else(|error| panic!("{}", error))
+            }
+
+            fn try_build_component_map(
+                context: &mut ::shaku::ModuleBuildContext<Self>
+            ) -> ::std::result::Result<
+                ::std::collections::HashMap<&'static str, ::std::sync::Arc<#interface>>,
+                ::shaku::BuildError
+            > {
+                #[allow(unused_mut)]
+                let mut components = ::std::collections::HashMap::new();
+                #(
+                components.insert(#keys, context.try_build_keyed_component::<#component_tys>()?);
+                )*
+                #get_submodules
+                #(
+                let submodule_components = ::shaku::HasComponentMap::<#interface>::resolve_map(
+                    ::std::sync::Arc::as_ref(#contributing_submodules)
+                );
+                for (key, component) in submodule_components {
+                    if components.insert(key, component).is_some() {
+                        return Err(::shaku::BuildError::DuplicateKey {
+                            interface: ::std::any::type_name::<#interface>(),
+                            key,
+                        });
+                    }
+                }
+                )*
+
+                Ok(components)
+            }
+
+            fn resolve_map(&self) -> ::std::collections::HashMap<&'static str, ::std::sync::Arc<#interface>> {
+                #[allow(unused_mut)]
+                let mut components = ::std::collections::HashMap::new();
+                #(
+                components.insert(#keys, ::std::sync::Arc::clone(&self.#properties));
+                )*
+                #(
+                components.extend(::shaku::HasComponentMap::<#interface>::resolve_map(
+                    ::std::sync::Arc::as_ref(&self.#contributing_submodules)
+                ));
+                )*
+
+                components
+            }
+
+            fn resolve_keyed(&self, key: &str) -> ::std::option::Option<::std::sync::Arc<#interface>> {
+                match key {
+                    #(
+                    #keys => return Some(::std::sync::Arc::clone(&self.#properties)),
+                    )*
+                    _ => {}
+                }
+                #(
+                if let Some(component) = ::shaku::HasComponentMap::<#interface>::resolve_keyed(
+                    ::std::sync::Arc::as_ref(&self.#contributing_submodules),
+                    key
+                ) {
+                    return Some(component);
+                }
+                )*
+
+                None
+            }
+        }
+    }
+}
+
+/// Create a HasComponent impl for an async component
+fn has_async_component_impl(
+    index: usize,
+    component: &ComponentItem,
+    module: &ModuleData,
+) -> TokenStream {
+    let component_ty = &component.ty;
+    let property = generate_name(index, "component", component_ty.span());
+    let interface = interface_from_component(component);
+    let module_name = &module.metadata.identifier;
+    let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+
+    // Lazy async components can only be initialized by resolve_async
+    let (get_ref_code, resolve_async) = if component.is_lazy() {
+        let get_ref_code = quote! {
+            let component = self.#property.get().unwrap_or_else(|| panic!(
+                "{} has not been initialized yet. Use HasComponent::resolve_async to resolve it.",
+                ::std::any::type_name::<#component_ty>()
+            ));
+        };
+        let resolve_async = quote! {
+            fn resolve_async(&self) -> ::shaku::BoxFuture<'_, ::std::sync::Arc<#interface>> {
+                Box::pin(async move {
+                    let component = self.#property.get_or_init(|| {
+                        ::shaku::trace_lazy_component_async::<#component_ty, #interface, _>(Box::pin(async {
+                            let mut context = self.build_context.lock().await;
+                            <Self as ::shaku::HasComponent<#interface>>::build_component_async(&mut *context)
+                                .await
+                                .unwrap_or_else(|error| panic!("{}", error))
+                        }))
+                    }).await;
+
+                    ::std::sync::Arc::clone(component)
+                })
+            }
+        };
+
+        (get_ref_code, Some(resolve_async))
+    } else {
+        (quote! { let component = &self.#property; }, None)
+    };
+
+    quote! {
+        impl #impl_generics ::shaku::HasComponent<#interface> for #module_name #ty_generics #where_clause {
+            fn build_component(
+                context: &mut ::shaku::ModuleBuildContext<Self>
+            ) -> ::std::sync::Arc<#interface> {
+                context.try_get_async_component::<#component_ty>()
+                    .unwrap_or_else(|error| panic!("{}", error))
+            }
+
+            fn try_build_component(
+                context: &mut ::shaku::ModuleBuildContext<Self>
+            ) -> ::std::result::Result<::std::sync::Arc<#interface>, ::shaku::BuildError> {
+                context.try_get_async_component::<#component_ty>()
+            }
+
+            fn build_component_async(
+                context: &mut ::shaku::ModuleBuildContext<Self>
+            ) -> ::shaku::BoxFuture<'_, ::std::result::Result<::std::sync::Arc<#interface>, ::shaku::BuildError>> {
+                context.build_async_component::<#component_ty>()
+            }
+
+            fn resolve(&self) -> ::std::sync::Arc<#interface> {
+                #get_ref_code
+                ::std::sync::Arc::clone(component)
+            }
+
+            fn resolve_ref(&self) -> &#interface {
+                #get_ref_code
+                ::std::sync::Arc::as_ref(component)
+            }
+
+            #resolve_async
+        }
+    }
+}
+
+/// Create a HasProvider (or HasAsyncProvider) impl. Scoped providers also get
+/// a HasScopedProvider impl.
+fn has_provider_impl(index: usize, provider: &ProviderItem, module: &ModuleData) -> TokenStream {
+    let property = generate_name(index, "provider", provider.ty.span());
+    let scoped_property = generate_name(index, "scoped_provider", provider.ty.span());
+    let interface = interface_from_provider(provider);
+    let module_name = &module.metadata.identifier;
+    let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+
+    if provider.is_async() {
+        return quote! {
+            impl #impl_generics ::shaku::HasAsyncProvider<#interface> for #module_name #ty_generics #where_clause {
+                fn provide_async(&self) -> ::shaku::BoxFuture<'_, ::std::result::Result<
+                    ::std::boxed::Box<#interface>,
+                    ::std::boxed::Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync>
+                >> {
+                    (self.#property)(self)
+                }
+            }
+        };
+    }
+
+    let has_scoped_provider_impl = if provider.is_scoped() {
+        quote! {
+            impl #impl_generics ::shaku::HasScopedProvider<#interface> for #module_name #ty_generics #where_clause {}
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    quote! {
         impl #impl_generics ::shaku::HasProvider<#interface> for #module_name #ty_generics #where_clause {
             fn provide(&self) -> ::std::result::Result<
                 ::std::boxed::Box<#interface>,
@@ -357,23 +1662,87 @@ fn has_provider_impl(index: usize, provider_ty: &Type, module: &ModuleData) -> T
             > {
                 (self.#property)(self)
             }
+
+            fn provide_scoped(scope: &::shaku::Scope<Self>) -> ::std::result::Result<
+                ::std::boxed::Box<#interface>,
+                ::std::boxed::Box<dyn ::std::error::Error>
+            > {
+                (scope.module().#scoped_property)(scope)
+            }
         }
+
+        #has_scoped_provider_impl
     }
 }
 
-/// Create a HasComponent impl for a subcomponent
+/// Create a HasComponent (or HasNamedComponent) impl for a subcomponent
 fn has_subcomponent_impl(
     submodule_index: usize,
     submodule: &Submodule,
-    component_ty: &Type,
+    component: &ComponentItem,
     module: &ModuleData,
 ) -> TokenStream {
+    let component_ty = &component.ty;
     let module_name = &module.metadata.identifier;
     let submodule_ty = &submodule.ty;
     let submodule_names = submodule_names(&module.submodules);
     let submodule_name = generate_name(submodule_index, "submodule", submodule_ty.span());
     let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
 
+    // Multi-bound and keyed subcomponents are resolved via their set or map
+    if component.is_multi() || component.is_keyed() {
+        return TokenStream::new();
+    }
+
+    if let Some(tag) = component.named() {
+        return quote! {
+            #[allow(bare_trait_objects)]
+            impl #impl_generics ::shaku::HasNamedComponent<#component_ty, #tag> for #module_name #ty_generics #where_clause {
+                fn build_named_component(
+                    context: &mut ::shaku::ModuleBuildContext<Self>
+                ) -> ::std::sync::Arc<#component_ty> {
+                    let (#(#submodule_names),*) = context.submodules();
+                    ::shaku::HasNamedComponent::<#component_ty, #tag>::resolve_named(
+                        ::std::sync::Arc::as_ref(#submodule_name)
+                    )
+                }
+
+                fn resolve_named(&self) -> ::std::sync::Arc<#component_ty> {
+                    ::shaku::HasNamedComponent::<#component_ty, #tag>::resolve_named(
+                        ::std::sync::Arc::as_ref(&self.#submodule_name)
+                    )
+                }
+
+                fn resolve_named_ref(&self) -> &#component_ty {
+                    ::shaku::HasNamedComponent::<#component_ty, #tag>::resolve_named_ref(
+                        ::std::sync::Arc::as_ref(&self.#submodule_name)
+                    )
+                }
+            }
+        };
+    }
+
+    let has_reloadable_component_impl = if component.is_reloadable() {
+        quote! {
+            #[allow(bare_trait_objects)]
+            impl #impl_generics ::shaku::HasReloadableComponent<#component_ty> for #module_name #ty_generics #where_clause {
+                type Component = <#submodule_ty as ::shaku::HasReloadableComponent<#component_ty>>::Component;
+
+                fn replace_component(
+                    &self,
+                    component: ::std::boxed::Box<Self::Component>
+                ) -> ::std::sync::Arc<#component_ty> {
+                    ::shaku::HasReloadableComponent::<#component_ty>::replace_component(
+                        ::std::sync::Arc::as_ref(&self.#submodule_name),
+                        component
+                    )
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
     quote! {
         #[allow(bare_trait_objects)]
         impl #impl_generics ::shaku::HasComponent<#component_ty> for #module_name #ty_generics #where_clause {
@@ -381,32 +1750,70 @@ fn has_subcomponent_impl(
                 context: &mut ::shaku::ModuleBuildContext<Self>
             ) -> ::std::sync::Arc<#component_ty> {
                 let (#(#submodule_names),*) = context.submodules();
-                #submodule_name.resolve()
+                ::shaku::HasComponent::<#component_ty>::resolve(
+                    ::std::sync::Arc::as_ref(#submodule_name)
+                )
+            }
+
+            fn try_build_component(
+                context: &mut ::shaku::ModuleBuildContext<Self>
+            ) -> ::std::result::Result<::std::sync::Arc<#component_ty>, ::shaku::BuildError> {
+                let (#(#submodule_names),*) = context.submodules();
+                ::shaku::HasComponent::<#component_ty>::try_resolve(
+                    ::std::sync::Arc::as_ref(#submodule_name)
+                )
             }
 
             fn resolve(&self) -> ::std::sync::Arc<#component_ty> {
-                self.#submodule_name.resolve()
+                ::shaku::HasComponent::<#component_ty>::resolve(
+                    ::std::sync::Arc::as_ref(&self.#submodule_name)
+                )
             }
 
             fn resolve_ref(&self) -> &#component_ty {
-                self.#submodule_name.resolve_ref()
+                ::shaku::HasComponent::<#component_ty>::resolve_ref(
+                    ::std::sync::Arc::as_ref(&self.#submodule_name)
+                )
+            }
+
+            fn try_resolve(&self) -> ::std::result::Result<::std::sync::Arc<#component_ty>, ::shaku::BuildError> {
+                ::shaku::HasComponent::<#component_ty>::try_resolve(
+                    ::std::sync::Arc::as_ref(&self.#submodule_name)
+                )
             }
         }
+
+        #has_reloadable_component_impl
     }
 }
 
-/// Create a HasProvider impl for a subprovider
+/// Create a HasProvider (or HasAsyncProvider) impl for a subprovider
 fn has_subprovider_impl(
     submodule_index: usize,
     submodule: &Submodule,
-    provider_ty: &Type,
+    provider: &ProviderItem,
     module: &ModuleData,
 ) -> TokenStream {
+    let provider_ty = &provider.ty;
     let module_name = &module.metadata.identifier;
     let submodule_ty = &submodule.ty;
     let submodule_name = generate_name(submodule_index, "submodule", submodule_ty.span());
     let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
 
+    if provider.is_async() {
+        return quote! {
+            #[allow(bare_trait_objects)]
+            impl #impl_generics ::shaku::HasAsyncProvider<#provider_ty> for #module_name #ty_generics #where_clause {
+                fn provide_async(&self) -> ::shaku::BoxFuture<'_, ::std::result::Result<
+                    ::std::boxed::Box<#provider_ty>,
+                    ::std::boxed::Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync>
+                >> {
+                    ::shaku::HasAsyncProvider::provide_async(::std::sync::Arc::as_ref(&self.#submodule_name))
+                }
+            }
+        };
+    }
+
     quote! {
         #[allow(bare_trait_objects)]
         impl #impl_generics ::shaku::HasProvider<#provider_ty> for #module_name #ty_generics #where_clause {
@@ -420,17 +1827,122 @@ fn has_subprovider_impl(
     }
 }
 
-/// Get the interface type of a component via projection
-fn interface_from_component(component_ty: &Type) -> TokenStream {
+/// Create the ResolveAny impl, which resolves the components of the module
+/// and its submodules by the TypeId of their interface
+fn resolve_any_impl(module: &ModuleData) -> TokenStream {
+    let module_name = &module.metadata.identifier;
+    let (impl_generics, ty_generics, where_clause) = module.metadata.generics.split_for_impl();
+
+    // Named and multi-bound components are not resolved by their interface
+    let components = module
+        .services
+        .components
+        .items
+        .iter()
+        .enumerate()
+        .filter(|(_, component)| !component.is_multi() && component.named().is_none())
+        .map(|(index, component)| {
+            let interface = interface_from_component(component);
+
+            // Lazy async components can't be built synchronously, so they are
+            // only returned once they are initialized
+            let resolve = if component.is_lazy() && component.is_async() {
+                let property = generate_name(index, "component", component.ty.span());
+                quote! {
+                    self.#property.get().map(|component| {
+                        ::std::sync::Arc::new(::std::sync::Arc::clone(component)) as ::shaku::AnyComponent
+                    })
+                }
+            } else {
+                resolve_any_component(&interface)
+            };
+
+            (interface, resolve)
+        });
+    let subcomponents = module
+        .submodules
+        .iter()
+        .flat_map(|submodule| submodule.services.components.items.iter())
+        .filter(|component| {
+            !component.is_multi() && !component.is_keyed() && component.named().is_none()
+        })
+        .map(|component| {
+            let interface = &component.ty;
+            let interface = quote! { #interface };
+            let resolve = resolve_any_component(&interface);
+
+            (interface, resolve)
+        });
+    let (interfaces, resolves): (Vec<TokenStream>, Vec<TokenStream>) =
+        components.chain(subcomponents).unzip();
+
     quote! {
-        <#component_ty as ::shaku::Component<Self>>::Interface
+        #[allow(bare_trait_objects)]
+        impl #impl_generics ::shaku::ResolveAny for #module_name #ty_generics #where_clause {
+            fn resolve_any(
+                &self,
+                interface: ::std::any::TypeId
+            ) -> ::std::option::Option<::shaku::AnyComponent> {
+                #(
+                if interface == ::std::any::TypeId::of::<#interfaces>() {
+                    return #resolves;
+                }
+                )*
+
+                None
+            }
+
+            fn interfaces(&self) -> ::std::vec::Vec<::shaku::InterfaceInfo> {
+                vec![#(::shaku::InterfaceInfo::of::<#interfaces>()),*]
+            }
+        }
     }
 }
 
-/// Get the interface type of a provider via projection
-fn interface_from_provider(provider_ty: &Type) -> TokenStream {
+/// Get the tag a component is resolved under by its interface
+fn component_tag(component: &ComponentItem) -> TokenStream {
+    match component.named() {
+        Some(tag) => quote! { #tag },
+        None => quote! { ::shaku::Untagged },
+    }
+}
+
+/// Resolve a component of the interface as an `AnyComponent`
+fn resolve_any_component(interface: &TokenStream) -> TokenStream {
     quote! {
-        <#provider_ty as ::shaku::Provider<Self>>::Interface
+        Some(::std::sync::Arc::new(
+            <Self as ::shaku::HasComponent<#interface>>::resolve(self)
+        ))
+    }
+}
+
+/// Get the interface type of a component via projection
+fn interface_from_component(component: &ComponentItem) -> TokenStream {
+    let component_ty = &component.ty;
+
+    if component.is_async() {
+        quote! {
+            <#component_ty as ::shaku::AsyncComponent<Self>>::Interface
+        }
+    } else {
+        quote! {
+            <#component_ty as ::shaku::Component<Self>>::Interface
+        }
+    }
+}
+
+/// Get the interface type of a provider via projection
+fn interface_from_provider(provider: &ProviderItem) -> TokenStream {
+    let provider_ty = &provider.ty;
+
+    if provider.is_async() {
+        quote! {
+            <#provider_ty as ::shaku::AsyncProvider<Self>>::Interface
+        }
+    } else {
+        quote! {
+            <#provider_ty as ::shaku::Provider<Self>>::Interface
+        }
     }
 }
 
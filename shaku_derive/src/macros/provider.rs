@@ -1,7 +1,9 @@
 //! Implementation of the `#[derive(Provider)]` procedural macro
 
 use crate::debug::get_debug_level;
-use crate::macros::common_output::create_dependency;
+use crate::macros::common_output::{
+    check_provider_metadata, create_dependencies_fn, create_dependency, create_resolve_component,
+};
 use crate::structures::service::{Property, PropertyType, ServiceData};
 use proc_macro2::TokenStream;
 use syn::{DeriveInput, Error};
@@ -14,6 +16,8 @@ pub fn expand_derive_provider(input: &DeriveInput) -> syn::Result<TokenStream> {
         println!("Service data parsed from Provider input: {:#?}", service);
     }
 
+    check_provider_metadata(&service.metadata)?;
+
     let resolve_properties: Vec<TokenStream> = service
         .properties
         .iter()
@@ -26,18 +30,38 @@ pub fn expand_derive_provider(input: &DeriveInput) -> syn::Result<TokenStream> {
         .filter_map(create_dependency)
         .collect();
 
+    let dependencies_fn = create_dependencies_fn(&service);
+
     // Provider implementation
     let provider_name = service.metadata.identifier;
     let interface = service.metadata.interface;
     let (_, generic_tys, generic_where) = service.metadata.generics.split_for_impl();
     let generic_impls_no_parens = &service.metadata.generics.params;
-    let output = quote! {
-        impl<
-            M: ::shaku::Module #(+ #dependencies)*,
-            #generic_impls_no_parens
-        > ::shaku::Provider<M> for #provider_name #generic_tys #generic_where {
-            type Interface = dyn #interface;
 
+    // Providers with provided dependencies are always provided within a scope,
+    // so their scoped dependencies are shared
+    let has_provided_properties = service.properties.iter().any(Property::is_provided);
+    let provide_fns = if has_provided_properties {
+        quote! {
+            fn provide(module: &M) -> ::std::result::Result<
+                Box<Self::Interface>,
+                Box<dyn ::std::error::Error>
+            > {
+                Self::provide_scoped(&::shaku::Scope::new(module))
+            }
+
+            fn provide_scoped(scope: &::shaku::Scope<M>) -> ::std::result::Result<
+                Box<Self::Interface>,
+                Box<dyn ::std::error::Error>
+            > {
+                let module = scope.module();
+                Ok(Box::new(Self {
+                    #(#resolve_properties),*
+                }))
+            }
+        }
+    } else {
+        quote! {
             fn provide(module: &M) -> ::std::result::Result<
                 Box<Self::Interface>,
                 Box<dyn ::std::error::Error>
@@ -49,6 +73,19 @@ pub fn expand_derive_provider(input: &DeriveInput) -> syn::Result<TokenStream> {
         }
     };
 
+    let output = quote! {
+        impl<
+            M: ::shaku::Module #(+ #dependencies)*,
+            #generic_impls_no_parens
+        > ::shaku::Provider<M> for #provider_name #generic_tys #generic_where {
+            type Interface = dyn #interface;
+
+            #provide_fns
+
+            #dependencies_fn
+        }
+    };
+
     if debug_level > 0 {
         println!("{}", output);
     }
@@ -60,11 +97,20 @@ fn create_property_assignment(property: &Property) -> syn::Result<TokenStream> {
     let property_name = &property.property_name;
 
     match property.property_type {
-        PropertyType::Component => Ok(quote! {
-            #property_name: module.resolve()
-        }),
-        PropertyType::Provided => Ok(quote! {
-            #property_name: module.provide()?
+        PropertyType::Component | PropertyType::Components | PropertyType::ComponentMap => {
+            let resolve_component = create_resolve_component(property);
+            Ok(quote! {
+                #property_name: #resolve_component
+            })
+        }
+        PropertyType::Provided => {
+            let property_ty = &property.ty;
+            Ok(quote! {
+                #property_name: <M as ::shaku::HasProvider<#property_ty>>::provide_scoped(scope)?
+            })
+        }
+        PropertyType::Scoped => Ok(quote! {
+            #property_name: scope.provide()?
         }),
         PropertyType::Parameter => Err(Error::new(
             property.property_name.span(),
@@ -1,13 +1,85 @@
 use crate::consts;
-use crate::parser::{get_shaku_attribute, KeyValue, Parser};
+use crate::parser::Parser;
 use crate::structures::service::MetaData;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::{DeriveInput, Error, Type};
+use syn::{DeriveInput, Error, Expr, ExprPath, Ident, Type};
+
+/// A key-value pair in a `#[shaku(...)]` attribute on the service struct
+enum ServiceAttribute {
+    Interface(Type),
+    TryBuild(ExprPath),
+    OnStart(ExprPath),
+    OnStop(ExprPath),
+    Validate(ExprPath),
+    Unknown(Ident),
+}
+
+impl Parse for ServiceAttribute {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key: Ident = input.parse()?;
+        input.parse::<syn::Token![=]>()?;
+
+        if key == consts::INTERFACE_ATTR_NAME {
+            Ok(ServiceAttribute::Interface(input.parse()?))
+        } else if key == consts::TRY_BUILD_ATTR_NAME {
+            Ok(ServiceAttribute::TryBuild(input.parse()?))
+        } else if key == consts::ON_START_ATTR_NAME {
+            Ok(ServiceAttribute::OnStart(input.parse()?))
+        } else if key == consts::ON_STOP_ATTR_NAME {
+            Ok(ServiceAttribute::OnStop(input.parse()?))
+        } else if key == consts::VALIDATE_ATTR_NAME {
+            Ok(ServiceAttribute::Validate(input.parse()?))
+        } else {
+            input.parse::<Expr>()?;
+            Ok(ServiceAttribute::Unknown(key))
+        }
+    }
+}
 
 impl Parser<MetaData> for DeriveInput {
     fn parse_as(&self) -> syn::Result<MetaData> {
-        // Find the shaku(interface = ?) attribute
-        let shaku_attribute = get_shaku_attribute(&self.attrs).ok_or_else(|| {
+        let mut interface = None;
+        let mut try_build = None;
+        let mut on_start = None;
+        let mut on_stop = None;
+        let mut validate = None;
+        let mut unknown_key = None;
+
+        // Parse all of the #[shaku(key = value, ...)] attributes
+        for shaku_attribute in self
+            .attrs
+            .iter()
+            .filter(|a| a.path.is_ident(consts::ATTR_NAME))
+        {
+            let service_attributes = shaku_attribute
+                .parse_args_with(Punctuated::<ServiceAttribute, syn::Token![,]>::parse_terminated)
+                .map_err(|_| {
+                    Error::new(
+                        shaku_attribute.span(),
+                        format!(
+                            "Invalid attribute format. The attribute must be in name-value form. \
+                             Example: #[{}({} = <your trait>)]",
+                            consts::ATTR_NAME,
+                            consts::INTERFACE_ATTR_NAME
+                        ),
+                    )
+                })?;
+
+            for service_attribute in service_attributes {
+                match service_attribute {
+                    ServiceAttribute::Interface(ty) => interface = Some(ty),
+                    ServiceAttribute::TryBuild(path) => try_build = Some(path),
+                    ServiceAttribute::OnStart(path) => on_start = Some(path),
+                    ServiceAttribute::OnStop(path) => on_stop = Some(path),
+                    ServiceAttribute::Validate(path) => validate = Some(path),
+                    ServiceAttribute::Unknown(key) => unknown_key = unknown_key.or(Some(key)),
+                }
+            }
+        }
+
+        let interface = interface.ok_or_else(|| {
             Error::new(
                 self.ident.span(),
                 format!(
@@ -18,35 +90,22 @@ impl Parser<MetaData> for DeriveInput {
             )
         })?;
 
-        // Get the interface key/value
-        let interface_kv: KeyValue<Type> = shaku_attribute.parse_args().map_err(|_| {
-            Error::new(
-                shaku_attribute.span(),
-                format!(
-                    "Invalid attribute format. The attribute must be in name-value form. \
-                     Example: #[{}({} = <your trait>)]",
-                    consts::ATTR_NAME,
-                    consts::INTERFACE_ATTR_NAME
-                ),
-            )
-        })?;
-
-        if interface_kv.key != consts::INTERFACE_ATTR_NAME {
+        if let Some(key) = unknown_key {
             return Err(Error::new(
-                self.ident.span(),
-                format!(
-                    "Unable to find interface. Please add a '#[{}({} = <your trait>)]'",
-                    consts::ATTR_NAME,
-                    consts::INTERFACE_ATTR_NAME
-                ),
+                key.span(),
+                format!("Unknown shaku attribute: '{}'", key),
             ));
         }
 
         Ok(MetaData {
             identifier: self.ident.clone(),
             generics: self.generics.clone(),
-            interface: interface_kv.value,
+            interface,
             visibility: self.vis.clone(),
+            try_build,
+            on_start,
+            on_stop,
+            validate,
         })
     }
 }
@@ -5,9 +5,10 @@ use crate::structures::module::{
 };
 use std::collections::HashSet;
 use std::hash::Hash;
+use syn::ext::IdentExt;
 use syn::parse::{Parse, ParseStream};
 use syn::spanned::Spanned;
-use syn::{Attribute, Error, Generics};
+use syn::{AttrStyle, Attribute, Error, Generics, Ident, Path};
 
 impl Parse for ModuleData {
     fn parse(input: ParseStream) -> syn::Result<Self> {
@@ -68,24 +69,35 @@ impl Parse for Submodule {
             return Err(content.error("expected end of input"));
         }
 
-        // Make sure components don't use attributes
+        // Make sure components only use the named, multi, key, or reloadable
+        // attribute
         for component in &services.components.items {
-            if !component.attributes.is_empty() {
+            let is_valid = component.attributes.len() <= 1
+                && !component.is_lazy()
+                && !component.is_async()
+                && component.multi_interface().is_none()
+                && component.key().is_none();
+
+            if !is_valid {
                 return Err(syn::Error::new(
                     component.ty.span(),
-                    "Submodule components cannot have attributes",
+                    "Submodule components can only have the named, multi, key, or reloadable attribute",
                 ));
             }
         }
 
-        // Make sure providers don't use attributes
-        for provider in &services.providers.items {
-            if !provider.attributes.is_empty() {
-                return Err(syn::Error::new(
-                    provider.ty.span(),
-                    "Submodule providers cannot have attributes",
-                ));
-            }
+        // Scopes are created from the root module, so submodule providers
+        // cannot be scoped
+        if let Some(provider) = services
+            .providers
+            .items
+            .iter()
+            .find(|provider| provider.is_scoped())
+        {
+            return Err(syn::Error::new(
+                provider.ty.span(),
+                "Submodule providers cannot be scoped",
+            ));
         }
 
         Ok(Submodule { ty, services })
@@ -123,7 +135,7 @@ where
     Attribute: Parser<A>,
 {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let unparsed_attrs = input.call(Attribute::parse_outer)?;
+        let unparsed_attrs = parse_item_attributes(input)?;
         let mut attributes = HashSet::with_capacity(unparsed_attrs.len());
 
         // Parse attributes and check for duplicates
@@ -144,10 +156,52 @@ where
     }
 }
 
+/// Parse the outer attributes of a module item. Unlike
+/// `Attribute::parse_outer`, keywords are allowed as the attribute name so
+/// `#[async]` can be used.
+fn parse_item_attributes(input: ParseStream) -> syn::Result<Vec<Attribute>> {
+    let mut attributes = Vec::new();
+
+    while input.peek(syn::Token![#]) {
+        let content;
+        let pound_token = input.parse()?;
+        let bracket_token = syn::bracketed!(content in input);
+        let path = if content.peek(Ident::peek_any) && !content.peek2(syn::Token![::]) {
+            Path::from(content.call(Ident::parse_any)?)
+        } else {
+            content.call(Path::parse_mod_style)?
+        };
+
+        attributes.push(Attribute {
+            pound_token,
+            style: AttrStyle::Outer,
+            bracket_token,
+            path,
+            tokens: content.parse()?,
+        });
+    }
+
+    Ok(attributes)
+}
+
 impl Parser<ComponentAttribute> for Attribute {
     fn parse_as(&self) -> syn::Result<ComponentAttribute> {
         if self.path.is_ident("lazy") && self.tokens.is_empty() {
             Ok(ComponentAttribute::Lazy)
+        } else if self.path.is_ident("async") && self.tokens.is_empty() {
+            Ok(ComponentAttribute::Async)
+        } else if self.path.is_ident("reloadable") && self.tokens.is_empty() {
+            Ok(ComponentAttribute::Reloadable)
+        } else if self.path.is_ident("named") {
+            Ok(ComponentAttribute::Named(self.parse_args()?))
+        } else if self.path.is_ident("multi") && self.tokens.is_empty() {
+            Ok(ComponentAttribute::Multi(None))
+        } else if self.path.is_ident("multi") {
+            Ok(ComponentAttribute::Multi(Some(self.parse_args()?)))
+        } else if self.path.is_ident("key") && self.tokens.is_empty() {
+            Ok(ComponentAttribute::Key(None))
+        } else if self.path.is_ident("key") {
+            Ok(ComponentAttribute::Key(Some(self.parse_args()?)))
         } else {
             Err(Error::new(self.span(), "Unknown attribute".to_string()))
         }
@@ -156,6 +210,12 @@ impl Parser<ComponentAttribute> for Attribute {
 
 impl Parser<ProviderAttribute> for Attribute {
     fn parse_as(&self) -> syn::Result<ProviderAttribute> {
-        Err(Error::new(self.span(), "Providers cannot have attributes"))
+        if self.path.is_ident("async") && self.tokens.is_empty() {
+            Ok(ProviderAttribute::Async)
+        } else if self.path.is_ident("scoped") && self.tokens.is_empty() {
+            Ok(ProviderAttribute::Scoped)
+        } else {
+            Err(Error::new(self.span(), "Unknown attribute".to_string()))
+        }
     }
 }
@@ -1,8 +1,13 @@
 use crate::consts;
-use crate::parser::{get_shaku_attribute, KeyValue, Parser};
+use crate::parser::Parser;
 use crate::structures::service::{Property, PropertyDefault, PropertyType};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::{Attribute, Error, Expr, Field, GenericArgument, Path, PathArguments, Type};
+use syn::{
+    Attribute, Error, Expr, ExprPath, Field, GenericArgument, Ident, LitStr, Path, PathArguments,
+    Type,
+};
 
 fn check_for_attr(attr_name: &str, attrs: &[Attribute]) -> bool {
     attrs.iter().any(|a| {
@@ -13,11 +18,195 @@ fn check_for_attr(attr_name: &str, attrs: &[Attribute]) -> bool {
     })
 }
 
+/// An item in a `#[shaku(inject, named = Tag)]` attribute
+enum InjectArgument {
+    Inject,
+    Named(Type),
+}
+
+impl Parse for InjectArgument {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key: Ident = input.parse()?;
+
+        if key == consts::INJECT_ATTR_NAME {
+            Ok(InjectArgument::Inject)
+        } else if key == consts::NAMED_ATTR_NAME {
+            input.parse::<syn::Token![=]>()?;
+            Ok(InjectArgument::Named(input.parse()?))
+        } else {
+            Err(Error::new(key.span(), "Expected inject or named"))
+        }
+    }
+}
+
+/// Find the `#[shaku(inject, named = Tag)]` attribute. Returns the tag, or
+/// `None` if the property is not a named component.
+fn get_named_tag(attrs: &[Attribute]) -> syn::Result<Option<Type>> {
+    for attr in attrs.iter().filter(|a| a.path.is_ident(consts::ATTR_NAME)) {
+        let arguments = match attr
+            .parse_args_with(Punctuated::<InjectArgument, syn::Token![,]>::parse_terminated)
+        {
+            Ok(arguments) => arguments,
+            Err(_) => continue,
+        };
+
+        let mut is_injected = false;
+        let mut tag = None;
+        for argument in arguments {
+            match argument {
+                InjectArgument::Inject => is_injected = true,
+                InjectArgument::Named(ty) => tag = Some(ty),
+            }
+        }
+
+        match (is_injected, tag) {
+            (true, Some(tag)) => return Ok(Some(tag)),
+            (false, Some(tag)) => {
+                return Err(Error::new(
+                    tag.span(),
+                    format!(
+                        "Named properties must be injected. Example: #[{}({}, {} = <your tag>)]",
+                        consts::ATTR_NAME,
+                        consts::INJECT_ATTR_NAME,
+                        consts::NAMED_ATTR_NAME
+                    ),
+                ))
+            }
+            (_, None) => {}
+        }
+    }
+
+    Ok(None)
+}
+
+/// An item in a `#[shaku(default = ..., env = "...", validate = ...)]`
+/// attribute on a parameter
+enum ParameterArgument {
+    Default(Option<Expr>),
+    Env(LitStr),
+    Validate(ExprPath),
+    Unknown(Ident),
+}
+
+impl Parse for ParameterArgument {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key: Ident = input.parse()?;
+
+        if key == consts::DEFAULT_ATTR_NAME {
+            if input.peek(syn::Token![=]) {
+                input.parse::<syn::Token![=]>()?;
+                Ok(ParameterArgument::Default(Some(input.parse()?)))
+            } else {
+                Ok(ParameterArgument::Default(None))
+            }
+        } else if key == consts::ENV_ATTR_NAME {
+            input.parse::<syn::Token![=]>()?;
+            Ok(ParameterArgument::Env(input.parse()?))
+        } else if key == consts::VALIDATE_ATTR_NAME {
+            input.parse::<syn::Token![=]>()?;
+            Ok(ParameterArgument::Validate(input.parse()?))
+        } else {
+            input.parse::<syn::Token![=]>()?;
+            input.parse::<Expr>()?;
+            Ok(ParameterArgument::Unknown(key))
+        }
+    }
+}
+
+/// Parse the `#[shaku(...)]` attributes of a parameter. Returns the default
+/// value, the environment variable, and the validation function of the
+/// parameter.
+fn get_parameter_attributes(
+    attrs: &[Attribute],
+) -> syn::Result<(PropertyDefault, Option<LitStr>, Option<ExprPath>)> {
+    let mut default = PropertyDefault::NoDefault;
+    let mut env = None;
+    let mut validate = None;
+
+    for attr in attrs.iter().filter(|a| a.path.is_ident(consts::ATTR_NAME)) {
+        let arguments = attr
+            .parse_args_with(Punctuated::<ParameterArgument, syn::Token![,]>::parse_terminated)
+            .map_err(|_| {
+                Error::new(
+                    attr.span(),
+                    format!("Unknown attribute: 'shaku{}'", attr.tokens),
+                )
+            })?;
+
+        for argument in arguments {
+            match argument {
+                ParameterArgument::Default(Some(expr)) => {
+                    default = PropertyDefault::Provided(Box::new(expr))
+                }
+                ParameterArgument::Default(None) => default = PropertyDefault::NotProvided,
+                ParameterArgument::Env(variable) => env = Some(variable),
+                ParameterArgument::Validate(path) => validate = Some(path),
+                ParameterArgument::Unknown(key) => {
+                    return Err(Error::new(
+                        key.span(),
+                        format!("Unknown shaku attribute: '{}'", key),
+                    ))
+                }
+            }
+        }
+    }
+
+    Ok((default, env, validate))
+}
+
+/// Get the item type of a `Vec<T>` type
+fn vec_item_type(ty: &Type) -> Option<&Type> {
+    let segment = match ty {
+        Type::Path(path) => path.path.segments.last()?,
+        _ => return None,
+    };
+
+    if segment.ident != "Vec" {
+        return None;
+    }
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(abpd) => match abpd.args.first()? {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Check if the type is an `Arc<T>`
+fn is_arc(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path.path.segments[0].ident == "Arc",
+        _ => false,
+    }
+}
+
+/// Get the value type of a `HashMap<K, V>` type
+fn map_value_type(ty: &Type) -> Option<&Type> {
+    let segment = match ty {
+        Type::Path(path) => path.path.segments.last()?,
+        _ => return None,
+    };
+
+    if segment.ident != "HashMap" {
+        return None;
+    }
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(abpd) => match abpd.args.iter().nth(1)? {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 impl Parser<Property> for Field {
     fn parse_as(&self) -> syn::Result<Property> {
-        let is_injected = check_for_attr(consts::INJECT_ATTR_NAME, &self.attrs);
+        let named = get_named_tag(&self.attrs)?;
+        let is_injected = check_for_attr(consts::INJECT_ATTR_NAME, &self.attrs) || named.is_some();
         let is_provided = check_for_attr(consts::PROVIDE_ATTR_NAME, &self.attrs);
-        let has_default = check_for_attr(consts::DEFAULT_ATTR_NAME, &self.attrs);
 
         let property_name = self.ident.clone().ok_or_else(|| {
             Error::new(self.span(), "Struct properties must be named".to_string())
@@ -31,42 +220,38 @@ impl Parser<Property> for Field {
 
         let property_type = match (is_injected, is_provided) {
             (false, false) => {
-                let property_default = get_shaku_attribute(&self.attrs)
-                    .map(|attr| match attr.parse_args::<KeyValue<Expr>>().ok() {
-                        Some(inner) => {
-                            if inner.key == consts::DEFAULT_ATTR_NAME {
-                                Ok(PropertyDefault::Provided(Box::new(inner.value)))
-                            } else {
-                                Err(Error::new(
-                                    inner.key.span(),
-                                    format!("Unknown shaku attribute: '{}'", inner.key),
-                                ))
-                            }
-                        }
-                        None => {
-                            if has_default {
-                                Ok(PropertyDefault::NotProvided)
-                            } else {
-                                Err(Error::new(
-                                    attr.span(),
-                                    format!("Unknown attribute: 'shaku{}'", attr.tokens),
-                                ))
-                            }
-                        }
-                    })
-                    .transpose()?
-                    .unwrap_or(PropertyDefault::NoDefault);
+                let (property_default, env, validate) = get_parameter_attributes(&self.attrs)?;
 
                 return Ok(Property {
                     property_name,
                     ty: self.ty.clone(),
                     property_type: PropertyType::Parameter,
+                    named: None,
                     default: property_default,
+                    env,
+                    validate,
                     doc_comment,
                 });
             }
+            // Provided services in an Arc are shared within a scope
+            (false, true) if is_arc(&self.ty) => PropertyType::Scoped,
             (false, true) => PropertyType::Provided,
-            (true, false) => PropertyType::Component,
+            (true, false) => {
+                let is_collection =
+                    vec_item_type(&self.ty).is_some() || map_value_type(&self.ty).is_some();
+
+                match (is_collection, &named) {
+                    (true, Some(tag)) => {
+                        return Err(Error::new(
+                            tag.span(),
+                            "Named components cannot be injected as a collection",
+                        ))
+                    }
+                    (true, None) if vec_item_type(&self.ty).is_some() => PropertyType::Components,
+                    (true, None) => PropertyType::ComponentMap,
+                    (false, _) => PropertyType::Component,
+                }
+            }
             (true, true) => {
                 return Err(Error::new(
                     property_name.span(),
@@ -75,13 +260,23 @@ impl Parser<Property> for Field {
             }
         };
 
-        match &self.ty {
+        // Collections of components are wrapped in a Vec or HashMap
+        let wrapper_ty = match property_type {
+            PropertyType::Components => vec_item_type(&self.ty).unwrap(),
+            PropertyType::ComponentMap => map_value_type(&self.ty).unwrap(),
+            _ => &self.ty,
+        };
+
+        match wrapper_ty {
             Type::Path(path)
                 if {
                     // Make sure it has the right wrapper type
                     let name = &path.path.segments[0].ident;
                     match property_type {
-                        PropertyType::Component => name == "Arc",
+                        PropertyType::Component
+                        | PropertyType::Components
+                        | PropertyType::ComponentMap
+                        | PropertyType::Scoped => name == "Arc",
                         PropertyType::Provided => name == "Box",
                         PropertyType::Parameter => unreachable!(),
                     }
@@ -118,24 +313,29 @@ impl Parser<Property> for Field {
                     property_name,
                     ty: (*interface_type).clone(),
                     property_type,
+                    named,
                     default: PropertyDefault::NotProvided,
+                    env: None,
+                    validate: None,
                     doc_comment,
                 })
             }
 
             _ => match property_type {
-                PropertyType::Component => Err(Error::new(
-                    property_name.span(),
-                    format!(
-                        "Found non-Arc type annotated with #[{}({})]",
-                        consts::ATTR_NAME,
-                        consts::INJECT_ATTR_NAME
-                    ),
-                )),
-                PropertyType::Provided => Err(Error::new(
+                PropertyType::Component | PropertyType::Components | PropertyType::ComponentMap => {
+                    Err(Error::new(
+                        property_name.span(),
+                        format!(
+                            "Found non-Arc type annotated with #[{}({})]",
+                            consts::ATTR_NAME,
+                            consts::INJECT_ATTR_NAME
+                        ),
+                    ))
+                }
+                PropertyType::Provided | PropertyType::Scoped => Err(Error::new(
                     property_name.span(),
                     format!(
-                        "Found non-Box type annotated with #[{}({})]",
+                        "Found non-Box and non-Arc type annotated with #[{}({})]",
                         consts::ATTR_NAME,
                         consts::PROVIDE_ATTR_NAME
                     ),
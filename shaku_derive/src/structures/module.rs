@@ -2,12 +2,14 @@
 
 use crate::parser::Parser;
 use std::collections::HashSet;
-use std::hash::Hash;
+use std::hash::{Hash, Hasher};
+use std::mem;
 use syn::parse::Parse;
 use syn::punctuated::Punctuated;
-use syn::{token, Attribute, Generics, Ident, Type, Visibility};
+use syn::{token, Attribute, Generics, Ident, LitStr, Type, Visibility};
 
 pub type ComponentItem = ModuleItem<ComponentAttribute>;
+pub type ProviderItem = ModuleItem<ProviderAttribute>;
 
 mod kw {
     syn::custom_keyword!(components);
@@ -76,16 +78,125 @@ impl ModuleItem<ComponentAttribute> {
     pub fn is_lazy(&self) -> bool {
         self.attributes.contains(&ComponentAttribute::Lazy)
     }
+
+    /// Check if a component is marked with `#[async]`
+    pub fn is_async(&self) -> bool {
+        self.attributes.contains(&ComponentAttribute::Async)
+    }
+
+    /// Check if a component is marked with `#[reloadable]`
+    pub fn is_reloadable(&self) -> bool {
+        self.attributes.contains(&ComponentAttribute::Reloadable)
+    }
+
+    /// Get the tag of a component marked with `#[named(...)]`
+    pub fn named(&self) -> Option<&Type> {
+        self.attributes
+            .iter()
+            .find_map(|attribute| match attribute {
+                ComponentAttribute::Named(tag) => Some(tag),
+                _ => None,
+            })
+    }
+
+    /// Check if a component is marked with `#[multi]` or `#[multi(...)]`
+    pub fn is_multi(&self) -> bool {
+        self.attributes.contains(&ComponentAttribute::Multi(None))
+    }
+
+    /// Get the interface of a component marked with `#[multi(...)]`
+    pub fn multi_interface(&self) -> Option<&Type> {
+        self.attributes
+            .iter()
+            .find_map(|attribute| match attribute {
+                ComponentAttribute::Multi(interface) => interface.as_ref(),
+                _ => None,
+            })
+    }
+
+    /// Check if a component is marked with `#[key]` or `#[key(...)]`
+    pub fn is_keyed(&self) -> bool {
+        self.attributes.contains(&ComponentAttribute::Key(None))
+    }
+
+    /// Get the key of a component marked with `#[key(...)]`
+    pub fn key(&self) -> Option<&LitStr> {
+        self.attributes
+            .iter()
+            .find_map(|attribute| match attribute {
+                ComponentAttribute::Key(key) => key.as_ref(),
+                _ => None,
+            })
+    }
+
+    /// Get the interface of the map a component is added to, if it is marked
+    /// with `#[multi(...)]` and `#[key(...)]`
+    pub fn map_interface(&self) -> Option<&Type> {
+        if self.is_keyed() {
+            self.multi_interface()
+        } else {
+            None
+        }
+    }
+
+    /// Get the interface of the set a component is added to, if it is marked
+    /// with `#[multi(...)]` but not `#[key(...)]`
+    pub fn set_interface(&self) -> Option<&Type> {
+        if self.is_keyed() {
+            None
+        } else {
+            self.multi_interface()
+        }
+    }
 }
 
 /// Valid component attributes
-#[derive(Debug, Eq, PartialEq, Hash)]
+#[derive(Debug)]
 pub enum ComponentAttribute {
     Lazy,
+    Async,
+    Reloadable,
+    Named(Type),
+    /// The interface is only given for the module's own components, since
+    /// submodule components are already interfaces.
+    Multi(Option<Type>),
+    /// The key is only given for the module's own components, since submodule
+    /// maps are imported as a whole.
+    Key(Option<LitStr>),
+}
+
+// Attributes are compared by kind (ignoring the tag or interface), so
+// duplicate `#[named]` attributes are detected and `#[lazy]` can be looked up
+// directly.
+impl PartialEq for ComponentAttribute {
+    fn eq(&self, other: &Self) -> bool {
+        mem::discriminant(self) == mem::discriminant(other)
+    }
+}
+
+impl Eq for ComponentAttribute {}
+
+impl Hash for ComponentAttribute {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state)
+    }
+}
+
+impl ModuleItem<ProviderAttribute> {
+    /// Check if a provider is marked with `#[async]`
+    pub fn is_async(&self) -> bool {
+        self.attributes.contains(&ProviderAttribute::Async)
+    }
+
+    /// Check if a provider is marked with `#[scoped]`
+    pub fn is_scoped(&self) -> bool {
+        self.attributes.contains(&ProviderAttribute::Scoped)
+    }
 }
 
 /// Valid provider attributes
 #[derive(Debug, Eq, PartialEq, Hash)]
 pub enum ProviderAttribute {
-    // None currently
+    Async,
+    Scoped,
 }
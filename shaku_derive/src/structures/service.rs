@@ -1,7 +1,7 @@
 //! Structures to hold useful service data parsed from syn::DeriveInput
 
 use crate::parser::Parser;
-use syn::{Attribute, DeriveInput, Expr, Generics, Ident, Type, Visibility};
+use syn::{Attribute, DeriveInput, Expr, ExprPath, Generics, Ident, LitStr, Type, Visibility};
 
 /// The main data structure, representing the data required to implement
 /// Component or Provider.
@@ -27,13 +27,27 @@ pub struct MetaData {
     pub interface: Type,
     pub generics: Generics,
     pub visibility: Visibility,
+    /// A function which finishes creating the service, and may fail
+    pub try_build: Option<ExprPath>,
+    /// A function which is called after the component is built
+    pub on_start: Option<ExprPath>,
+    /// A function which is called when the module is shut down
+    pub on_stop: Option<ExprPath>,
+    /// A function which validates the parameters struct as a whole
+    pub validate: Option<ExprPath>,
 }
 
 #[derive(Copy, Clone, Debug)]
 pub enum PropertyType {
     Parameter,
     Component,
+    /// All of the components in a set (`Vec<Arc<dyn Trait>>`)
+    Components,
+    /// All of the components in a map (`HashMap<&'static str, Arc<dyn Trait>>`)
+    ComponentMap,
     Provided,
+    /// A provided service which is shared within a scope (`Arc<dyn Trait>`)
+    Scoped,
 }
 
 /// Holds information about a service property.
@@ -44,17 +58,48 @@ pub struct Property {
     /// Otherwise, the interface type (the type inside the Arc or Box).
     pub ty: Type,
     pub property_type: PropertyType,
+    /// The tag of a named component dependency
+    pub named: Option<Type>,
     pub default: PropertyDefault,
+    /// The environment variable of a `#[shaku(env = "...")]` parameter
+    pub env: Option<LitStr>,
+    /// The function of a `#[shaku(validate = ...)]` parameter
+    pub validate: Option<ExprPath>,
     pub doc_comment: Vec<Attribute>,
 }
 
 impl Property {
     pub fn is_service(&self) -> bool {
         match self.property_type {
-            PropertyType::Component | PropertyType::Provided => true,
+            PropertyType::Component
+            | PropertyType::Components
+            | PropertyType::ComponentMap
+            | PropertyType::Provided
+            | PropertyType::Scoped => true,
             PropertyType::Parameter => false,
         }
     }
+
+    /// Check if this is a provided service (scoped or not)
+    pub fn is_provided(&self) -> bool {
+        match self.property_type {
+            PropertyType::Provided | PropertyType::Scoped => true,
+            PropertyType::Parameter
+            | PropertyType::Component
+            | PropertyType::Components
+            | PropertyType::ComponentMap => false,
+        }
+    }
+
+    /// Check if this is a parameter without a default value. Parameters
+    /// which are read from an environment variable are checked when the
+    /// variable is read instead.
+    pub fn is_required_parameter(&self) -> bool {
+        match self.default {
+            PropertyDefault::NoDefault => !self.is_service() && self.env.is_none(),
+            PropertyDefault::Provided(_) | PropertyDefault::NotProvided => false,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
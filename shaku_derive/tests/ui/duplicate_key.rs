@@ -0,0 +1,27 @@
+//! Keys must be unique within a map
+
+use shaku::{module, Component, Interface};
+
+trait ComponentTrait: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = ComponentTrait)]
+struct ComponentImpl1;
+impl ComponentTrait for ComponentImpl1 {}
+
+#[derive(Component)]
+#[shaku(interface = ComponentTrait)]
+struct ComponentImpl2;
+impl ComponentTrait for ComponentImpl2 {}
+
+module! {
+    TestModule {
+        components = [
+            #[multi(dyn ComponentTrait)] #[key("a")] ComponentImpl1,
+            #[multi(dyn ComponentTrait)] #[key("a")] ComponentImpl2
+        ],
+        providers = []
+    }
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+//! Interface proxies can only delegate methods which take `&self`
+
+use shaku::Interface;
+
+#[shaku::interface]
+trait Counter: Interface {
+    fn increment(&mut self);
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+//! Keyed components must specify the interface of their map
+
+use shaku::{module, Component, Interface};
+
+trait ComponentTrait: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = ComponentTrait)]
+struct ComponentImpl;
+impl ComponentTrait for ComponentImpl {}
+
+module! {
+    TestModule {
+        components = [#[key("a")] ComponentImpl],
+        providers = []
+    }
+}
+
+fn main() {}
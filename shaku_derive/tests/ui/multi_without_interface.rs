@@ -0,0 +1,19 @@
+//! Multi-bound components must specify their interface
+
+use shaku::{module, Component, Interface};
+
+trait ComponentTrait: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = ComponentTrait)]
+struct ComponentImpl;
+impl ComponentTrait for ComponentImpl {}
+
+module! {
+    TestModule {
+        components = [#[multi] ComponentImpl],
+        providers = []
+    }
+}
+
+fn main() {}
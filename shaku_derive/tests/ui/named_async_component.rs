@@ -0,0 +1,20 @@
+//! Named components cannot be async
+
+use shaku::{module, Component, Interface};
+
+trait ComponentTrait: Interface {}
+struct Tag;
+
+#[derive(Component)]
+#[shaku(interface = ComponentTrait)]
+struct ComponentImpl;
+impl ComponentTrait for ComponentImpl {}
+
+module! {
+    TestModule {
+        components = [#[named(Tag)] #[async] ComponentImpl],
+        providers = []
+    }
+}
+
+fn main() {}
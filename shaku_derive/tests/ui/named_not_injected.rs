@@ -0,0 +1,18 @@
+//! Only injected properties can be named
+
+use shaku::{Component, Interface};
+use std::sync::Arc;
+
+trait DependencyTrait: Interface {}
+trait ComponentTrait: Interface {}
+struct Tag;
+
+#[derive(Component)]
+#[shaku(interface = ComponentTrait)]
+struct ComponentImpl {
+    #[shaku(named = Tag)]
+    dependency: Arc<dyn DependencyTrait>,
+}
+impl ComponentTrait for ComponentImpl {}
+
+fn main() {}
@@ -1,7 +1,7 @@
-//! A non-Box property cannot be provided
+//! Only Box and Arc (scoped) properties can be provided
 
 use shaku::{Component, Interface, Provider};
-use std::sync::Arc;
+use std::rc::Rc;
 
 trait DependencyTrait: Interface {}
 trait ProviderTrait {}
@@ -15,7 +15,7 @@ impl DependencyTrait for DependencyImpl {}
 #[shaku(interface = ProviderTrait)]
 struct ProviderImpl {
     #[shaku(provide)]
-    dependency: Arc<dyn DependencyTrait>,
+    dependency: Rc<dyn DependencyTrait>,
 }
 impl ProviderTrait for ProviderImpl {}
 
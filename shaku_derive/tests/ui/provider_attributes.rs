@@ -1,4 +1,4 @@
-//! Providers cannot have module attributes
+//! Providers cannot have module attributes other than `#[async]`
 
 use shaku::{module, Provider};
 
@@ -0,0 +1,16 @@
+//! Providers cannot have lifecycle hooks
+
+use shaku::Provider;
+
+trait ProviderTrait {}
+
+#[derive(Provider)]
+#[shaku(interface = ProviderTrait, on_stop = ProviderImpl::stop)]
+struct ProviderImpl;
+impl ProviderTrait for ProviderImpl {}
+
+impl ProviderImpl {
+    fn stop(&self) {}
+}
+
+fn main() {}
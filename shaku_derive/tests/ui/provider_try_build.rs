@@ -0,0 +1,12 @@
+//! Providers cannot use the try_build attribute
+
+use shaku::Provider;
+
+trait ProviderTrait {}
+
+#[derive(Provider)]
+#[shaku(interface = ProviderTrait, try_build = ProviderImpl::validate)]
+struct ProviderImpl;
+impl ProviderTrait for ProviderImpl {}
+
+fn main() {}
@@ -0,0 +1,19 @@
+//! Reloadable components cannot be lazy
+
+use shaku::{module, Component, Interface};
+
+trait ComponentTrait: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = ComponentTrait)]
+struct ComponentImpl;
+impl ComponentTrait for ComponentImpl {}
+
+module! {
+    TestModule {
+        components = [#[reloadable] #[lazy] ComponentImpl],
+        providers = []
+    }
+}
+
+fn main() {}
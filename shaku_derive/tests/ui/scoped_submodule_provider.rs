@@ -0,0 +1,31 @@
+//! Submodule providers cannot be scoped
+
+use shaku::{module, Interface, Provider};
+
+trait ProviderTrait: Interface {}
+
+#[derive(Provider)]
+#[shaku(interface = ProviderTrait)]
+struct ProviderImpl;
+impl ProviderTrait for ProviderImpl {}
+
+module! {
+    Submodule {
+        components = [],
+        providers = [#[scoped] ProviderImpl]
+    }
+}
+
+module! {
+    TestModule {
+        components = [],
+        providers = [],
+
+        use Submodule {
+            components = [],
+            providers = [#[scoped] dyn ProviderTrait]
+        }
+    }
+}
+
+fn main() {}
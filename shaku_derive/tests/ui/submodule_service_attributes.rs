@@ -1,4 +1,4 @@
-//! Submodule services cannot have attributes
+//! Submodule components can only be `#[named]` or `#[multi]`, and providers can only be `#[async]`
 
 use shaku::{module, Component, Interface, Provider};
 
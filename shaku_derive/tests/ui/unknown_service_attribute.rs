@@ -0,0 +1,12 @@
+//! Unknown keys in the service's shaku attribute are rejected
+
+use shaku::{Component, Interface};
+
+trait ComponentTrait: Interface {}
+
+#[derive(Component)]
+#[shaku(interface = ComponentTrait, unknown = 42)]
+struct ComponentImpl;
+impl ComponentTrait for ComponentImpl {}
+
+fn main() {}
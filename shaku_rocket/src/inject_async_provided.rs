@@ -0,0 +1,91 @@
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use rocket::outcome::try_outcome;
+use rocket::request::{FromRequest, Outcome};
+use rocket::{http::Status, Request};
+use shaku::{HasAsyncProvider, ModuleInterface};
+
+use crate::get_module_from_state;
+
+/// Used to create a service from a shaku `Module` using an async provider.
+/// The module should be stored in Rocket's state, in a `Box` (It could be
+/// `Box<dyn MyModule>` if the module implementation changes at runtime).
+/// Use this `InjectAsyncProvided` struct as a request guard.
+///
+/// # Example
+/// ```rust
+/// #[macro_use] extern crate rocket;
+///
+/// use shaku::{module, AsyncProvider};
+/// use shaku_rocket::InjectAsyncProvided;
+///
+/// trait HelloWorld: Send + Sync {
+///     fn greet(&self) -> String;
+/// }
+///
+/// #[derive(AsyncProvider)]
+/// #[shaku(interface = HelloWorld)]
+/// struct HelloWorldImpl;
+///
+/// impl HelloWorld for HelloWorldImpl {
+///     fn greet(&self) -> String {
+///         "Hello, world!".to_owned()
+///     }
+/// }
+///
+/// module! {
+///     HelloModule {
+///         components = [],
+///         providers = [#[async] HelloWorldImpl]
+///     }
+/// }
+///
+/// #[get("/")]
+/// fn hello(hello_world: InjectAsyncProvided<HelloModule, dyn HelloWorld>) -> String {
+///     hello_world.greet()
+/// }
+///
+/// # fn main() { // We don't actually want to launch the server in an example.
+/// #[rocket::launch]
+/// fn rocket() -> _ {
+///     let module = HelloModule::builder().build();
+///
+///     rocket::build()
+///         .manage(Box::new(module))
+///         .mount("/", routes![hello])
+/// }
+/// # }
+/// ```
+pub struct InjectAsyncProvided<M: ModuleInterface + HasAsyncProvider<I> + ?Sized, I: ?Sized>(
+    Box<I>,
+    PhantomData<M>,
+);
+
+#[rocket::async_trait]
+impl<'r, M: ModuleInterface + HasAsyncProvider<I> + ?Sized, I: ?Sized> FromRequest<'r>
+    for InjectAsyncProvided<M, I>
+{
+    type Error = String;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let module = try_outcome!(get_module_from_state::<M>(request).await);
+
+        let service_result = module.inner().provide_async().await;
+
+        match service_result {
+            Ok(service) => Outcome::Success(InjectAsyncProvided(service, PhantomData)),
+            Err(e) => Outcome::Error((Status::InternalServerError, e.to_string())),
+        }
+    }
+}
+
+impl<M: ModuleInterface + HasAsyncProvider<I> + ?Sized, I: ?Sized> Deref
+    for InjectAsyncProvided<M, I>
+{
+    type Target = I;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.deref()
+    }
+}
@@ -1,13 +1,16 @@
 //! This crate provides integration between the `shaku` and `rocket` crates.
 //!
-//! See [`Inject`] and [`InjectProvided`] for details.
+//! See [`Inject`], [`InjectProvided`] and [`InjectAsyncProvided`] for details.
 //!
 //! [`Inject`]: struct.Inject.html
 //! [`InjectProvided`]: struct.InjectProvided.html
+//! [`InjectAsyncProvided`]: struct.InjectAsyncProvided.html
 
+mod inject_async_provided;
 mod inject_component;
 mod inject_provided;
 
+pub use inject_async_provided::InjectAsyncProvided;
 pub use inject_component::Inject;
 pub use inject_provided::InjectProvided;
 
@@ -1,12 +1,16 @@
-//! Module interfaces can be used with `Inject` and `InjectProvided`.
+//! Module interfaces can be used with `Inject`, `InjectProvided` and `InjectAsyncProvided`.
 //! The module itself would be stored in state as `Box<dyn MyModule>`.
 #![allow(clippy::let_unit_value)]
 
-use shaku::{module, Component, HasComponent, HasProvider, Interface, Provider};
-use shaku_rocket::{Inject, InjectProvided};
+use shaku::{
+    module, AsyncProvider, Component, HasAsyncProvider, HasComponent, HasProvider, Interface,
+    Provider,
+};
+use shaku_rocket::{Inject, InjectAsyncProvided, InjectProvided};
 
 trait MyComponent: Interface {}
 trait MyProvider {}
+trait MyAsyncProvider: Send {}
 
 #[derive(Component)]
 #[shaku(interface = MyComponent)]
@@ -18,12 +22,20 @@ impl MyComponent for MyComponentImpl {}
 struct MyProviderImpl;
 impl MyProvider for MyProviderImpl {}
 
-trait MyModule: HasComponent<dyn MyComponent> + HasProvider<dyn MyProvider> {}
+#[derive(AsyncProvider)]
+#[shaku(interface = MyAsyncProvider)]
+struct MyAsyncProviderImpl;
+impl MyAsyncProvider for MyAsyncProviderImpl {}
+
+trait MyModule:
+    HasComponent<dyn MyComponent> + HasProvider<dyn MyProvider> + HasAsyncProvider<dyn MyAsyncProvider>
+{
+}
 
 module! {
     MyModuleImpl: MyModule {
         components = [MyComponentImpl],
-        providers = [MyProviderImpl]
+        providers = [MyProviderImpl, #[async] MyAsyncProviderImpl]
     }
 }
 
@@ -31,6 +43,7 @@ module! {
 #[rocket::get("/")]
 fn index(
     _component: Inject<dyn MyModule, dyn MyComponent>,
+    _async_provider: InjectAsyncProvided<dyn MyModule, dyn MyAsyncProvider>,
     _provider: InjectProvided<dyn MyModule, dyn MyProvider>,
 ) {
 }